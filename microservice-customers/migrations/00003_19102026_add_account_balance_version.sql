@@ -0,0 +1,3 @@
+-- Version of the ledger balance last applied to the account. Balance-changed
+-- events carry the ledger's version and are only applied when newer.
+ALTER TABLE accounts ADD COLUMN IF NOT EXISTS balance_version BIGINT NOT NULL DEFAULT 0;
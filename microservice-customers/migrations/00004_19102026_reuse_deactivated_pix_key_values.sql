@@ -0,0 +1,6 @@
+-- A deactivated key frees its value: only active keys must be unique.
+ALTER TABLE pix_keys DROP CONSTRAINT IF EXISTS pix_keys_key_value_key;
+
+CREATE UNIQUE INDEX IF NOT EXISTS uq_pix_keys_active_key_value
+    ON pix_keys(key_value)
+    WHERE is_active;
@@ -0,0 +1,31 @@
+-- Portability and ownership claims over Pix keys held by another account.
+CREATE TYPE key_claim_type AS ENUM ('PORTABILITY', 'OWNERSHIP');
+CREATE TYPE key_claim_status AS ENUM ('OPEN', 'WAITING_RESOLUTION', 'CONFIRMED', 'CANCELLED', 'COMPLETED');
+
+CREATE TABLE key_claims (
+    id                  UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    claim_type          key_claim_type NOT NULL,
+    status              key_claim_status NOT NULL DEFAULT 'OPEN',
+    key_type            pix_key_type NOT NULL,
+    key_value           VARCHAR(255) NOT NULL,
+    pix_key_id          UUID NOT NULL REFERENCES pix_keys(id),
+    claimer_account_id  UUID NOT NULL REFERENCES accounts(id) ON DELETE CASCADE,
+    donor_account_id    UUID NOT NULL REFERENCES accounts(id) ON DELETE CASCADE,
+    notified_at         TIMESTAMPTZ,
+    resolution_deadline TIMESTAMPTZ,
+    cancelled_by        VARCHAR(10),
+    completed_at        TIMESTAMPTZ,
+    created_at          TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at          TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- At most one claim in progress per key value.
+CREATE UNIQUE INDEX uq_key_claims_in_progress
+    ON key_claims(key_value)
+    WHERE status IN ('OPEN', 'WAITING_RESOLUTION', 'CONFIRMED');
+
+CREATE INDEX idx_key_claims_claimer_account_id ON key_claims(claimer_account_id);
+CREATE INDEX idx_key_claims_donor_account_id ON key_claims(donor_account_id);
+CREATE INDEX idx_key_claims_resolution_deadline
+    ON key_claims(resolution_deadline)
+    WHERE status = 'WAITING_RESOLUTION';
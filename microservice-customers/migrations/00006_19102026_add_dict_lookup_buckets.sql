@@ -0,0 +1,14 @@
+-- Anti-scraping token bucket for DICT key lookups, one per requesting
+-- customer. Lookups take tokens; only successful transfers put them back.
+CREATE TABLE dict_lookup_buckets (
+    customer_id UUID PRIMARY KEY REFERENCES customers(id) ON DELETE CASCADE,
+    tokens      INTEGER NOT NULL CHECK (tokens >= 0),
+    updated_at  TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Transfers that already refilled a bucket, so redelivered events are ignored.
+CREATE TABLE dict_bucket_refills (
+    transfer_id UUID PRIMARY KEY,
+    customer_id UUID NOT NULL REFERENCES customers(id) ON DELETE CASCADE,
+    created_at  TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
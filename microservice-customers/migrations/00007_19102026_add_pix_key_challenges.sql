@@ -0,0 +1,15 @@
+-- One-time codes proving possession of an email or phone before its key is
+-- registered. Only a hash of the code is stored.
+CREATE TABLE pix_key_challenges (
+    id              UUID PRIMARY KEY,
+    account_id      UUID NOT NULL REFERENCES accounts(id) ON DELETE CASCADE,
+    key_type        pix_key_type NOT NULL,
+    key_value       VARCHAR(255) NOT NULL,
+    code_hash       CHAR(64) NOT NULL,
+    attempts        INTEGER NOT NULL DEFAULT 0,
+    expires_at      TIMESTAMPTZ NOT NULL,
+    confirmed_at    TIMESTAMPTZ,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_pix_key_challenges_account_id ON pix_key_challenges(account_id);
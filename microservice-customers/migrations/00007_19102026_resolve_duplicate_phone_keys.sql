@@ -0,0 +1,25 @@
+-- Runs before 00008_19102026_normalize_phone_numbers.sql, which it sorts ahead
+-- of: the same number may be held by two active phone keys written
+-- differently, and rewriting both to E.164 would break
+-- uq_pix_keys_active_key_value. The oldest key keeps the number and the
+-- others are deactivated. Where 00008 already ran this finds nothing to do.
+WITH normalized AS (
+    SELECT id, created_at,
+           CASE
+               WHEN key_value NOT LIKE '+%'
+                    AND regexp_replace(key_value, '\D', '', 'g') ~ '^[1-9][0-9]{9,10}$'
+                   THEN '+55' || regexp_replace(key_value, '\D', '', 'g')
+               WHEN regexp_replace(key_value, '\D', '', 'g') ~ '^55[1-9][0-9]{9,10}$'
+                   THEN '+' || regexp_replace(key_value, '\D', '', 'g')
+               ELSE key_value
+           END AS e164
+    FROM pix_keys
+    WHERE key_type = 'PHONE' AND is_active
+),
+ranked AS (
+    SELECT id, ROW_NUMBER() OVER (PARTITION BY e164 ORDER BY created_at, id) AS position
+    FROM normalized
+)
+UPDATE pix_keys
+SET is_active = FALSE, deactivated_at = NOW()
+WHERE id IN (SELECT id FROM ranked WHERE position > 1);
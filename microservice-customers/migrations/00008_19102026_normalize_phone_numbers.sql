@@ -0,0 +1,26 @@
+-- Phones are stored in E.164 ("+5511912345678") so the same number written
+-- differently maps to a single phone key. Rewrites Brazilian numbers written
+-- with or without the country code; anything else is left untouched.
+UPDATE customers
+SET phone = '+55' || regexp_replace(phone, '\D', '', 'g'),
+    updated_at = NOW()
+WHERE phone NOT LIKE '+%'
+  AND regexp_replace(phone, '\D', '', 'g') ~ '^[1-9][0-9]{9,10}$';
+
+UPDATE customers
+SET phone = '+' || regexp_replace(phone, '\D', '', 'g'),
+    updated_at = NOW()
+WHERE regexp_replace(phone, '\D', '', 'g') ~ '^55[1-9][0-9]{9,10}$'
+  AND phone <> '+' || regexp_replace(phone, '\D', '', 'g');
+
+UPDATE pix_keys
+SET key_value = '+55' || regexp_replace(key_value, '\D', '', 'g')
+WHERE key_type = 'PHONE'
+  AND key_value NOT LIKE '+%'
+  AND regexp_replace(key_value, '\D', '', 'g') ~ '^[1-9][0-9]{9,10}$';
+
+UPDATE pix_keys
+SET key_value = '+' || regexp_replace(key_value, '\D', '', 'g')
+WHERE key_type = 'PHONE'
+  AND regexp_replace(key_value, '\D', '', 'g') ~ '^55[1-9][0-9]{9,10}$'
+  AND key_value <> '+' || regexp_replace(key_value, '\D', '', 'g');
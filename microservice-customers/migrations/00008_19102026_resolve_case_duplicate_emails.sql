@@ -0,0 +1,32 @@
+-- Runs before 00009_19102026_case_insensitive_emails.sql, which it sorts
+-- ahead of. Where 00009 already ran this finds nothing to do.
+
+-- Customers whose emails differ only in case or spacing are separate people
+-- or duplicate sign-ups; which one keeps the address is not ours to guess.
+DO $$
+DECLARE
+    duplicates TEXT;
+BEGIN
+    SELECT string_agg(email, ', ' ORDER BY email) INTO duplicates
+    FROM (
+        SELECT lower(trim(email)) AS email
+        FROM customers
+        GROUP BY lower(trim(email))
+        HAVING COUNT(*) > 1
+    ) AS clashing;
+
+    IF duplicates IS NOT NULL THEN
+        RAISE EXCEPTION 'Customers share emails that differ only in case: %. Change or merge them before rerunning this migration.', duplicates;
+    END IF;
+END $$;
+
+-- Active email keys differing only in case: the oldest keeps the value and
+-- the others are deactivated, so lowercasing cannot break uq_pix_keys_active_key_value.
+WITH ranked AS (
+    SELECT id, ROW_NUMBER() OVER (PARTITION BY lower(trim(key_value)) ORDER BY created_at, id) AS position
+    FROM pix_keys
+    WHERE key_type = 'EMAIL' AND is_active
+)
+UPDATE pix_keys
+SET is_active = FALSE, deactivated_at = NOW()
+WHERE id IN (SELECT id FROM ranked WHERE position > 1);
@@ -0,0 +1,17 @@
+-- Emails are stored lowercased; the indexes below also keep rows written
+-- outside the API from differing only in case.
+UPDATE customers
+SET email = lower(trim(email)), updated_at = NOW()
+WHERE email <> lower(trim(email));
+
+UPDATE pix_keys
+SET key_value = lower(trim(key_value))
+WHERE key_type = 'EMAIL'
+  AND key_value <> lower(trim(key_value));
+
+CREATE UNIQUE INDEX IF NOT EXISTS uq_customers_email_lower
+    ON customers(lower(email));
+
+CREATE UNIQUE INDEX IF NOT EXISTS uq_pix_keys_active_email_lower
+    ON pix_keys(lower(key_value))
+    WHERE is_active AND key_type = 'EMAIL';
@@ -0,0 +1,15 @@
+-- Customers are either individuals (PF, identified by CPF) or businesses
+-- (PJ, identified by CNPJ and registered under a legal name).
+CREATE TYPE person_type AS ENUM ('PF', 'PJ');
+
+ALTER TABLE customers
+    ADD COLUMN IF NOT EXISTS person_type person_type NOT NULL DEFAULT 'PF',
+    ADD COLUMN IF NOT EXISTS cnpj CHAR(14) NULL UNIQUE,
+    ADD COLUMN IF NOT EXISTS legal_name VARCHAR(255) NULL;
+
+ALTER TABLE customers ALTER COLUMN cpf DROP NOT NULL;
+
+ALTER TABLE customers ADD CONSTRAINT chk_customers_identity CHECK (
+    (person_type = 'PF' AND cpf IS NOT NULL AND cnpj IS NULL AND legal_name IS NULL)
+    OR (person_type = 'PJ' AND cnpj IS NOT NULL AND cpf IS NULL AND legal_name IS NOT NULL)
+);
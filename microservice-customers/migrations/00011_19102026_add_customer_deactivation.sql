@@ -0,0 +1,5 @@
+-- Deactivating a customer blocks their accounts: no new transfers may debit
+-- or credit a blocked account.
+ALTER TABLE accounts
+    ADD COLUMN IF NOT EXISTS is_blocked BOOLEAN NOT NULL DEFAULT FALSE,
+    ADD COLUMN IF NOT EXISTS blocked_at TIMESTAMPTZ NULL;
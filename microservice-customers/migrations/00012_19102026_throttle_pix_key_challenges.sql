@@ -0,0 +1,15 @@
+-- A challenge stays open for its account and key value until it is confirmed
+-- or expires; asking again resends a new code on the same row, so sends are
+-- counted and spaced out. Claim challenges prove the claimer holds the value.
+ALTER TABLE pix_key_challenges
+    ADD COLUMN IF NOT EXISTS sends INTEGER NOT NULL DEFAULT 1,
+    ADD COLUMN IF NOT EXISTS last_sent_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    ADD COLUMN IF NOT EXISTS key_claim_id UUID NULL REFERENCES key_claims(id) ON DELETE CASCADE;
+
+CREATE INDEX IF NOT EXISTS idx_pix_key_challenges_open
+    ON pix_key_challenges(account_id, key_value)
+    WHERE confirmed_at IS NULL;
+
+CREATE INDEX IF NOT EXISTS idx_pix_key_challenges_key_claim_id
+    ON pix_key_challenges(key_claim_id)
+    WHERE key_claim_id IS NOT NULL;
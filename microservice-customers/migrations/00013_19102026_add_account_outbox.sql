@@ -0,0 +1,9 @@
+-- Blocks waiting to be published on the `accounts` topic. A row is queued in
+-- the transaction that blocks the account and deleted once the broker has it,
+-- so the ledger learns about every block even if the publisher was down.
+CREATE TABLE IF NOT EXISTS account_outbox (
+    id          BIGSERIAL PRIMARY KEY,
+    account_id  UUID NOT NULL REFERENCES accounts(id),
+    blocked_at  TIMESTAMPTZ NOT NULL,
+    created_at  TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,8 @@
+WITH blocked AS (
+    UPDATE public.accounts
+    SET is_blocked = TRUE, blocked_at = NOW(), updated_at = NOW()
+    WHERE customer_id = $1 AND NOT is_blocked
+    RETURNING id, blocked_at
+)
+INSERT INTO public.account_outbox (account_id, blocked_at)
+SELECT id, blocked_at FROM blocked;
@@ -0,0 +1,5 @@
+UPDATE public.key_claims
+SET status = 'CANCELLED', cancelled_by = 'CLOSURE', updated_at = NOW()
+WHERE status IN ('OPEN', 'WAITING_RESOLUTION', 'CONFIRMED')
+  AND (claimer_account_id IN (SELECT id FROM public.accounts WHERE customer_id = $1)
+    OR donor_account_id IN (SELECT id FROM public.accounts WHERE customer_id = $1));
\ No newline at end of file
@@ -0,0 +1 @@
+UPDATE public.pix_key_challenges SET confirmed_at = NOW() WHERE id = $1;
\ No newline at end of file
@@ -0,0 +1 @@
+SELECT COUNT(*) FROM public.pix_keys WHERE account_id = $1 AND is_active;
\ No newline at end of file
@@ -1,4 +1,4 @@
 INSERT INTO public.customers
-(full_name, email, cpf)
-VALUES ($1, $2, $3)
+(full_name, email, cpf, phone, person_type, cnpj, legal_name)
+VALUES ($1, $2, $3, $4, $5, $6, $7)
 RETURNING $table_fields;
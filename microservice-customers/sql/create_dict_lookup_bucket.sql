@@ -0,0 +1,3 @@
+INSERT INTO public.dict_lookup_buckets (customer_id, tokens)
+VALUES ($1, $2)
+ON CONFLICT (customer_id) DO NOTHING;
\ No newline at end of file
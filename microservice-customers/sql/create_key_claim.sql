@@ -0,0 +1,4 @@
+INSERT INTO public.key_claims
+(claim_type, key_type, key_value, pix_key_id, claimer_account_id, donor_account_id)
+VALUES($1, $2, $3, $4, $5, $6)
+RETURNING $table_fields;
\ No newline at end of file
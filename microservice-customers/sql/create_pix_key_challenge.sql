@@ -0,0 +1,4 @@
+INSERT INTO public.pix_key_challenges
+(id, account_id, key_type, key_value, code_hash, expires_at, key_claim_id)
+VALUES($1, $2, $3, $4, $5, $6, $7)
+RETURNING $table_fields;
\ No newline at end of file
@@ -0,0 +1,4 @@
+UPDATE public.customers
+SET is_active = FALSE, updated_at = NOW()
+WHERE id = $1
+RETURNING $table_fields;
\ No newline at end of file
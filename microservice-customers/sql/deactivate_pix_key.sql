@@ -0,0 +1,4 @@
+UPDATE public.pix_keys
+SET is_active = FALSE, deactivated_at = NOW()
+WHERE id = $1 AND account_id = $2 AND is_active
+RETURNING $table_fields;
\ No newline at end of file
@@ -0,0 +1,4 @@
+UPDATE public.pix_keys k
+SET is_active = FALSE, deactivated_at = NOW()
+FROM public.accounts a
+WHERE a.id = k.account_id AND a.customer_id = $1 AND k.is_active;
\ No newline at end of file
@@ -0,0 +1 @@
+DELETE FROM public.account_outbox WHERE id = ANY($1);
\ No newline at end of file
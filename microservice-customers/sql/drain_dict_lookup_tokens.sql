@@ -0,0 +1,4 @@
+UPDATE public.dict_lookup_buckets
+SET tokens = GREATEST(tokens - $2, 0), updated_at = NOW()
+WHERE customer_id = $1
+RETURNING tokens;
\ No newline at end of file
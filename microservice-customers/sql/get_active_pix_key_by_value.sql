@@ -0,0 +1 @@
+SELECT $table_fields FROM public.pix_keys WHERE key_value = $1 AND is_active LIMIT 1;
\ No newline at end of file
@@ -0,0 +1 @@
+SELECT $table_fields FROM public.customers WHERE id = (SELECT customer_id FROM public.accounts WHERE id = $1) LIMIT 1;
\ No newline at end of file
@@ -0,0 +1,7 @@
+SELECT k.key_type, k.key_value, k.created_at, a.id, a.account_type,
+       c.person_type, c.full_name, c.legal_name, c.cpf, c.cnpj
+FROM public.pix_keys k
+JOIN public.accounts a ON a.id = k.account_id
+JOIN public.customers c ON c.id = a.customer_id
+WHERE k.key_value = $1 AND k.is_active AND c.is_active
+LIMIT 1;
\ No newline at end of file
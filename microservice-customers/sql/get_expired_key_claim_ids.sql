@@ -0,0 +1,3 @@
+SELECT id FROM public.key_claims
+WHERE status = 'WAITING_RESOLUTION' AND resolution_deadline <= NOW()
+ORDER BY resolution_deadline;
\ No newline at end of file
@@ -0,0 +1 @@
+SELECT $table_fields FROM public.key_claims WHERE id = $1 LIMIT 1;
\ No newline at end of file
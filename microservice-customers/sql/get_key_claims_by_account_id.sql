@@ -0,0 +1,3 @@
+SELECT $table_fields FROM public.key_claims
+WHERE claimer_account_id = $1 OR donor_account_id = $1
+ORDER BY created_at DESC;
\ No newline at end of file
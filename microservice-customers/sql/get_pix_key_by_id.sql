@@ -0,0 +1 @@
+SELECT $table_fields FROM public.pix_keys WHERE id = $1 AND account_id = $2 LIMIT 1;
\ No newline at end of file
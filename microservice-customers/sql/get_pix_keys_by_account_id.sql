@@ -0,0 +1,3 @@
+SELECT $table_fields FROM public.pix_keys
+WHERE account_id = $1 AND is_active
+ORDER BY created_at;
\ No newline at end of file
@@ -0,0 +1 @@
+SELECT id FROM public.key_claims WHERE status = 'OPEN' ORDER BY created_at;
\ No newline at end of file
@@ -0,0 +1,4 @@
+SELECT EXISTS (
+    SELECT 1 FROM public.pix_key_challenges
+    WHERE key_claim_id = $1 AND account_id = $2 AND key_value = $3 AND confirmed_at IS NOT NULL
+);
\ No newline at end of file
@@ -0,0 +1,4 @@
+SELECT EXISTS (
+    SELECT 1 FROM public.key_claims
+    WHERE pix_key_id = $1 AND status IN ('OPEN', 'WAITING_RESOLUTION', 'CONFIRMED')
+);
\ No newline at end of file
@@ -0,0 +1,2 @@
+SELECT id, account_id, blocked_at FROM public.account_outbox
+ORDER BY id LIMIT $1 FOR UPDATE SKIP LOCKED;
\ No newline at end of file
@@ -0,0 +1 @@
+SELECT $table_fields FROM public.customers WHERE id = $1 FOR UPDATE;
\ No newline at end of file
@@ -0,0 +1,5 @@
+SELECT $table_fields FROM public.pix_key_challenges
+WHERE key_claim_id = $1 AND account_id = $2
+ORDER BY created_at DESC
+LIMIT 1
+FOR UPDATE;
\ No newline at end of file
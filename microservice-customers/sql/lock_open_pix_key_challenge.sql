@@ -0,0 +1,5 @@
+SELECT $table_fields FROM public.pix_key_challenges
+WHERE account_id = $1 AND key_value = $2 AND confirmed_at IS NULL AND expires_at > NOW()
+ORDER BY created_at DESC
+LIMIT 1
+FOR UPDATE;
\ No newline at end of file
@@ -0,0 +1 @@
+SELECT $table_fields FROM public.pix_keys WHERE id = $1 FOR UPDATE;
\ No newline at end of file
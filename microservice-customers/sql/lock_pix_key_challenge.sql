@@ -0,0 +1 @@
+SELECT $table_fields FROM public.pix_key_challenges WHERE id = $1 AND account_id = $2 FOR UPDATE;
\ No newline at end of file
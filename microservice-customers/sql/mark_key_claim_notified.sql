@@ -0,0 +1,4 @@
+UPDATE public.key_claims
+SET status = 'WAITING_RESOLUTION', notified_at = NOW(), resolution_deadline = $2, updated_at = NOW()
+WHERE id = $1 AND status = 'OPEN'
+RETURNING $table_fields;
\ No newline at end of file
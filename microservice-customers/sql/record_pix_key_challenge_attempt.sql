@@ -0,0 +1 @@
+UPDATE public.pix_key_challenges SET attempts = attempts + 1 WHERE id = $1;
\ No newline at end of file
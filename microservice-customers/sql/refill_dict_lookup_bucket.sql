@@ -0,0 +1,10 @@
+WITH refill AS (
+    INSERT INTO public.dict_bucket_refills (transfer_id, customer_id)
+    SELECT $1, customer_id FROM public.accounts WHERE id = $2
+    ON CONFLICT (transfer_id) DO NOTHING
+    RETURNING customer_id
+)
+INSERT INTO public.dict_lookup_buckets (customer_id, tokens)
+SELECT customer_id, $4::INTEGER FROM refill
+ON CONFLICT (customer_id) DO UPDATE
+SET tokens = LEAST(dict_lookup_buckets.tokens + $3::INTEGER, $4::INTEGER), updated_at = NOW();
\ No newline at end of file
@@ -0,0 +1,3 @@
+UPDATE public.pix_keys
+SET is_active = FALSE, deactivated_at = NOW()
+WHERE id = $1 AND is_active;
\ No newline at end of file
@@ -0,0 +1,4 @@
+UPDATE public.pix_key_challenges
+SET code_hash = $2, attempts = 0, sends = sends + 1, last_sent_at = NOW(), expires_at = $3, key_claim_id = $4
+WHERE id = $1
+RETURNING $table_fields;
\ No newline at end of file
@@ -0,0 +1,4 @@
+UPDATE public.dict_lookup_buckets
+SET tokens = tokens - $2, updated_at = NOW()
+WHERE customer_id = $1 AND tokens >= $2
+RETURNING tokens;
\ No newline at end of file
@@ -0,0 +1,3 @@
+UPDATE public.accounts
+SET ledger_balance = $2, available_balance = $3, balance_version = $4, updated_at = NOW()
+WHERE id = $1 AND balance_version < $4;
@@ -0,0 +1,4 @@
+UPDATE public.customers
+SET full_name = $2, email = $3, phone = $4, updated_at = NOW()
+WHERE id = $1
+RETURNING $table_fields;
\ No newline at end of file
@@ -0,0 +1,7 @@
+UPDATE public.key_claims
+SET status = $2,
+    cancelled_by = $3,
+    completed_at = CASE WHEN $2 = 'COMPLETED'::key_claim_status THEN NOW() ELSE completed_at END,
+    updated_at = NOW()
+WHERE id = $1
+RETURNING $table_fields;
\ No newline at end of file
@@ -1,7 +1,7 @@
 use deadpool_postgres::{Client, Transaction};
 use uuid::Uuid;
 
-use crate::{application::dto::account_dto::AccountCreateRequest, domain::{account::Account, enums::AccountType}, infraestructure::{db::account_repo, error::MyError}};
+use crate::{application::dto::account_dto::{AccountCreateRequest, BalanceChangedEvent}, domain::{account::Account, enums::AccountType}, infraestructure::{db::account_repo, error::MyError}};
 
 pub async fn create_account(
     client_or_tx: &Transaction<'_>,
@@ -23,4 +23,10 @@ pub async fn get_account_by_customer_id(client: &Client, id: Uuid) -> Result<Acc
 
     Ok(account)
 
-}
\ No newline at end of file
+}
+
+/// Applies a balance from the ledger unless a newer one was already applied,
+/// so events delivered out of order never roll the balance back.
+pub async fn apply_balance_change(client: &Client, event: &BalanceChangedEvent) -> Result<bool, MyError> {
+    account_repo::update_balance(client, event).await
+}
@@ -2,13 +2,17 @@ use deadpool_postgres::Client;
 use uuid::Uuid;
 
 use crate::{
-    application::{account_service, dto::customer_dto::CreateCustomerRequest, jwt_service},
-    domain::customer::Customer,
+    application::{
+        account_service,
+        dto::customer_dto::{CreateCustomerRequest, UpdateCustomerRequest},
+        jwt_service,
+    },
+    domain::{customer::Customer, enums::PersonType, pix_key::PixKeyType},
     infraestructure::{
-        db::customer_repo,
-        error::{ApiError, MyError},
+        db::{account_repo, customer_repo, key_claim_repo, pix_key_repo},
+        error::{ApiError, ConflictError, MyError},
     },
-    shared::cpf,
+    shared::{cnpj, cpf, email, phone},
 };
 
 pub async fn create_customer(
@@ -17,10 +21,21 @@ pub async fn create_customer(
 ) -> Result<Customer, MyError> {
     create_customer_request.sanitize_fields();
 
-    let cpf_validation_result = cpf::validate_cpf(&create_customer_request.cpf);
+    if let Some(document) = &create_customer_request.cnpj {
+        match cnpj::parse_cnpj(document) {
+            Ok(normalized) => create_customer_request.cnpj = Some(normalized),
+            Err(error) => {
+                return Err(MyError::ApiError(ApiError {
+                    msg: error.to_string(),
+                }))
+            }
+        }
+    }
+
+    validate_identity(&create_customer_request)?;
 
-    match cpf_validation_result {
-        Ok(()) => println!("CPF valido"),
+    match email::parse_email(&create_customer_request.email) {
+        Ok(normalized) => create_customer_request.email = normalized,
         Err(error) => {
             return Err(MyError::ApiError(ApiError {
                 msg: error.to_string(),
@@ -28,6 +43,17 @@ pub async fn create_customer(
         }
     }
 
+    if let Some(raw_phone) = &create_customer_request.phone {
+        match phone::parse_phone(raw_phone) {
+            Ok(normalized) => create_customer_request.phone = Some(normalized),
+            Err(error) => {
+                return Err(MyError::ApiError(ApiError {
+                    msg: error.to_string(),
+                }))
+            }
+        }
+    }
+
     let transaction = client.build_transaction().start().await?;
 
     let new_customer =
@@ -44,3 +70,282 @@ pub async fn get_customer_by_id(client: &Client, id: Uuid) -> Result<Customer, M
 
     Ok(customer)
 }
+
+/// Same as `get_customer_by_id`, but deactivated customers cannot act anymore.
+pub async fn get_active_customer_by_id(client: &Client, id: Uuid) -> Result<Customer, MyError> {
+    let customer = customer_repo::get_customer_by_id(client, id).await?;
+
+    ensure_active(&customer)?;
+    Ok(customer)
+}
+
+/// Updates the customer's own contact details. Keys bound to an email or
+/// phone that changed are deactivated, since the customer no longer proves
+/// possession of the old value.
+pub async fn update_customer(
+    client: &mut Client,
+    authenticated_id: Uuid,
+    id: Uuid,
+    mut update_customer_request: UpdateCustomerRequest,
+) -> Result<Customer, MyError> {
+    if authenticated_id != id {
+        return Err(MyError::NotFound);
+    }
+
+    update_customer_request.sanitize_fields();
+
+    if let Some(full_name) = &update_customer_request.full_name {
+        if full_name.is_empty() {
+            return Err(MyError::ApiError(ApiError {
+                msg: "Full name cannot be empty".to_string(),
+            }));
+        }
+    }
+
+    if let Some(raw_email) = &update_customer_request.email {
+        match email::parse_email(raw_email) {
+            Ok(normalized) => update_customer_request.email = Some(normalized),
+            Err(error) => {
+                return Err(MyError::ApiError(ApiError {
+                    msg: error.to_string(),
+                }))
+            }
+        }
+    }
+
+    let new_phone = update_customer_request.phone_change().map_err(|error| {
+        MyError::ApiError(ApiError {
+            msg: error.to_string(),
+        })
+    })?;
+
+    let transaction = client.build_transaction().start().await?;
+
+    let current = customer_repo::lock_customer(&transaction, id).await?;
+    ensure_active(&current)?;
+
+    let full_name = update_customer_request.full_name.unwrap_or_else(|| current.full_name.clone());
+    let email = update_customer_request.email.unwrap_or_else(|| current.email.clone());
+    let phone = new_phone.unwrap_or_else(|| current.phone.clone());
+
+    let updated_customer =
+        customer_repo::update_customer(&transaction, id, &full_name, &email, phone.as_deref()).await?;
+
+    for (key_type, key_value) in stale_pix_keys(&current, &updated_customer) {
+        pix_key_repo::deactivate_pix_keys_by_value(&transaction, id, &key_type, &key_value).await?;
+    }
+
+    transaction.commit().await?;
+    Ok(updated_customer)
+}
+
+/// Deactivates the customer: every key is deactivated, claims in progress are
+/// cancelled and their accounts are blocked for new transfers. Deactivating
+/// twice is a no-op.
+pub async fn deactivate_customer(
+    client: &mut Client,
+    authenticated_id: Uuid,
+    id: Uuid,
+) -> Result<Customer, MyError> {
+    if authenticated_id != id {
+        return Err(MyError::NotFound);
+    }
+
+    let transaction = client.build_transaction().start().await?;
+
+    let current = customer_repo::lock_customer(&transaction, id).await?;
+    if !current.is_active {
+        transaction.commit().await?;
+        return Ok(current);
+    }
+
+    let deactivated_customer = customer_repo::deactivate_customer(&transaction, id).await?;
+
+    let keys = pix_key_repo::deactivate_pix_keys_by_customer_id(&transaction, id).await?;
+    let claims = key_claim_repo::cancel_key_claims_by_customer_id(&transaction, id).await?;
+    let accounts = account_repo::block_accounts_by_customer_id(&transaction, id).await?;
+
+    transaction.commit().await?;
+
+    log::info!(
+        "Customer {} deactivated: {} keys deactivated, {} claims cancelled, {} accounts blocked",
+        id,
+        keys,
+        claims,
+        accounts
+    );
+    Ok(deactivated_customer)
+}
+
+/// Email and phone key values the update replaced or removed; keys bound to
+/// them no longer belong to the customer.
+fn stale_pix_keys(current: &Customer, updated: &Customer) -> Vec<(PixKeyType, String)> {
+    let mut stale = Vec::new();
+
+    if current.email != updated.email {
+        stale.push((PixKeyType::EMAIL, current.email.clone()));
+    }
+
+    if let Some(old_phone) = &current.phone {
+        if updated.phone.as_ref() != Some(old_phone) {
+            stale.push((PixKeyType::PHONE, old_phone.clone()));
+        }
+    }
+
+    stale
+}
+
+fn ensure_active(customer: &Customer) -> Result<(), MyError> {
+    if !customer.is_active {
+        return Err(MyError::Conflict(ConflictError {
+            msg: "Customer is deactivated".to_string(),
+        }));
+    }
+
+    Ok(())
+}
+
+/// Individuals register with a CPF only; businesses with a CNPJ and the legal
+/// name it is registered under.
+fn validate_identity(request: &CreateCustomerRequest) -> Result<(), MyError> {
+    let api_error = |msg: String| MyError::ApiError(ApiError { msg });
+
+    match request.person_type {
+        PersonType::PF => {
+            let document = request
+                .cpf
+                .as_deref()
+                .ok_or_else(|| api_error("CPF is required for individuals".to_string()))?;
+
+            cpf::validate_cpf(document).map_err(|error| api_error(error.to_string()))?;
+
+            if request.cnpj.is_some() || request.legal_name.is_some() {
+                return Err(api_error(
+                    "CNPJ and legal name are only accepted for businesses (PJ)".to_string(),
+                ));
+            }
+        }
+        PersonType::PJ => {
+            // The CNPJ itself was already parsed by create_customer.
+            if request.cnpj.is_none() {
+                return Err(api_error("CNPJ is required for businesses".to_string()));
+            }
+
+            if request.legal_name.is_none() {
+                return Err(api_error("Legal name is required for businesses".to_string()));
+            }
+
+            if request.cpf.is_some() {
+                return Err(api_error("CPF is only accepted for individuals (PF)".to_string()));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn individual() -> CreateCustomerRequest {
+        CreateCustomerRequest {
+            person_type: PersonType::PF,
+            full_name: "Maria Silva".to_string(),
+            email: "maria@email.com".to_string(),
+            cpf: Some("52998224725".to_string()),
+            cnpj: None,
+            legal_name: None,
+            phone: None,
+        }
+    }
+
+    fn business() -> CreateCustomerRequest {
+        CreateCustomerRequest {
+            person_type: PersonType::PJ,
+            cpf: None,
+            cnpj: Some("04252011000110".to_string()),
+            legal_name: Some("Empresa Ltda".to_string()),
+            ..individual()
+        }
+    }
+
+    fn rejected(request: CreateCustomerRequest) -> bool {
+        matches!(validate_identity(&request), Err(MyError::ApiError(_)))
+    }
+
+    #[test]
+    fn test_valid_identities() {
+        assert!(validate_identity(&individual()).is_ok());
+        assert!(validate_identity(&business()).is_ok());
+    }
+
+    #[test]
+    fn test_individual_requires_cpf() {
+        assert!(rejected(CreateCustomerRequest { cpf: None, ..individual() }));
+        assert!(rejected(CreateCustomerRequest { cpf: Some("52998224726".to_string()), ..individual() }));
+    }
+
+    #[test]
+    fn test_individual_rejects_business_fields() {
+        assert!(rejected(CreateCustomerRequest { cnpj: Some("04252011000110".to_string()), ..individual() }));
+        assert!(rejected(CreateCustomerRequest { legal_name: Some("Empresa Ltda".to_string()), ..individual() }));
+    }
+
+    #[test]
+    fn test_business_requires_cnpj_and_legal_name() {
+        assert!(rejected(CreateCustomerRequest { cnpj: None, ..business() }));
+        assert!(rejected(CreateCustomerRequest { legal_name: None, ..business() }));
+    }
+
+    #[test]
+    fn test_business_rejects_cpf() {
+        assert!(rejected(CreateCustomerRequest { cpf: Some("52998224725".to_string()), ..business() }));
+    }
+
+    fn customer(email: &str, phone: Option<&str>) -> Customer {
+        Customer {
+            id: Uuid::new_v4(),
+            full_name: "Maria Silva".to_string(),
+            email: email.to_string(),
+            person_type: PersonType::PF,
+            cpf: Some("52998224725".to_string()),
+            cnpj: None,
+            legal_name: None,
+            phone: phone.map(str::to_string),
+            is_active: true,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_unchanged_contacts_keep_their_keys() {
+        let current = customer("maria@email.com", Some("+5511912345678"));
+        assert!(stale_pix_keys(&current, &current.clone()).is_empty());
+    }
+
+    #[test]
+    fn test_changed_email_deactivates_the_old_email_keys() {
+        let current = customer("maria@email.com", Some("+5511912345678"));
+        let updated = customer("maria@empresa.com", Some("+5511912345678"));
+        assert_eq!(
+            stale_pix_keys(&current, &updated),
+            vec![(PixKeyType::EMAIL, "maria@email.com".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_changed_or_removed_phone_deactivates_the_old_phone_keys() {
+        let current = customer("maria@email.com", Some("+5511912345678"));
+        let old_phone = vec![(PixKeyType::PHONE, "+5511912345678".to_string())];
+        assert_eq!(stale_pix_keys(&current, &customer("maria@email.com", Some("+5511987654321"))), old_phone);
+        assert_eq!(stale_pix_keys(&current, &customer("maria@email.com", None)), old_phone);
+    }
+
+    #[test]
+    fn test_added_phone_deactivates_nothing() {
+        let current = customer("maria@email.com", None);
+        assert!(stale_pix_keys(&current, &customer("maria@email.com", Some("+5511912345678"))).is_empty());
+    }
+}
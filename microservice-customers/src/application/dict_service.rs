@@ -0,0 +1,113 @@
+use deadpool_postgres::Client;
+use uuid::Uuid;
+
+use crate::{
+    application::{
+        customer_service,
+        dto::transfer_dto::{TransferEvent, TransferEventType},
+    },
+    domain::dict::{
+        DictEntry, LOOKUP_BUCKET_CAPACITY, LOOKUP_COST, MISSED_LOOKUP_PENALTY, TRANSFER_REFILL,
+    },
+    infraestructure::{
+        db::dict_repo,
+        error::{MyError, RateLimitError},
+    },
+    shared::{cnpj, cpf, email, phone},
+};
+
+/// Resolves a key for `customer_id`, charging the lookup to their bucket.
+/// Returns the entry and the tokens left.
+pub async fn lookup_key(client: &Client, customer_id: Uuid, key_value: &str) -> Result<(DictEntry, i32), MyError> {
+    customer_service::get_active_customer_by_id(client, customer_id).await?;
+
+    let remaining = dict_repo::take_lookup_tokens(client, customer_id, LOOKUP_COST, LOOKUP_BUCKET_CAPACITY)
+        .await?
+        .ok_or(MyError::RateLimited(RateLimitError {
+            msg: "Key lookup limit reached; it is restored as you complete transfers".to_string(),
+        }))?;
+
+    for candidate in lookup_candidates(key_value) {
+        if let Some(entry) = dict_repo::get_dict_entry_by_key_value(client, &candidate).await? {
+            return Ok((entry, remaining));
+        }
+    }
+
+    dict_repo::drain_lookup_tokens(client, customer_id, MISSED_LOOKUP_PENALTY).await?;
+    Err(MyError::NotFound)
+}
+
+/// The stored forms `key_value` may take, one per key type it parses as: a
+/// phone as "+5511912345678", a CPF or CNPJ without punctuation, an email
+/// lowercased. Anything else is looked up as written.
+fn lookup_candidates(key_value: &str) -> Vec<String> {
+    let key_value = key_value.trim();
+    let cpf_digits: String = key_value.chars().filter(|c| !matches!(c, '.' | '-' | ' ')).collect();
+
+    let parsed = [
+        email::parse_email(key_value).ok(),
+        phone::parse_phone(key_value).ok(),
+        (cpf_digits.chars().all(|c| c.is_ascii_digit()) && cpf::validate_cpf(&cpf_digits).is_ok())
+            .then(|| cpf_digits.clone()),
+        cnpj::parse_cnpj(key_value).ok(),
+        Uuid::parse_str(key_value).ok().map(|id| id.to_string()),
+    ];
+
+    let mut candidates: Vec<String> = Vec::new();
+    for value in parsed.into_iter().flatten() {
+        if !candidates.contains(&value) {
+            candidates.push(value);
+        }
+    }
+
+    if candidates.is_empty() {
+        candidates.push(key_value.to_string());
+    }
+
+    candidates
+}
+
+/// Only transfers that reached the ledger give lookups back to the payer.
+pub async fn refill_from_transfer(client: &Client, event: &TransferEvent) -> Result<bool, MyError> {
+    if event.event_type != TransferEventType::POSTED {
+        return Ok(false);
+    }
+
+    dict_repo::refill_lookup_bucket(
+        client,
+        event.transfer_id,
+        event.debit_account_id,
+        TRANSFER_REFILL,
+        LOOKUP_BUCKET_CAPACITY,
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_phone_is_looked_up_in_e164() {
+        assert!(lookup_candidates("(11) 91234-5678").contains(&"+5511912345678".to_string()));
+        assert!(lookup_candidates("+55 11 91234-5678").contains(&"+5511912345678".to_string()));
+    }
+
+    #[test]
+    fn test_documents_are_looked_up_without_punctuation() {
+        assert_eq!(lookup_candidates("529.982.247-25"), vec!["52998224725".to_string()]);
+        assert_eq!(lookup_candidates("04.252.011/0001-10"), vec!["04252011000110".to_string()]);
+    }
+
+    #[test]
+    fn test_email_is_looked_up_lowercased() {
+        assert_eq!(lookup_candidates(" User@Email.com "), vec!["user@email.com".to_string()]);
+    }
+
+    #[test]
+    fn test_random_key_is_looked_up_as_written() {
+        let id = Uuid::new_v4().to_string();
+        assert_eq!(lookup_candidates(&id.to_uppercase()), vec![id]);
+        assert_eq!(lookup_candidates("unknown"), vec!["unknown".to_string()]);
+    }
+}
@@ -1,3 +1,5 @@
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -10,4 +12,22 @@ pub struct AccountCreateRequest {
     pub customer_id: Uuid,
     pub account_type: AccountType,
     pub currency: String,
-}
\ No newline at end of file
+}
+
+/// Published by microservice-ledgers on the `balances` topic.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BalanceChangedEvent {
+    pub account_id: Uuid,
+    pub ledger_balance: Decimal,
+    pub available_balance: Decimal,
+    pub version: i64,
+    pub changed_at: DateTime<Utc>,
+}
+
+/// Published on the `accounts` topic when an account is blocked; the ledger
+/// refuses transfers from or to it from then on.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccountBlockedEvent {
+    pub account_id: Uuid,
+    pub blocked_at: DateTime<Utc>,
+}
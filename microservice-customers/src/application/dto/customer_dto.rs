@@ -1,41 +1,194 @@
-use crate::{domain::customer::Customer};
+use crate::{domain::{customer::Customer, enums::PersonType}, shared::phone::{self, PhoneValidationError}};
 use serde::{Deserialize, Serialize};
 #[derive(Debug, Deserialize)]
 pub struct CreateCustomerRequest {
+    #[serde(default)]
+    pub person_type: PersonType,
     pub full_name: String,
     pub email: String,
-    pub cpf: String,
+    #[serde(default)]
+    pub cpf: Option<String>,
+    #[serde(default)]
+    pub cnpj: Option<String>,
+    #[serde(default)]
+    pub legal_name: Option<String>,
+    #[serde(default)]
+    pub phone: Option<String>,
+}
+
+/// Fields left out keep their current value. An empty `phone` removes it.
+#[derive(Debug, Default, Deserialize)]
+pub struct UpdateCustomerRequest {
+    #[serde(default)]
+    pub full_name: Option<String>,
+    #[serde(default)]
+    pub email: Option<String>,
+    #[serde(default)]
+    pub phone: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
 pub struct CustomerResponse {
     pub id: uuid::Uuid,
+    pub person_type: PersonType,
     pub full_name: String,
     pub email: String,
-    pub cpf: String,
+    pub cpf: Option<String>,
+    pub cnpj: Option<String>,
+    pub legal_name: Option<String>,
+    pub phone: Option<String>,
     pub is_active: bool,
     pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
 }
 
 impl From<Customer> for CustomerResponse {
     fn from(c: Customer) -> Self {
         Self {
             id: c.id,
+            person_type: c.person_type,
             full_name: c.full_name,
             email: c.email,
             cpf: c.cpf,
+            cnpj: c.cnpj,
+            legal_name: c.legal_name,
+            phone: c.phone,
             is_active: c.is_active,
             created_at: c.created_at,
+            updated_at: c.updated_at,
         }
     }
 }
 impl CreateCustomerRequest {
     pub fn sanitize_fields(&mut self) {
         self.sanitize_cpf();
+        self.sanitize_cnpj();
         self.full_name = self.full_name.trim().to_string();
         self.email = self.email.trim().to_string();
+        self.legal_name = trimmed(self.legal_name.take());
+        self.phone = trimmed(self.phone.take());
     }
     fn sanitize_cpf(&mut self) {
-        self.cpf = self.cpf.chars().filter(|c| c.is_ascii_digit()).collect();
+        self.cpf = trimmed(self.cpf.take())
+            .map(|cpf| cpf.chars().filter(|c| c.is_ascii_digit()).collect());
+    }
+    fn sanitize_cnpj(&mut self) {
+        // Punctuation and letter case are normalized by cnpj::parse_cnpj.
+        self.cnpj = trimmed(self.cnpj.take());
+    }
+}
+
+impl UpdateCustomerRequest {
+    pub fn sanitize_fields(&mut self) {
+        self.full_name = self.full_name.take().map(|name| name.trim().to_string());
+        self.email = self.email.take().map(|email| email.trim().to_string());
+        self.phone = self.phone.take().map(|phone| phone.trim().to_string());
+    }
+
+    /// `None` keeps the current phone, `Some(None)` removes it and
+    /// `Some(Some(_))` sets it, normalized. Expects sanitized fields.
+    pub fn phone_change(&self) -> Result<Option<Option<String>>, PhoneValidationError> {
+        match self.phone.as_deref() {
+            None => Ok(None),
+            Some("") => Ok(Some(None)),
+            Some(raw_phone) => phone::parse_phone(raw_phone).map(|normalized| Some(Some(normalized))),
+        }
+    }
+}
+
+fn trimmed(value: Option<String>) -> Option<String> {
+    value
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn business(cnpj: Option<&str>) -> CreateCustomerRequest {
+        CreateCustomerRequest {
+            person_type: PersonType::PJ,
+            full_name: " Maria Silva ".to_string(),
+            email: " contato@empresa.com ".to_string(),
+            cpf: None,
+            cnpj: cnpj.map(str::to_string),
+            legal_name: Some(" Empresa Ltda ".to_string()),
+            phone: None,
+        }
+    }
+
+    fn update(phone: Option<&str>) -> UpdateCustomerRequest {
+        let mut request = UpdateCustomerRequest {
+            phone: phone.map(str::to_string),
+            ..Default::default()
+        };
+        request.sanitize_fields();
+        request
+    }
+
+    #[test]
+    fn test_update_sanitize_trims_given_fields_only() {
+        let mut request = UpdateCustomerRequest {
+            full_name: Some(" Maria Silva ".to_string()),
+            email: Some(" Maria@Email.com ".to_string()),
+            phone: None,
+        };
+        request.sanitize_fields();
+        assert_eq!(request.full_name.as_deref(), Some("Maria Silva"));
+        assert_eq!(request.email.as_deref(), Some("Maria@Email.com"));
+        assert_eq!(request.phone, None);
+    }
+
+    #[test]
+    fn test_absent_phone_keeps_the_current_one() {
+        assert_eq!(update(None).phone_change(), Ok(None));
+    }
+
+    #[test]
+    fn test_empty_phone_removes_it() {
+        assert_eq!(update(Some("")).phone_change(), Ok(Some(None)));
+        assert_eq!(update(Some("   ")).phone_change(), Ok(Some(None)));
+    }
+
+    #[test]
+    fn test_given_phone_is_normalized() {
+        assert_eq!(
+            update(Some(" (11) 91234-5678 ")).phone_change(),
+            Ok(Some(Some("+5511912345678".to_string())))
+        );
+        assert!(update(Some("11@91234-5678")).phone_change().is_err());
+    }
+
+    #[test]
+    fn test_sanitize_cnpj_trims_and_keeps_punctuation_for_the_parser() {
+        let mut request = business(Some(" 12.ABC.345/01DE-35 "));
+        request.sanitize_fields();
+        assert_eq!(request.cnpj.as_deref(), Some("12.ABC.345/01DE-35"));
+        assert_eq!(request.legal_name.as_deref(), Some("Empresa Ltda"));
+    }
+
+    #[test]
+    fn test_sanitize_cnpj_drops_blank_values() {
+        let mut request = business(Some("   "));
+        request.sanitize_fields();
+        assert_eq!(request.cnpj, None);
+
+        let mut request = business(None);
+        request.sanitize_fields();
+        assert_eq!(request.cnpj, None);
+    }
+
+    #[test]
+    fn test_sanitize_cpf_keeps_digits_only() {
+        let mut request = CreateCustomerRequest {
+            person_type: PersonType::PF,
+            cpf: Some(" 529.982.247-25 ".to_string()),
+            cnpj: None,
+            legal_name: None,
+            ..business(None)
+        };
+        request.sanitize_fields();
+        assert_eq!(request.cpf.as_deref(), Some("52998224725"));
     }
 }
@@ -0,0 +1,51 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::{
+    domain::{
+        dict::DictEntry,
+        enums::{AccountType, PersonType},
+        pix_key::PixKeyType,
+    },
+    shared::{
+        cnpj::format_cnpj,
+        mask::{mask_cpf, mask_name},
+    },
+};
+
+/// What a payer sees about a key before confirming a transfer. Individuals
+/// are shown masked; a business's legal name and CNPJ are public records and
+/// are shown in full.
+#[derive(Debug, Serialize)]
+pub struct DictKeyResponse {
+    pub key_type: PixKeyType,
+    pub key_value: String,
+    pub key_created_at: DateTime<Utc>,
+    pub account_id: Uuid,
+    pub account_type: AccountType,
+    pub owner_type: PersonType,
+    pub owner_name: String,
+    pub owner_document: String,
+}
+
+impl From<DictEntry> for DictKeyResponse {
+    fn from(entry: DictEntry) -> Self {
+        DictKeyResponse {
+            key_type: entry.key_type,
+            key_value: entry.key_value,
+            key_created_at: entry.key_created_at,
+            account_id: entry.account_id,
+            account_type: entry.account_type,
+            owner_type: entry.owner_type,
+            owner_name: match entry.owner_type {
+                PersonType::PF => mask_name(&entry.owner_name),
+                PersonType::PJ => entry.owner_legal_name.unwrap_or(entry.owner_name),
+            },
+            owner_document: match entry.owner_type {
+                PersonType::PF => mask_cpf(entry.owner_cpf.as_deref().unwrap_or_default()),
+                PersonType::PJ => format_cnpj(entry.owner_cnpj.as_deref().unwrap_or_default()),
+            },
+        }
+    }
+}
@@ -0,0 +1,55 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::domain::{
+    key_claim::{KeyClaim, KeyClaimStatus, KeyClaimType},
+    pix_key::PixKeyType,
+};
+
+#[derive(Debug, Deserialize)]
+pub struct CreateKeyClaimRequest {
+    pub claim_type: KeyClaimType,
+    pub key_type: PixKeyType,
+    pub key_value: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct VerifyKeyClaimRequest {
+    pub code: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct KeyClaimResponse {
+    pub id: Uuid,
+    pub claim_type: KeyClaimType,
+    pub status: KeyClaimStatus,
+    pub key_type: PixKeyType,
+    pub key_value: String,
+    pub claimer_account_id: Uuid,
+    pub donor_account_id: Uuid,
+    pub resolution_deadline: Option<DateTime<Utc>>,
+    pub cancelled_by: Option<String>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<KeyClaim> for KeyClaimResponse {
+    fn from(claim: KeyClaim) -> Self {
+        KeyClaimResponse {
+            id: claim.id,
+            claim_type: claim.claim_type,
+            status: claim.status,
+            key_type: claim.key_type,
+            key_value: claim.key_value,
+            claimer_account_id: claim.claimer_account_id,
+            donor_account_id: claim.donor_account_id,
+            resolution_deadline: claim.resolution_deadline,
+            cancelled_by: claim.cancelled_by,
+            completed_at: claim.completed_at,
+            created_at: claim.created_at,
+            updated_at: claim.updated_at,
+        }
+    }
+}
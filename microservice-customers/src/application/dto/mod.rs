@@ -1,3 +1,6 @@
 pub mod customer_dto;
 pub mod account_dto;
 pub mod pix_key_dto;
+pub mod key_claim_dto;
+pub mod dict_dto;
+pub mod transfer_dto;
@@ -33,4 +33,29 @@ impl From<crate::domain::pix_key::PixKey> for PixKeyResponse {
             deactivated_at: pix_key.deactivated_at,
         }
     }
-}
\ No newline at end of file
+}
+#[derive(Debug, Deserialize)]
+pub struct ConfirmPixKeyChallengeRequest {
+    pub code: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PixKeyChallengeResponse {
+    pub challenge_id: Uuid,
+    pub key_type: PixKeyType,
+    pub key_value: String,
+    pub expires_at: DateTime<Utc>,
+    pub attempts_left: i32,
+}
+
+impl From<crate::domain::pix_key_challenge::PixKeyChallenge> for PixKeyChallengeResponse {
+    fn from(challenge: crate::domain::pix_key_challenge::PixKeyChallenge) -> Self {
+        PixKeyChallengeResponse {
+            challenge_id: challenge.id,
+            attempts_left: challenge.attempts_left(),
+            key_type: challenge.key_type,
+            key_value: challenge.key_value,
+            expires_at: challenge.expires_at,
+        }
+    }
+}
@@ -0,0 +1,18 @@
+use serde::Deserialize;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum TransferEventType {
+    CREATED,
+    POSTED,
+    REVERSED,
+}
+
+/// Published by microservice-transfers on the `transfers` topic. Only the
+/// fields the DICT refill reads are kept; serde skips the rest.
+#[derive(Debug, Clone, Deserialize)]
+pub struct TransferEvent {
+    pub event_type: TransferEventType,
+    pub transfer_id: Uuid,
+    pub debit_account_id: Uuid,
+}
@@ -0,0 +1,367 @@
+use chrono::{Duration, Utc};
+use deadpool_postgres::Client;
+use uuid::Uuid;
+
+use crate::{
+    application::{
+        account_service, customer_service,
+        dto::{key_claim_dto::CreateKeyClaimRequest, pix_key_dto::CreatePixKeyRequest},
+        pix_service,
+    },
+    domain::{
+        key_claim::{ClaimParty, KeyClaim, KeyClaimStatus, RESOLUTION_PERIOD_DAYS},
+        pix_key::PixKeyType,
+        pix_key_challenge::PixKeyChallenge,
+    },
+    infraestructure::{
+        db::{customer_repo, key_claim_repo, pix_key_challenge_repo, pix_key_repo},
+        error::{ApiError, ConflictError, InternalError, MyError},
+        notification::notifier::Notifier,
+    },
+    shared::{email, phone},
+};
+
+/// Opens a claim over a key held by another account, sends the claimer a code
+/// to prove they hold the value and notifies the donor.
+pub async fn open_claim(
+    client: &mut Client,
+    notifier: &dyn Notifier,
+    customer_id: Uuid,
+    mut claim_info: CreateKeyClaimRequest,
+) -> Result<KeyClaim, MyError> {
+    if !matches!(claim_info.key_type, PixKeyType::EMAIL | PixKeyType::PHONE) {
+        return Err(MyError::ApiError(ApiError {
+            msg: "Only EMAIL and PHONE keys can be claimed".to_string(),
+        }));
+    }
+
+    claim_info.key_value = match claim_info.key_type {
+        PixKeyType::PHONE => phone::parse_phone(&claim_info.key_value)
+            .map_err(|error| MyError::ApiError(ApiError { msg: error.to_string() }))?,
+        _ => email::parse_email(&claim_info.key_value)
+            .map_err(|error| MyError::ApiError(ApiError { msg: error.to_string() }))?,
+    };
+
+    customer_service::get_active_customer_by_id(client, customer_id).await?;
+    let account = account_service::get_account_by_customer_id(client, customer_id).await?;
+
+    // Possession of the value is proven with the code sent below, not against
+    // the claimer's profile: the donor may still hold it there.
+    let pix_key = pix_key_repo::get_active_pix_key_by_value(client, &claim_info.key_value)
+        .await?
+        .filter(|pix_key| pix_key.key_type == claim_info.key_type)
+        .ok_or(MyError::ApiError(ApiError {
+            msg: "Key is not registered to any account; create it instead".to_string(),
+        }))?;
+
+    if pix_key.account_id == account.id {
+        return Err(MyError::Conflict(ConflictError {
+            msg: "Key is already registered to this account".to_string(),
+        }));
+    }
+
+    let transaction = client.build_transaction().start().await?;
+
+    // The donor may be deleting the key right now; whoever locks it first wins.
+    if !pix_key_repo::lock_pix_key(&transaction, pix_key.id).await?.is_active {
+        return Err(MyError::ApiError(ApiError {
+            msg: "Key is not registered to any account; create it instead".to_string(),
+        }));
+    }
+
+    let claim = key_claim_repo::create_key_claim(
+        &transaction,
+        claim_info.claim_type,
+        &pix_key,
+        account.id,
+    )
+    .await?;
+    pix_service::send_ownership_challenge(
+        &transaction,
+        notifier,
+        account.id,
+        &claim.key_type,
+        &claim.key_value,
+        Some(claim.id),
+    )
+    .await?;
+    transaction.commit().await?;
+
+    // A failed notification leaves the claim OPEN for the timer to retry.
+    match notify_donor(client, notifier, &claim).await {
+        Ok(Some(notified)) => Ok(notified),
+        Ok(None) => Ok(claim),
+        Err(e) => {
+            log::error!("Failed to notify donor of key claim {}: {:?}", claim.id, e);
+            Ok(claim)
+        }
+    }
+}
+
+/// Tells the donor about the claim and starts the resolution period.
+pub async fn notify_donor(
+    client: &Client,
+    notifier: &dyn Notifier,
+    claim: &KeyClaim,
+) -> Result<Option<KeyClaim>, MyError> {
+    let donor = customer_repo::get_customer_by_account_id(client, claim.donor_account_id).await?;
+
+    notifier
+        .send(
+            &PixKeyType::EMAIL,
+            &donor.email,
+            &format!(
+                "A {} claim was opened over your {} key {}. Confirm or cancel it within {} days; \
+                 without an answer the claim is {}.",
+                claim.claim_type,
+                claim.key_type,
+                claim.key_value,
+                RESOLUTION_PERIOD_DAYS,
+                claim.status_on_expiry()
+            ),
+        )
+        .map_err(|e| MyError::Internal(InternalError {
+            msg: format!("Failed to notify donor: {}", e),
+        }))?;
+
+    let deadline = Utc::now() + Duration::days(RESOLUTION_PERIOD_DAYS);
+
+    key_claim_repo::mark_key_claim_notified(client, claim.id, deadline).await
+}
+
+pub async fn list_claims(client: &Client, customer_id: Uuid) -> Result<Vec<KeyClaim>, MyError> {
+    let account = account_service::get_account_by_customer_id(client, customer_id).await?;
+
+    key_claim_repo::get_key_claims_by_account_id(client, account.id).await
+}
+
+/// Claims are only visible to their claimer and donor.
+pub async fn get_claim(client: &Client, customer_id: Uuid, id: Uuid) -> Result<KeyClaim, MyError> {
+    let account = account_service::get_account_by_customer_id(client, customer_id).await?;
+    let claim = key_claim_repo::get_key_claim_by_id(client, id).await?;
+
+    claim.party(account.id).ok_or(MyError::NotFound)?;
+
+    Ok(claim)
+}
+
+/// The claimer proves they hold the claimed email or phone with the code sent
+/// when the claim was opened. Completing the claim requires it.
+pub async fn verify_claim(
+    client: &mut Client,
+    customer_id: Uuid,
+    id: Uuid,
+    code: &str,
+) -> Result<KeyClaim, MyError> {
+    let account = account_service::get_account_by_customer_id(client, customer_id).await?;
+
+    let transaction = client.build_transaction().start().await?;
+    let claim = key_claim_repo::lock_key_claim(&transaction, id).await?;
+
+    if claim.party(account.id) != Some(ClaimParty::CLAIMER) {
+        return Err(MyError::NotFound);
+    }
+
+    if !claim.is_in_progress() {
+        return Err(MyError::Conflict(ConflictError {
+            msg: format!("A {} claim can no longer be verified", claim.status),
+        }));
+    }
+
+    let challenge = pix_key_challenge_repo::lock_key_claim_challenge(&transaction, claim.id, account.id).await?;
+    let transaction = pix_service::redeem_challenge_code(transaction, &challenge, code).await?;
+
+    transaction.commit().await?;
+    Ok(claim)
+}
+
+/// Sends the claimer a new code, subject to the same resend limits as key registration.
+pub async fn resend_claim_code(
+    client: &mut Client,
+    notifier: &dyn Notifier,
+    customer_id: Uuid,
+    id: Uuid,
+) -> Result<PixKeyChallenge, MyError> {
+    let account = account_service::get_account_by_customer_id(client, customer_id).await?;
+
+    let transaction = client.build_transaction().start().await?;
+    let claim = key_claim_repo::lock_key_claim(&transaction, id).await?;
+
+    if claim.party(account.id) != Some(ClaimParty::CLAIMER) {
+        return Err(MyError::NotFound);
+    }
+
+    if !claim.is_in_progress() {
+        return Err(MyError::Conflict(ConflictError {
+            msg: format!("A {} claim can no longer be verified", claim.status),
+        }));
+    }
+
+    let challenge = pix_service::send_ownership_challenge(
+        &transaction,
+        notifier,
+        account.id,
+        &claim.key_type,
+        &claim.key_value,
+        Some(claim.id),
+    )
+    .await?;
+
+    transaction.commit().await?;
+    Ok(challenge)
+}
+
+/// The donor agrees to release the key.
+pub async fn confirm_claim(client: &mut Client, customer_id: Uuid, id: Uuid) -> Result<KeyClaim, MyError> {
+    let account = account_service::get_account_by_customer_id(client, customer_id).await?;
+
+    let transaction = client.build_transaction().start().await?;
+    let claim = key_claim_repo::lock_key_claim(&transaction, id).await?;
+    let party = claim.party(account.id).ok_or(MyError::NotFound)?;
+
+    if !claim.can_confirm(party) {
+        return Err(invalid_transition(&claim, "confirmed", party));
+    }
+
+    let claim = key_claim_repo::update_key_claim_status(
+        &transaction,
+        claim.id,
+        KeyClaimStatus::CONFIRMED,
+        None,
+    )
+    .await?;
+
+    transaction.commit().await?;
+    Ok(claim)
+}
+
+/// The claimer gives up, or the donor refuses to release the key.
+pub async fn cancel_claim(client: &mut Client, customer_id: Uuid, id: Uuid) -> Result<KeyClaim, MyError> {
+    let account = account_service::get_account_by_customer_id(client, customer_id).await?;
+
+    let transaction = client.build_transaction().start().await?;
+    let claim = key_claim_repo::lock_key_claim(&transaction, id).await?;
+    let party = claim.party(account.id).ok_or(MyError::NotFound)?;
+
+    if !claim.can_cancel(party) {
+        return Err(invalid_transition(&claim, "cancelled", party));
+    }
+
+    let claim = key_claim_repo::update_key_claim_status(
+        &transaction,
+        claim.id,
+        KeyClaimStatus::CANCELLED,
+        Some(party.to_string()),
+    )
+    .await?;
+
+    transaction.commit().await?;
+    Ok(claim)
+}
+
+/// Moves the key to the claimer's account once the claim is confirmed.
+pub async fn complete_claim(client: &mut Client, customer_id: Uuid, id: Uuid) -> Result<KeyClaim, MyError> {
+    let customer = customer_service::get_active_customer_by_id(client, customer_id).await?;
+    let account = account_service::get_account_by_customer_id(client, customer_id).await?;
+
+    let transaction = client.build_transaction().start().await?;
+    let claim = key_claim_repo::lock_key_claim(&transaction, id).await?;
+    let party = claim.party(account.id).ok_or(MyError::NotFound)?;
+
+    if !claim.can_complete(party) {
+        return Err(invalid_transition(&claim, "completed", party));
+    }
+
+    if !pix_key_challenge_repo::has_confirmed_key_claim_challenge(
+        &transaction,
+        claim.id,
+        account.id,
+        &claim.key_value,
+    )
+    .await?
+    {
+        return Err(MyError::Conflict(ConflictError {
+            msg: format!(
+                "Confirm the code sent to {} before completing the claim",
+                claim.key_value
+            ),
+        }));
+    }
+
+    // The donor may have deleted the key meanwhile; the value is free either way.
+    pix_key_repo::release_pix_key(&transaction, claim.pix_key_id).await?;
+
+    let pix_key = pix_service::insert_pix_key(
+        &transaction,
+        &customer,
+        account.id,
+        &CreatePixKeyRequest {
+            key_type: claim.key_type.clone(),
+            key_value: claim.key_value.clone(),
+        },
+    )
+    .await?;
+
+    let claim = key_claim_repo::update_key_claim_status(
+        &transaction,
+        claim.id,
+        KeyClaimStatus::COMPLETED,
+        None,
+    )
+    .await?;
+
+    transaction.commit().await?;
+
+    log::info!(
+        "Key claim {} completed: key {} moved to account {}",
+        claim.id,
+        pix_key.id,
+        account.id
+    );
+    Ok(claim)
+}
+
+/// Retries pending donor notifications and resolves claims whose resolution
+/// period lapsed without an answer. Returns how many claims were resolved.
+pub async fn resolve_expired_claims(client: &mut Client, notifier: &dyn Notifier) -> Result<usize, MyError> {
+    for id in key_claim_repo::get_unnotified_key_claim_ids(client).await? {
+        let claim = key_claim_repo::get_key_claim_by_id(client, id).await?;
+
+        // One unreachable donor must not hold up the other claims.
+        if let Err(e) = notify_donor(client, notifier, &claim).await {
+            log::error!("Failed to notify donor of key claim {}: {:?}", claim.id, e);
+        }
+    }
+
+    let mut resolved = 0;
+
+    for id in key_claim_repo::get_expired_key_claim_ids(client).await? {
+        let transaction = client.build_transaction().start().await?;
+        let claim = key_claim_repo::lock_key_claim(&transaction, id).await?;
+
+        // Answered while we were waiting for the lock.
+        if claim.status != KeyClaimStatus::WAITING_RESOLUTION {
+            continue;
+        }
+
+        let status = claim.status_on_expiry();
+        let cancelled_by = (status == KeyClaimStatus::CANCELLED).then(|| "TIMER".to_string());
+
+        key_claim_repo::update_key_claim_status(&transaction, claim.id, status, cancelled_by).await?;
+        transaction.commit().await?;
+
+        log::info!("Key claim {} expired and is now {}", claim.id, status);
+        resolved += 1;
+    }
+
+    Ok(resolved)
+}
+
+fn invalid_transition(claim: &KeyClaim, action: &str, party: ClaimParty) -> MyError {
+    MyError::Conflict(ConflictError {
+        msg: format!(
+            "A {} claim cannot be {} by the {}",
+            claim.status, action, party
+        ),
+    })
+}
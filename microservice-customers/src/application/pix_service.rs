@@ -1,4 +1,5 @@
-use deadpool_postgres::Client;
+use chrono::{Duration, Utc};
+use deadpool_postgres::{Client, Transaction};
 use uuid::Uuid;
 
 use crate::{
@@ -6,39 +7,40 @@ use crate::{
     domain::{
         account::{self, Account},
         customer::{self, Customer},
-        enums::AccountType,
-        pix_key::{PixKey, PixKeyType},
+        enums::{AccountType, PersonType},
+        pix_key::{PixKey, PixKeyType, MAX_KEYS_PER_BUSINESS_ACCOUNT, MAX_KEYS_PER_INDIVIDUAL_ACCOUNT},
+        pix_key_challenge::{NewPixKeyChallenge, PixKeyChallenge, CODE_TTL_MINUTES},
     },
     infraestructure::{
-        db::pix_key_repo,
-        error::{ApiError, MyError},
+        db::{account_repo, key_claim_repo, pix_key_challenge_repo, pix_key_repo},
+        error::{ApiError, ConflictError, InternalError, KeyLimitError, MyError, RateLimitError},
+        notification::notifier::Notifier,
     },
     shared::{
         cnpj::{self, validate_cnpj, CnpjValidationError},
-        cpf, email, phone,
+        cpf, email, otp, phone,
     },
 };
 
+/// Outcome of a key registration request. EMAIL and PHONE keys are only
+/// created once the customer proves possession with the code sent to them.
+pub enum PixKeyRegistration {
+    Created(PixKey),
+    ChallengeSent(PixKeyChallenge),
+}
+
 pub async fn create_pix_key(
     client: &mut Client,
+    notifier: &dyn Notifier,
     pix_key_info: &mut CreatePixKeyRequest,
     customer_id: Uuid,
-) -> Result<PixKey, MyError> {
+) -> Result<PixKeyRegistration, MyError> {
     let uiuiuid = customer_id.clone();
-    let customer: Customer = customer_service::get_customer_by_id(client, customer_id).await?;
+    let customer: Customer = customer_service::get_active_customer_by_id(client, customer_id).await?;
     let account: Account = account_service::get_account_by_customer_id(client, customer_id).await?;
 
     if pix_key_info.key_type == PixKeyType::CNPJ {
-        let cnpj_validation_result = cnpj::validate_cnpj(&pix_key_info.key_value);
-
-        match cnpj_validation_result {
-            Ok(()) => println!("CNPJ validated."),
-            Err(error) => {
-                return Err(MyError::ApiError(ApiError {
-                    msg: error.to_string(),
-                }))
-            }
-        }
+        pix_key_info.key_value = parse_cnpj_key(&customer, &pix_key_info.key_value)?;
     }
 
     if pix_key_info.key_type == PixKeyType::CPF {
@@ -53,7 +55,7 @@ pub async fn create_pix_key(
             }
         }
 
-        if customer.cpf != pix_key_info.key_value {
+        if customer.cpf.as_deref() != Some(pix_key_info.key_value.as_str()) {
             return Err(MyError::ApiError(ApiError {
                 msg: "CPF does not match the customer's CPF".to_string(),
             }));
@@ -61,10 +63,10 @@ pub async fn create_pix_key(
     }
 
     if pix_key_info.key_type == PixKeyType::EMAIL {
-        let email_validation_result = email::validate_email(&pix_key_info.key_value);
+        let email_validation_result = email::parse_email(&pix_key_info.key_value);
 
         match email_validation_result {
-            Ok(()) => println!("Email validated."),
+            Ok(normalized) => pix_key_info.key_value = normalized,
             Err(error) => {
                 return Err(MyError::ApiError(ApiError {
                     msg: error.to_string(),
@@ -81,10 +83,10 @@ pub async fn create_pix_key(
 
     if pix_key_info.key_type == PixKeyType::PHONE {
         if let Some(phone) = &customer.phone {
-            let phone_validation_result = phone::validate_phone(&pix_key_info.key_value);
+            let phone_validation_result = phone::parse_phone(&pix_key_info.key_value);
 
             match phone_validation_result {
-                Ok(()) => println!("Phone validated."),
+                Ok(normalized) => pix_key_info.key_value = normalized,
                 Err(error) => {
                     return Err(MyError::ApiError(ApiError {
                         msg: error.to_string(),
@@ -108,7 +110,334 @@ pub async fn create_pix_key(
         pix_key_info.key_value = uuid::Uuid::new_v4().to_string();
     }
 
-    let new_pix_key = pix_key_repo::create_pix_key(client, pix_key_info, account.id).await?;
+    if matches!(pix_key_info.key_type, PixKeyType::EMAIL | PixKeyType::PHONE) {
+        // No point proving possession of a value someone else holds; that takes a claim.
+        if pix_key_repo::get_active_pix_key_by_value(client, &pix_key_info.key_value)
+            .await?
+            .is_some()
+        {
+            return Err(MyError::Conflict(ConflictError {
+                msg: "Key is already registered; open a key claim to move it".to_string(),
+            }));
+        }
+
+        let transaction = client.build_transaction().start().await?;
+        let challenge = send_ownership_challenge(
+            &transaction,
+            notifier,
+            account.id,
+            &pix_key_info.key_type,
+            &pix_key_info.key_value,
+            None,
+        )
+        .await?;
+        transaction.commit().await?;
+
+        return Ok(PixKeyRegistration::ChallengeSent(challenge));
+    }
+
+    let transaction = client.build_transaction().start().await?;
+
+    let new_pix_key = insert_pix_key(&transaction, &customer, account.id, pix_key_info).await?;
+
+    transaction.commit().await?;
+    Ok(PixKeyRegistration::Created(new_pix_key))
+}
+
+/// Sends a one-time code to the email or phone being registered or claimed.
+/// An open challenge for the same account and value is reused with a new
+/// code, at most MAX_CODE_SENDS times and RESEND_INTERVAL_SECS apart.
+pub async fn send_ownership_challenge(
+    transaction: &Transaction<'_>,
+    notifier: &dyn Notifier,
+    account_id: Uuid,
+    key_type: &PixKeyType,
+    key_value: &str,
+    key_claim_id: Option<Uuid>,
+) -> Result<PixKeyChallenge, MyError> {
+    // Two requests for the same value must not both find no open challenge.
+    account_repo::lock_account(transaction, account_id).await?;
+
+    let now = Utc::now();
+    let open = pix_key_challenge_repo::lock_open_pix_key_challenge(transaction, account_id, key_value).await?;
+
+    if let Some(open) = &open {
+        match open.next_send_at() {
+            None => {
+                return Err(MyError::RateLimited(RateLimitError {
+                    msg: format!(
+                        "Too many codes sent; request a new one after {}",
+                        open.expires_at.to_rfc3339()
+                    ),
+                }))
+            }
+            Some(next_send_at) if next_send_at > now => {
+                return Err(MyError::RateLimited(RateLimitError {
+                    msg: format!(
+                        "Wait {} seconds before requesting another code",
+                        (next_send_at - now).num_seconds() + 1
+                    ),
+                }))
+            }
+            Some(_) => {}
+        }
+    }
+
+    let code = otp::generate_code();
+    let expires_at = now + Duration::minutes(CODE_TTL_MINUTES);
+
+    let challenge = match open {
+        Some(open) => {
+            pix_key_challenge_repo::resend_pix_key_challenge(
+                transaction,
+                open.id,
+                &otp::hash_code(open.id, &code),
+                expires_at,
+                key_claim_id,
+            )
+            .await?
+        }
+        None => {
+            let id = Uuid::new_v4();
+            let challenge = NewPixKeyChallenge {
+                id,
+                account_id,
+                key_type,
+                key_value,
+                code_hash: otp::hash_code(id, &code),
+                expires_at,
+                key_claim_id,
+            };
+            pix_key_challenge_repo::create_pix_key_challenge(transaction, &challenge).await?
+        }
+    };
+
+    notifier
+        .send(
+            key_type,
+            key_value,
+            &format!(
+                "Your PIX key verification code is {}. It expires in {} minutes.",
+                code, CODE_TTL_MINUTES
+            ),
+        )
+        .map_err(|e| MyError::Internal(InternalError {
+            msg: format!("Failed to send verification code: {}", e),
+        }))?;
+
+    Ok(challenge)
+}
+
+/// Checks the code against a locked challenge and marks it confirmed, within
+/// the TTL and attempt limit. A wrong code is counted and committed before the
+/// error is returned; on success the transaction is handed back to finish.
+pub async fn redeem_challenge_code<'a>(
+    transaction: Transaction<'a>,
+    challenge: &PixKeyChallenge,
+    code: &str,
+) -> Result<Transaction<'a>, MyError> {
+    if challenge.confirmed_at.is_some() {
+        return Err(MyError::Conflict(ConflictError {
+            msg: "Verification code was already used".to_string(),
+        }));
+    }
+
+    if challenge.is_expired(Utc::now()) {
+        return Err(MyError::ApiError(ApiError {
+            msg: "Verification code expired; request a new one".to_string(),
+        }));
+    }
+
+    if challenge.attempts_left() == 0 {
+        return Err(MyError::RateLimited(RateLimitError {
+            msg: "Too many wrong codes; request a new one".to_string(),
+        }));
+    }
+
+    if otp::hash_code(challenge.id, code) != challenge.code_hash {
+        pix_key_challenge_repo::record_pix_key_challenge_attempt(&transaction, challenge.id).await?;
+        transaction.commit().await?;
+
+        return Err(MyError::ApiError(ApiError {
+            msg: format!(
+                "Invalid verification code ({} attempts left)",
+                challenge.attempts_left() - 1
+            ),
+        }));
+    }
+
+    pix_key_challenge_repo::confirm_pix_key_challenge(&transaction, challenge.id).await?;
+
+    Ok(transaction)
+}
+
+/// Creates the key once the code matches.
+pub async fn confirm_pix_key_challenge(
+    client: &mut Client,
+    customer_id: Uuid,
+    challenge_id: Uuid,
+    code: &str,
+) -> Result<PixKey, MyError> {
+    let customer: Customer = customer_service::get_active_customer_by_id(client, customer_id).await?;
+    let account: Account = account_service::get_account_by_customer_id(client, customer_id).await?;
 
+    let transaction = client.build_transaction().start().await?;
+    let challenge = pix_key_challenge_repo::lock_pix_key_challenge(&transaction, challenge_id, account.id).await?;
+
+    if challenge.key_claim_id.is_some() {
+        return Err(MyError::ApiError(ApiError {
+            msg: "This code verifies a key claim; confirm it on the claim".to_string(),
+        }));
+    }
+
+    // The email or phone may have changed since the code was sent.
+    let current_value = match challenge.key_type {
+        PixKeyType::EMAIL => Some(customer.email.as_str()),
+        _ => customer.phone.as_deref(),
+    };
+    if current_value != Some(challenge.key_value.as_str()) {
+        return Err(MyError::ApiError(ApiError {
+            msg: "Key value no longer matches the customer's contact details".to_string(),
+        }));
+    }
+
+    let transaction = redeem_challenge_code(transaction, &challenge, code).await?;
+
+    let new_pix_key = insert_pix_key(
+        &transaction,
+        &customer,
+        account.id,
+        &CreatePixKeyRequest {
+            key_type: challenge.key_type,
+            key_value: challenge.key_value,
+        },
+    )
+    .await?;
+
+    transaction.commit().await?;
     Ok(new_pix_key)
 }
+
+/// A CNPJ key must be the customer's own CNPJ, however it is punctuated.
+fn parse_cnpj_key(customer: &Customer, key_value: &str) -> Result<String, MyError> {
+    let key_value = cnpj::parse_cnpj(key_value).map_err(|error| MyError::ApiError(ApiError {
+        msg: error.to_string(),
+    }))?;
+
+    if customer.cnpj.as_deref() != Some(key_value.as_str()) {
+        return Err(MyError::ApiError(ApiError {
+            msg: "CNPJ does not match the customer's CNPJ".to_string(),
+        }));
+    }
+
+    Ok(key_value)
+}
+
+/// Inserts a key for the account unless it already holds as many active keys
+/// as the customer is allowed.
+pub async fn insert_pix_key(
+    transaction: &Transaction<'_>,
+    customer: &Customer,
+    account_id: Uuid,
+    pix_key_info: &CreatePixKeyRequest,
+) -> Result<PixKey, MyError> {
+    // The account row lock makes the count and the insert atomic, so
+    // concurrent requests cannot both take the last free slot.
+    account_repo::lock_account(transaction, account_id).await?;
+
+    let limit = key_limit(customer);
+    let active_keys = pix_key_repo::count_active_pix_keys(transaction, account_id).await?;
+
+    if active_keys >= limit {
+        return Err(MyError::KeyLimitExceeded(KeyLimitError {
+            msg: format!("Account already holds the maximum of {} active PIX keys", limit),
+        }));
+    }
+
+    pix_key_repo::create_pix_key(transaction, pix_key_info, account_id).await
+}
+
+fn key_limit(customer: &Customer) -> i64 {
+    match customer.person_type {
+        PersonType::PF => MAX_KEYS_PER_INDIVIDUAL_ACCOUNT,
+        PersonType::PJ => MAX_KEYS_PER_BUSINESS_ACCOUNT,
+    }
+}
+
+pub async fn list_pix_keys(client: &Client, customer_id: Uuid) -> Result<Vec<PixKey>, MyError> {
+    let account: Account = account_service::get_account_by_customer_id(client, customer_id).await?;
+
+    pix_key_repo::get_pix_keys_by_account_id(client, account.id).await
+}
+
+pub async fn get_pix_key(client: &Client, customer_id: Uuid, id: Uuid) -> Result<PixKey, MyError> {
+    let account: Account = account_service::get_account_by_customer_id(client, customer_id).await?;
+
+    pix_key_repo::get_pix_key_by_id(client, id, account.id).await
+}
+
+/// A key under claim stays until the claim is cancelled or completed.
+pub async fn deactivate_pix_key(client: &mut Client, customer_id: Uuid, id: Uuid) -> Result<PixKey, MyError> {
+    let account: Account = account_service::get_account_by_customer_id(client, customer_id).await?;
+
+    let transaction = client.build_transaction().start().await?;
+
+    let pix_key = pix_key_repo::lock_pix_key(&transaction, id).await?;
+    if pix_key.account_id != account.id {
+        return Err(MyError::NotFound);
+    }
+
+    if key_claim_repo::has_key_claim_in_progress(&transaction, pix_key.id).await? {
+        return Err(MyError::Conflict(ConflictError {
+            msg: "Key has a claim in progress; it cannot be deleted until the claim is cancelled or completed"
+                .to_string(),
+        }));
+    }
+
+    let pix_key = pix_key_repo::deactivate_pix_key(&transaction, id, account.id).await?;
+
+    transaction.commit().await?;
+    Ok(pix_key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn business(cnpj: &str) -> Customer {
+        Customer {
+            id: Uuid::new_v4(),
+            full_name: "Maria Silva".to_string(),
+            email: "contato@empresa.com".to_string(),
+            person_type: PersonType::PJ,
+            cpf: None,
+            cnpj: Some(cnpj.to_string()),
+            legal_name: Some("Empresa Ltda".to_string()),
+            phone: None,
+            is_active: true,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_cnpj_key_matching_customer_is_normalized() {
+        let customer = business("04252011000110");
+        assert_eq!(parse_cnpj_key(&customer, "04.252.011/0001-10").unwrap(), "04252011000110");
+    }
+
+    #[test]
+    fn test_cnpj_key_not_matching_customer_is_rejected() {
+        let customer = business("04252011000110");
+        assert!(matches!(
+            parse_cnpj_key(&customer, "40.688.134/0001-61"),
+            Err(MyError::ApiError(_))
+        ));
+    }
+
+    #[test]
+    fn test_invalid_cnpj_key_is_rejected() {
+        let customer = business("04252011000110");
+        assert!(matches!(parse_cnpj_key(&customer, "04.252.011/0001-11"), Err(MyError::ApiError(_))));
+    }
+}
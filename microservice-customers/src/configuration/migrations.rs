@@ -35,8 +35,9 @@ pub async fn create_migration_table(client: &Client) -> Result<(), tokio_postgre
 pub async fn run_migrations(client: &Client) -> Result<(), Box<dyn std::error::Error>> {
     let migration_files = fs::read_dir("./migrations")?;
 
-    let entries: Vec<_> = migration_files.collect::<Result<_, _>>()?;
-    // entries.reverse();
+    let mut entries: Vec<_> = migration_files.collect::<Result<_, _>>()?;
+    // read_dir has no guaranteed order; migrations must run by file name
+    entries.sort_by_key(|entry| entry.file_name());
 
     for entry in entries {
         let path = entry.path();
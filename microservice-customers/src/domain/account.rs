@@ -14,6 +14,9 @@ pub struct Account {
     pub currency: String,
     pub available_balance: Decimal,
     pub ledger_balance: Decimal,
+    pub balance_version: i64,
+    pub is_blocked: bool,
+    pub blocked_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -3,13 +3,18 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use tokio_pg_mapper_derive::PostgresMapper;
 
+use super::enums::PersonType;
+
 #[derive(Debug, Clone, Serialize, Deserialize, PostgresMapper)]
 #[pg_mapper(table = "customers")]
 pub struct Customer {
     pub id: Uuid,
     pub full_name: String,
     pub email: String,
-    pub cpf: String,
+    pub person_type: PersonType,
+    pub cpf: Option<String>,
+    pub cnpj: Option<String>,
+    pub legal_name: Option<String>,
     pub phone: Option<String>,
     pub is_active: bool,
     pub created_at: DateTime<Utc>,
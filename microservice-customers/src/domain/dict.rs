@@ -0,0 +1,32 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use super::{
+    enums::{AccountType, PersonType},
+    pix_key::PixKeyType,
+};
+
+/// Tokens a customer's lookup bucket holds when full.
+pub const LOOKUP_BUCKET_CAPACITY: i32 = 100;
+/// Tokens taken by every lookup.
+pub const LOOKUP_COST: i32 = 1;
+/// Extra tokens taken when the key does not exist, so enumerating random
+/// values drains the bucket much faster than paying real keys.
+pub const MISSED_LOOKUP_PENALTY: i32 = 20;
+/// Tokens given back by each successful transfer the customer pays.
+pub const TRANSFER_REFILL: i32 = 1;
+
+/// An active key with the account and owner it resolves to.
+#[derive(Debug, Clone)]
+pub struct DictEntry {
+    pub key_type: PixKeyType,
+    pub key_value: String,
+    pub key_created_at: DateTime<Utc>,
+    pub account_id: Uuid,
+    pub account_type: AccountType,
+    pub owner_type: PersonType,
+    pub owner_name: String,
+    pub owner_legal_name: Option<String>,
+    pub owner_cpf: Option<String>,
+    pub owner_cnpj: Option<String>,
+}
@@ -21,3 +21,21 @@ impl fmt::Display for AccountType {
         }
     }
 }
+
+/// Pessoa física (individual, CPF) or pessoa jurídica (business, CNPJ).
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, ToSql, FromSql)]
+#[postgres(name = "person_type")]
+pub enum PersonType {
+    #[default]
+    PF,
+    PJ,
+}
+
+impl fmt::Display for PersonType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PersonType::PF => write!(f, "PF"),
+            PersonType::PJ => write!(f, "PJ"),
+        }
+    }
+}
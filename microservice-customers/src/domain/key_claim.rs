@@ -0,0 +1,217 @@
+use std::fmt;
+
+use chrono::{DateTime, Utc};
+use postgres_types::{FromSql, ToSql};
+use serde::{Deserialize, Serialize};
+use tokio_pg_mapper_derive::PostgresMapper;
+use uuid::Uuid;
+
+use super::pix_key::PixKeyType;
+
+/// Days the donor has to answer a claim before the timer resolves it.
+pub const RESOLUTION_PERIOD_DAYS: i64 = 7;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PostgresMapper)]
+#[pg_mapper(table = "key_claims")]
+pub struct KeyClaim {
+    pub id: Uuid,
+    pub claim_type: KeyClaimType,
+    pub status: KeyClaimStatus,
+    pub key_type: PixKeyType,
+    pub key_value: String,
+    pub pix_key_id: Uuid,
+    pub claimer_account_id: Uuid,
+    pub donor_account_id: Uuid,
+    pub notified_at: Option<DateTime<Utc>>,
+    pub resolution_deadline: Option<DateTime<Utc>>,
+    pub cancelled_by: Option<String>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// PORTABILITY moves a key the claimer already owns and needs the donor's
+/// confirmation. OWNERSHIP takes a key registered by someone else and goes
+/// through unless the donor cancels within the resolution period.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, ToSql, FromSql)]
+#[postgres(name = "key_claim_type")]
+pub enum KeyClaimType {
+    PORTABILITY,
+    OWNERSHIP,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, ToSql, FromSql)]
+#[postgres(name = "key_claim_status")]
+pub enum KeyClaimStatus {
+    OPEN,
+    WAITING_RESOLUTION,
+    CONFIRMED,
+    CANCELLED,
+    COMPLETED,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClaimParty {
+    CLAIMER,
+    DONOR,
+}
+
+impl fmt::Display for KeyClaimType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KeyClaimType::PORTABILITY => write!(f, "PORTABILITY"),
+            KeyClaimType::OWNERSHIP => write!(f, "OWNERSHIP"),
+        }
+    }
+}
+
+impl fmt::Display for KeyClaimStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KeyClaimStatus::OPEN => write!(f, "OPEN"),
+            KeyClaimStatus::WAITING_RESOLUTION => write!(f, "WAITING_RESOLUTION"),
+            KeyClaimStatus::CONFIRMED => write!(f, "CONFIRMED"),
+            KeyClaimStatus::CANCELLED => write!(f, "CANCELLED"),
+            KeyClaimStatus::COMPLETED => write!(f, "COMPLETED"),
+        }
+    }
+}
+
+impl fmt::Display for ClaimParty {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClaimParty::CLAIMER => write!(f, "CLAIMER"),
+            ClaimParty::DONOR => write!(f, "DONOR"),
+        }
+    }
+}
+
+impl KeyClaim {
+    pub fn party(&self, account_id: Uuid) -> Option<ClaimParty> {
+        if account_id == self.claimer_account_id {
+            Some(ClaimParty::CLAIMER)
+        } else if account_id == self.donor_account_id {
+            Some(ClaimParty::DONOR)
+        } else {
+            None
+        }
+    }
+
+    /// Only the donor confirms, and only while the claim waits for an answer.
+    pub fn can_confirm(&self, party: ClaimParty) -> bool {
+        party == ClaimParty::DONOR && self.status == KeyClaimStatus::WAITING_RESOLUTION
+    }
+
+    /// The claimer may give up until the claim completes; the donor may only
+    /// refuse while the claim waits for an answer.
+    pub fn can_cancel(&self, party: ClaimParty) -> bool {
+        match party {
+            ClaimParty::CLAIMER => self.is_in_progress(),
+            ClaimParty::DONOR => self.status == KeyClaimStatus::WAITING_RESOLUTION,
+        }
+    }
+
+    /// OPEN, WAITING_RESOLUTION or CONFIRMED: the key is spoken for.
+    pub fn is_in_progress(&self) -> bool {
+        matches!(
+            self.status,
+            KeyClaimStatus::OPEN | KeyClaimStatus::WAITING_RESOLUTION | KeyClaimStatus::CONFIRMED
+        )
+    }
+
+    pub fn can_complete(&self, party: ClaimParty) -> bool {
+        party == ClaimParty::CLAIMER && self.status == KeyClaimStatus::CONFIRMED
+    }
+
+    /// Status a claim takes when the donor lets the resolution period lapse.
+    pub fn status_on_expiry(&self) -> KeyClaimStatus {
+        match self.claim_type {
+            KeyClaimType::PORTABILITY => KeyClaimStatus::CANCELLED,
+            KeyClaimType::OWNERSHIP => KeyClaimStatus::CONFIRMED,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn claim(claim_type: KeyClaimType, status: KeyClaimStatus) -> KeyClaim {
+        KeyClaim {
+            id: Uuid::new_v4(),
+            claim_type,
+            status,
+            key_type: PixKeyType::EMAIL,
+            key_value: "user@email.com".to_string(),
+            pix_key_id: Uuid::new_v4(),
+            claimer_account_id: Uuid::new_v4(),
+            donor_account_id: Uuid::new_v4(),
+            notified_at: None,
+            resolution_deadline: None,
+            cancelled_by: None,
+            completed_at: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_party() {
+        let claim = claim(KeyClaimType::OWNERSHIP, KeyClaimStatus::OPEN);
+        assert_eq!(claim.party(claim.claimer_account_id), Some(ClaimParty::CLAIMER));
+        assert_eq!(claim.party(claim.donor_account_id), Some(ClaimParty::DONOR));
+        assert_eq!(claim.party(Uuid::new_v4()), None);
+    }
+
+    #[test]
+    fn test_only_donor_confirms_waiting_claims() {
+        let waiting = claim(KeyClaimType::PORTABILITY, KeyClaimStatus::WAITING_RESOLUTION);
+        assert!(waiting.can_confirm(ClaimParty::DONOR));
+        assert!(!waiting.can_confirm(ClaimParty::CLAIMER));
+
+        let open = claim(KeyClaimType::PORTABILITY, KeyClaimStatus::OPEN);
+        assert!(!open.can_confirm(ClaimParty::DONOR));
+    }
+
+    #[test]
+    fn test_cancel_rules() {
+        let confirmed = claim(KeyClaimType::OWNERSHIP, KeyClaimStatus::CONFIRMED);
+        assert!(confirmed.can_cancel(ClaimParty::CLAIMER));
+        assert!(!confirmed.can_cancel(ClaimParty::DONOR));
+
+        let completed = claim(KeyClaimType::OWNERSHIP, KeyClaimStatus::COMPLETED);
+        assert!(!completed.can_cancel(ClaimParty::CLAIMER));
+        assert!(!completed.can_cancel(ClaimParty::DONOR));
+    }
+
+    #[test]
+    fn test_in_progress_statuses() {
+        assert!(claim(KeyClaimType::OWNERSHIP, KeyClaimStatus::OPEN).is_in_progress());
+        assert!(claim(KeyClaimType::OWNERSHIP, KeyClaimStatus::WAITING_RESOLUTION).is_in_progress());
+        assert!(claim(KeyClaimType::OWNERSHIP, KeyClaimStatus::CONFIRMED).is_in_progress());
+        assert!(!claim(KeyClaimType::OWNERSHIP, KeyClaimStatus::CANCELLED).is_in_progress());
+        assert!(!claim(KeyClaimType::OWNERSHIP, KeyClaimStatus::COMPLETED).is_in_progress());
+    }
+
+    #[test]
+    fn test_only_claimer_completes_confirmed_claims() {
+        let confirmed = claim(KeyClaimType::PORTABILITY, KeyClaimStatus::CONFIRMED);
+        assert!(confirmed.can_complete(ClaimParty::CLAIMER));
+        assert!(!confirmed.can_complete(ClaimParty::DONOR));
+
+        let waiting = claim(KeyClaimType::PORTABILITY, KeyClaimStatus::WAITING_RESOLUTION);
+        assert!(!waiting.can_complete(ClaimParty::CLAIMER));
+    }
+
+    #[test]
+    fn test_status_on_expiry() {
+        assert_eq!(
+            claim(KeyClaimType::PORTABILITY, KeyClaimStatus::WAITING_RESOLUTION).status_on_expiry(),
+            KeyClaimStatus::CANCELLED
+        );
+        assert_eq!(
+            claim(KeyClaimType::OWNERSHIP, KeyClaimStatus::WAITING_RESOLUTION).status_on_expiry(),
+            KeyClaimStatus::CONFIRMED
+        );
+    }
+}
@@ -6,6 +6,11 @@ use serde::{Deserialize, Serialize};
 use tokio_pg_mapper_derive::PostgresMapper;
 use uuid::Uuid;
 
+/// BCB cap on active keys per account held by an individual (PF).
+pub const MAX_KEYS_PER_INDIVIDUAL_ACCOUNT: i64 = 5;
+/// BCB cap on active keys per account held by a business (PJ).
+pub const MAX_KEYS_PER_BUSINESS_ACCOUNT: i64 = 20;
+
 #[derive(Debug, Clone, Serialize, Deserialize, PostgresMapper)]
 #[pg_mapper(table = "pix_keys")]
 pub struct PixKey {
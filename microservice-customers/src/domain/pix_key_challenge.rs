@@ -0,0 +1,103 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use tokio_pg_mapper_derive::PostgresMapper;
+use uuid::Uuid;
+
+use super::pix_key::PixKeyType;
+
+/// Minutes a verification code stays valid.
+pub const CODE_TTL_MINUTES: i64 = 10;
+/// Wrong codes accepted before the challenge is locked.
+pub const MAX_CODE_ATTEMPTS: i32 = 5;
+/// Seconds to wait before another code is sent for the same challenge.
+pub const RESEND_INTERVAL_SECS: i64 = 60;
+/// Codes sent on one challenge; after that the customer waits for it to expire.
+pub const MAX_CODE_SENDS: i32 = 3;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PostgresMapper)]
+#[pg_mapper(table = "pix_key_challenges")]
+pub struct PixKeyChallenge {
+    pub id: Uuid,
+    pub account_id: Uuid,
+    pub key_type: PixKeyType,
+    pub key_value: String,
+    pub code_hash: String,
+    pub attempts: i32,
+    pub expires_at: DateTime<Utc>,
+    pub confirmed_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub sends: i32,
+    pub last_sent_at: DateTime<Utc>,
+    pub key_claim_id: Option<Uuid>,
+}
+
+/// A challenge about to be inserted, with its code already hashed.
+pub struct NewPixKeyChallenge<'a> {
+    pub id: Uuid,
+    pub account_id: Uuid,
+    pub key_type: &'a PixKeyType,
+    pub key_value: &'a str,
+    pub code_hash: String,
+    pub expires_at: DateTime<Utc>,
+    pub key_claim_id: Option<Uuid>,
+}
+
+impl PixKeyChallenge {
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        now >= self.expires_at
+    }
+
+    pub fn attempts_left(&self) -> i32 {
+        (MAX_CODE_ATTEMPTS - self.attempts).max(0)
+    }
+
+    /// When another code may be sent on this challenge, or None once it has
+    /// sent as many as it may.
+    pub fn next_send_at(&self) -> Option<DateTime<Utc>> {
+        (self.sends < MAX_CODE_SENDS).then(|| self.last_sent_at + Duration::seconds(RESEND_INTERVAL_SECS))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn challenge(sends: i32, last_sent_at: DateTime<Utc>) -> PixKeyChallenge {
+        PixKeyChallenge {
+            id: Uuid::new_v4(),
+            account_id: Uuid::new_v4(),
+            key_type: PixKeyType::EMAIL,
+            key_value: "user@email.com".to_string(),
+            code_hash: String::new(),
+            attempts: 0,
+            expires_at: last_sent_at + Duration::minutes(CODE_TTL_MINUTES),
+            confirmed_at: None,
+            created_at: last_sent_at,
+            sends,
+            last_sent_at,
+            key_claim_id: None,
+        }
+    }
+
+    #[test]
+    fn test_resend_waits_for_interval() {
+        let sent_at = Utc::now();
+        assert_eq!(
+            challenge(1, sent_at).next_send_at(),
+            Some(sent_at + Duration::seconds(RESEND_INTERVAL_SECS))
+        );
+    }
+
+    #[test]
+    fn test_no_resend_after_max_sends() {
+        assert!(challenge(MAX_CODE_SENDS - 1, Utc::now()).next_send_at().is_some());
+        assert_eq!(challenge(MAX_CODE_SENDS, Utc::now()).next_send_at(), None);
+    }
+
+    #[test]
+    fn test_attempts_left_never_negative() {
+        let mut challenge = challenge(1, Utc::now());
+        challenge.attempts = MAX_CODE_ATTEMPTS + 2;
+        assert_eq!(challenge.attempts_left(), 0);
+    }
+}
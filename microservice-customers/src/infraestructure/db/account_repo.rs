@@ -2,7 +2,7 @@ use deadpool_postgres::{Client, Transaction};
 use tokio_pg_mapper::FromTokioPostgresRow;
 use uuid::Uuid;
 
-use crate::{application::dto::account_dto::AccountCreateRequest, domain::{account::Account, customer::Customer}, infraestructure::error::{map_db_error, InternalError, MyError}};
+use crate::{application::dto::account_dto::{AccountBlockedEvent, AccountCreateRequest, BalanceChangedEvent}, domain::{account::Account, customer::Customer}, infraestructure::error::{map_db_error, InternalError, MyError}};
 
 pub async fn create_account(
     client_or_tx: &Transaction<'_>, account: AccountCreateRequest) -> Result<Account, MyError> {
@@ -45,3 +45,80 @@ pub async fn get_account_by_customer_id(client: &Client, id: Uuid) -> Result<Acc
 
     Ok(account)
 }
+
+/// Returns false when the account is unknown or already holds a newer version.
+pub async fn update_balance(client: &Client, event: &BalanceChangedEvent) -> Result<bool, MyError> {
+    let stmt = include_str!("../../../sql/update_account_balance.sql");
+    let stmt = client.prepare(stmt).await.map_err(map_db_error)?;
+
+    let updated = client
+        .execute(
+            &stmt,
+            &[
+                &event.account_id,
+                &event.ledger_balance,
+                &event.available_balance,
+                &event.version,
+            ],
+        )
+        .await
+        .map_err(map_db_error)?;
+
+    Ok(updated == 1)
+}
+
+/// Serializes concurrent changes to the account's Pix keys until the
+/// transaction ends.
+pub async fn lock_account(tx: &Transaction<'_>, id: Uuid) -> Result<(), MyError> {
+    let stmt = include_str!("../../../sql/lock_account.sql");
+    let stmt = tx.prepare(stmt).await.map_err(map_db_error)?;
+
+    tx.query_opt(&stmt, &[&id])
+        .await
+        .map_err(map_db_error)?
+        .ok_or(MyError::NotFound)?;
+
+    Ok(())
+}
+
+/// Blocks every account of the customer for new transfers and queues the
+/// account-blocked event for the ledger.
+pub async fn block_accounts_by_customer_id(tx: &Transaction<'_>, customer_id: Uuid) -> Result<u64, MyError> {
+    let stmt = include_str!("../../../sql/block_accounts_by_customer_id.sql");
+    let stmt = tx.prepare(stmt).await.map_err(map_db_error)?;
+
+    tx.execute(&stmt, &[&customer_id]).await.map_err(map_db_error)
+}
+
+/// Oldest queued blocks, locked so concurrent publishers skip them.
+pub async fn lock_account_outbox_batch(
+    tx: &Transaction<'_>,
+    limit: i64,
+) -> Result<Vec<(i64, AccountBlockedEvent)>, MyError> {
+    let stmt = include_str!("../../../sql/lock_account_outbox_batch.sql");
+    let stmt = tx.prepare(stmt).await.map_err(map_db_error)?;
+
+    let rows = tx.query(&stmt, &[&limit]).await.map_err(map_db_error)?;
+
+    Ok(rows
+        .iter()
+        .map(|row| {
+            (
+                row.get(0),
+                AccountBlockedEvent {
+                    account_id: row.get(1),
+                    blocked_at: row.get(2),
+                },
+            )
+        })
+        .collect())
+}
+
+pub async fn delete_account_outbox(tx: &Transaction<'_>, ids: &[i64]) -> Result<(), MyError> {
+    let stmt = include_str!("../../../sql/delete_account_outbox.sql");
+    let stmt = tx.prepare(stmt).await.map_err(map_db_error)?;
+
+    tx.execute(&stmt, &[&ids]).await.map_err(map_db_error)?;
+
+    Ok(())
+}
@@ -30,7 +30,15 @@ pub async fn create_customer(
     let stmt = client_or_tx.prepare(&sql).await.map_err(map_db_error)?;
 
     let rows = client_or_tx
-        .query(&stmt, &[&req.full_name, &req.email, &req.cpf])
+        .query(&stmt, &[
+                &req.full_name,
+                &req.email,
+                &req.cpf,
+                &req.phone,
+                &req.person_type,
+                &req.cnpj,
+                &req.legal_name,
+            ])
         .await
         .map_err(map_db_error)?;
 
@@ -57,3 +65,66 @@ pub async fn get_customer_by_id(client: &Client, id: Uuid) -> Result<Customer, M
     Ok(customer)
 }
 
+/// The owner of an account, e.g. to reach the other party of a key claim.
+pub async fn get_customer_by_account_id(client: &Client, account_id: Uuid) -> Result<Customer, MyError> {
+    let stmt = include_str!("../../../sql/get_customer_by_account_id.sql");
+    let stmt = stmt.replace("$table_fields", &Customer::sql_table_fields());
+    let stmt = client.prepare(&stmt).await.map_err(map_db_error)?;
+
+    let row = client
+        .query_opt(&stmt, &[&account_id])
+        .await
+        .map_err(map_db_error)?
+        .ok_or(MyError::NotFound)?;
+
+    Customer::from_row_ref(&row).map_err(|e| MyError::Internal(InternalError { msg: e.to_string() }))
+}
+
+/// Locks the customer row until the transaction ends.
+pub async fn lock_customer(tx: &Transaction<'_>, id: Uuid) -> Result<Customer, MyError> {
+    let stmt = include_str!("../../../sql/lock_customer.sql");
+    let stmt = stmt.replace("$table_fields", &Customer::sql_table_fields());
+    let stmt = tx.prepare(&stmt).await.map_err(map_db_error)?;
+
+    let row = tx
+        .query_opt(&stmt, &[&id])
+        .await
+        .map_err(map_db_error)?
+        .ok_or(MyError::NotFound)?;
+
+    Customer::from_row_ref(&row).map_err(|e| MyError::Internal(InternalError { msg: e.to_string() }))
+}
+
+pub async fn update_customer(
+    tx: &Transaction<'_>,
+    id: Uuid,
+    full_name: &str,
+    email: &str,
+    phone: Option<&str>,
+) -> Result<Customer, MyError> {
+    let raw_sql = include_str!("../../../sql/update_customer.sql");
+    let sql = raw_sql.replace("$table_fields", &Customer::sql_table_fields());
+    let stmt = tx.prepare(&sql).await.map_err(map_db_error)?;
+
+    let row = tx
+        .query_opt(&stmt, &[&id, &full_name, &email, &phone])
+        .await
+        .map_err(map_db_error)?
+        .ok_or(MyError::NotFound)?;
+
+    Customer::from_row_ref(&row).map_err(|e| MyError::Internal(InternalError { msg: e.to_string() }))
+}
+
+pub async fn deactivate_customer(tx: &Transaction<'_>, id: Uuid) -> Result<Customer, MyError> {
+    let raw_sql = include_str!("../../../sql/deactivate_customer.sql");
+    let sql = raw_sql.replace("$table_fields", &Customer::sql_table_fields());
+    let stmt = tx.prepare(&sql).await.map_err(map_db_error)?;
+
+    let row = tx
+        .query_opt(&stmt, &[&id])
+        .await
+        .map_err(map_db_error)?
+        .ok_or(MyError::NotFound)?;
+
+    Customer::from_row_ref(&row).map_err(|e| MyError::Internal(InternalError { msg: e.to_string() }))
+}
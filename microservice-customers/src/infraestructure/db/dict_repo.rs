@@ -0,0 +1,90 @@
+use deadpool_postgres::Client;
+use uuid::Uuid;
+
+use crate::{
+    domain::dict::DictEntry,
+    infraestructure::error::{map_db_error, MyError},
+};
+
+pub async fn get_dict_entry_by_key_value(client: &Client, key_value: &str) -> Result<Option<DictEntry>, MyError> {
+    let stmt = include_str!("../../../sql/get_dict_entry_by_key_value.sql");
+    let stmt = client.prepare(stmt).await.map_err(map_db_error)?;
+
+    let row = client
+        .query_opt(&stmt, &[&key_value])
+        .await
+        .map_err(map_db_error)?;
+
+    Ok(row.map(|row| DictEntry {
+        key_type: row.get(0),
+        key_value: row.get(1),
+        key_created_at: row.get(2),
+        account_id: row.get(3),
+        account_type: row.get(4),
+        owner_type: row.get(5),
+        owner_name: row.get(6),
+        owner_legal_name: row.get(7),
+        owner_cpf: row.get(8),
+        owner_cnpj: row.get(9),
+    }))
+}
+
+/// Takes `cost` tokens from the customer's bucket, creating it full on first
+/// use. Returns the tokens left, or None when the bucket cannot cover the cost.
+pub async fn take_lookup_tokens(
+    client: &Client,
+    customer_id: Uuid,
+    cost: i32,
+    capacity: i32,
+) -> Result<Option<i32>, MyError> {
+    let create = include_str!("../../../sql/create_dict_lookup_bucket.sql");
+    let create = client.prepare(create).await.map_err(map_db_error)?;
+    client
+        .execute(&create, &[&customer_id, &capacity])
+        .await
+        .map_err(map_db_error)?;
+
+    let take = include_str!("../../../sql/take_dict_lookup_tokens.sql");
+    let take = client.prepare(take).await.map_err(map_db_error)?;
+
+    let row = client
+        .query_opt(&take, &[&customer_id, &cost])
+        .await
+        .map_err(map_db_error)?;
+
+    Ok(row.map(|row| row.get(0)))
+}
+
+/// Takes up to `tokens` from the bucket without going below zero.
+pub async fn drain_lookup_tokens(client: &Client, customer_id: Uuid, tokens: i32) -> Result<i32, MyError> {
+    let stmt = include_str!("../../../sql/drain_dict_lookup_tokens.sql");
+    let stmt = client.prepare(stmt).await.map_err(map_db_error)?;
+
+    let row = client
+        .query_one(&stmt, &[&customer_id, &tokens])
+        .await
+        .map_err(map_db_error)?;
+
+    Ok(row.get(0))
+}
+
+/// Gives tokens back to the bucket of the customer owning `account_id`.
+/// Returns false when the transfer already refilled it or the account is
+/// not ours.
+pub async fn refill_lookup_bucket(
+    client: &Client,
+    transfer_id: Uuid,
+    account_id: Uuid,
+    tokens: i32,
+    capacity: i32,
+) -> Result<bool, MyError> {
+    let stmt = include_str!("../../../sql/refill_dict_lookup_bucket.sql");
+    let stmt = client.prepare(stmt).await.map_err(map_db_error)?;
+
+    let refilled = client
+        .execute(&stmt, &[&transfer_id, &account_id, &tokens, &capacity])
+        .await
+        .map_err(map_db_error)?;
+
+    Ok(refilled == 1)
+}
@@ -0,0 +1,165 @@
+use chrono::{DateTime, Utc};
+use deadpool_postgres::{Client, Transaction};
+use tokio_pg_mapper::FromTokioPostgresRow;
+use uuid::Uuid;
+
+use crate::{
+    domain::{
+        key_claim::{KeyClaim, KeyClaimStatus, KeyClaimType},
+        pix_key::PixKey,
+    },
+    infraestructure::error::{map_db_error, InternalError, MyError},
+};
+
+fn to_key_claim(row: &tokio_postgres::Row) -> Result<KeyClaim, MyError> {
+    KeyClaim::from_row_ref(row).map_err(|e| MyError::Internal(InternalError { msg: e.to_string() }))
+}
+
+pub async fn create_key_claim(
+    client_or_tx: &Transaction<'_>,
+    claim_type: KeyClaimType,
+    pix_key: &PixKey,
+    claimer_account_id: Uuid,
+) -> Result<KeyClaim, MyError> {
+    let raw_sql = include_str!("../../../sql/create_key_claim.sql");
+    let sql = raw_sql.replace("$table_fields", &KeyClaim::sql_table_fields());
+    let stmt = client_or_tx.prepare(&sql).await.map_err(map_db_error)?;
+
+    let rows = client_or_tx
+        .query(
+            &stmt,
+            &[
+                &claim_type,
+                &pix_key.key_type,
+                &pix_key.key_value,
+                &pix_key.id,
+                &claimer_account_id,
+                &pix_key.account_id,
+            ],
+        )
+        .await
+        .map_err(map_db_error)?;
+
+    let row = rows.first().ok_or(MyError::Internal(InternalError { msg: "Nenhum registro retornado".into() }))?;
+    to_key_claim(row)
+}
+
+pub async fn get_key_claim_by_id(client: &Client, id: Uuid) -> Result<KeyClaim, MyError> {
+    let stmt = include_str!("../../../sql/get_key_claim_by_id.sql");
+    let stmt = stmt.replace("$table_fields", &KeyClaim::sql_table_fields());
+    let stmt = client.prepare(&stmt).await.map_err(map_db_error)?;
+
+    let row = client
+        .query_opt(&stmt, &[&id])
+        .await
+        .map_err(map_db_error)?
+        .ok_or(MyError::NotFound)?;
+
+    to_key_claim(&row)
+}
+
+/// Locks the claim until the transaction ends so a donor answer, the claimer
+/// and the resolution timer never act on the same claim at once.
+pub async fn lock_key_claim(tx: &Transaction<'_>, id: Uuid) -> Result<KeyClaim, MyError> {
+    let stmt = include_str!("../../../sql/lock_key_claim.sql");
+    let stmt = stmt.replace("$table_fields", &KeyClaim::sql_table_fields());
+    let stmt = tx.prepare(&stmt).await.map_err(map_db_error)?;
+
+    let row = tx
+        .query_opt(&stmt, &[&id])
+        .await
+        .map_err(map_db_error)?
+        .ok_or(MyError::NotFound)?;
+
+    to_key_claim(&row)
+}
+
+/// Claims where the account is either the claimer or the donor.
+pub async fn get_key_claims_by_account_id(client: &Client, account_id: Uuid) -> Result<Vec<KeyClaim>, MyError> {
+    let stmt = include_str!("../../../sql/get_key_claims_by_account_id.sql");
+    let stmt = stmt.replace("$table_fields", &KeyClaim::sql_table_fields());
+    let stmt = client.prepare(&stmt).await.map_err(map_db_error)?;
+
+    client
+        .query(&stmt, &[&account_id])
+        .await
+        .map_err(map_db_error)?
+        .iter()
+        .map(to_key_claim)
+        .collect()
+}
+
+/// Moves an OPEN claim to WAITING_RESOLUTION. Returns None when the claim was
+/// already notified.
+pub async fn mark_key_claim_notified(
+    client: &Client,
+    id: Uuid,
+    resolution_deadline: DateTime<Utc>,
+) -> Result<Option<KeyClaim>, MyError> {
+    let raw_sql = include_str!("../../../sql/mark_key_claim_notified.sql");
+    let sql = raw_sql.replace("$table_fields", &KeyClaim::sql_table_fields());
+    let stmt = client.prepare(&sql).await.map_err(map_db_error)?;
+
+    client
+        .query_opt(&stmt, &[&id, &resolution_deadline])
+        .await
+        .map_err(map_db_error)?
+        .as_ref()
+        .map(to_key_claim)
+        .transpose()
+}
+
+pub async fn update_key_claim_status(
+    tx: &Transaction<'_>,
+    id: Uuid,
+    status: KeyClaimStatus,
+    cancelled_by: Option<String>,
+) -> Result<KeyClaim, MyError> {
+    let raw_sql = include_str!("../../../sql/update_key_claim_status.sql");
+    let sql = raw_sql.replace("$table_fields", &KeyClaim::sql_table_fields());
+    let stmt = tx.prepare(&sql).await.map_err(map_db_error)?;
+
+    let row = tx
+        .query_opt(&stmt, &[&id, &status, &cancelled_by])
+        .await
+        .map_err(map_db_error)?
+        .ok_or(MyError::NotFound)?;
+
+    to_key_claim(&row)
+}
+
+pub async fn get_unnotified_key_claim_ids(client: &Client) -> Result<Vec<Uuid>, MyError> {
+    let stmt = include_str!("../../../sql/get_unnotified_key_claim_ids.sql");
+    let stmt = client.prepare(stmt).await.map_err(map_db_error)?;
+
+    let rows = client.query(&stmt, &[]).await.map_err(map_db_error)?;
+
+    Ok(rows.iter().map(|row| row.get(0)).collect())
+}
+
+pub async fn get_expired_key_claim_ids(client: &Client) -> Result<Vec<Uuid>, MyError> {
+    let stmt = include_str!("../../../sql/get_expired_key_claim_ids.sql");
+    let stmt = client.prepare(stmt).await.map_err(map_db_error)?;
+
+    let rows = client.query(&stmt, &[]).await.map_err(map_db_error)?;
+
+    Ok(rows.iter().map(|row| row.get(0)).collect())
+}
+
+/// Whether an OPEN, WAITING_RESOLUTION or CONFIRMED claim is over the key.
+pub async fn has_key_claim_in_progress(tx: &Transaction<'_>, pix_key_id: Uuid) -> Result<bool, MyError> {
+    let stmt = include_str!("../../../sql/has_key_claim_in_progress.sql");
+    let stmt = tx.prepare(stmt).await.map_err(map_db_error)?;
+
+    let row = tx.query_one(&stmt, &[&pix_key_id]).await.map_err(map_db_error)?;
+
+    Ok(row.get(0))
+}
+
+/// Cancels claims in progress on either side of the customer's accounts.
+pub async fn cancel_key_claims_by_customer_id(tx: &Transaction<'_>, customer_id: Uuid) -> Result<u64, MyError> {
+    let stmt = include_str!("../../../sql/cancel_key_claims_by_customer_id.sql");
+    let stmt = tx.prepare(stmt).await.map_err(map_db_error)?;
+
+    tx.execute(&stmt, &[&customer_id]).await.map_err(map_db_error)
+}
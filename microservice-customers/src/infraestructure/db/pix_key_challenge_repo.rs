@@ -0,0 +1,146 @@
+use chrono::{DateTime, Utc};
+use deadpool_postgres::Transaction;
+use tokio_pg_mapper::FromTokioPostgresRow;
+use uuid::Uuid;
+
+use crate::{
+    domain::pix_key_challenge::{NewPixKeyChallenge, PixKeyChallenge},
+    infraestructure::error::{map_db_error, InternalError, MyError},
+};
+
+pub async fn create_pix_key_challenge(
+    tx: &Transaction<'_>,
+    challenge: &NewPixKeyChallenge<'_>,
+) -> Result<PixKeyChallenge, MyError> {
+    let raw_sql = include_str!("../../../sql/create_pix_key_challenge.sql");
+    let sql = raw_sql.replace("$table_fields", &PixKeyChallenge::sql_table_fields());
+    let stmt = tx.prepare(&sql).await.map_err(map_db_error)?;
+
+    let rows = tx
+        .query(
+            &stmt,
+            &[
+                &challenge.id,
+                &challenge.account_id,
+                challenge.key_type,
+                &challenge.key_value,
+                &challenge.code_hash,
+                &challenge.expires_at,
+                &challenge.key_claim_id,
+            ],
+        )
+        .await
+        .map_err(map_db_error)?;
+
+    let row = rows.first().ok_or(MyError::Internal(InternalError { msg: "Nenhum registro retornado".into() }))?;
+    PixKeyChallenge::from_row_ref(row).map_err(|e| MyError::Internal(InternalError { msg: e.to_string() }))
+}
+
+/// The unconfirmed, unexpired challenge of the account for `key_value`, locked
+/// so a resend and a confirmation never race.
+pub async fn lock_open_pix_key_challenge(
+    tx: &Transaction<'_>,
+    account_id: Uuid,
+    key_value: &str,
+) -> Result<Option<PixKeyChallenge>, MyError> {
+    let stmt = include_str!("../../../sql/lock_open_pix_key_challenge.sql");
+    let stmt = stmt.replace("$table_fields", &PixKeyChallenge::sql_table_fields());
+    let stmt = tx.prepare(&stmt).await.map_err(map_db_error)?;
+
+    tx.query_opt(&stmt, &[&account_id, &key_value])
+        .await
+        .map_err(map_db_error)?
+        .map(|row| PixKeyChallenge::from_row_ref(&row).map_err(|e| MyError::Internal(InternalError { msg: e.to_string() })))
+        .transpose()
+}
+
+/// Replaces the code of an open challenge and gives it a fresh TTL and attempt count.
+pub async fn resend_pix_key_challenge(
+    tx: &Transaction<'_>,
+    id: Uuid,
+    code_hash: &str,
+    expires_at: DateTime<Utc>,
+    key_claim_id: Option<Uuid>,
+) -> Result<PixKeyChallenge, MyError> {
+    let raw_sql = include_str!("../../../sql/resend_pix_key_challenge.sql");
+    let sql = raw_sql.replace("$table_fields", &PixKeyChallenge::sql_table_fields());
+    let stmt = tx.prepare(&sql).await.map_err(map_db_error)?;
+
+    let row = tx
+        .query_opt(&stmt, &[&id, &code_hash, &expires_at, &key_claim_id])
+        .await
+        .map_err(map_db_error)?
+        .ok_or(MyError::NotFound)?;
+
+    PixKeyChallenge::from_row_ref(&row).map_err(|e| MyError::Internal(InternalError { msg: e.to_string() }))
+}
+
+/// Locks the challenge so concurrent confirmations are counted one by one.
+pub async fn lock_pix_key_challenge(tx: &Transaction<'_>, id: Uuid, account_id: Uuid) -> Result<PixKeyChallenge, MyError> {
+    let stmt = include_str!("../../../sql/lock_pix_key_challenge.sql");
+    let stmt = stmt.replace("$table_fields", &PixKeyChallenge::sql_table_fields());
+    let stmt = tx.prepare(&stmt).await.map_err(map_db_error)?;
+
+    let row = tx
+        .query_opt(&stmt, &[&id, &account_id])
+        .await
+        .map_err(map_db_error)?
+        .ok_or(MyError::NotFound)?;
+
+    PixKeyChallenge::from_row_ref(&row).map_err(|e| MyError::Internal(InternalError { msg: e.to_string() }))
+}
+
+pub async fn record_pix_key_challenge_attempt(tx: &Transaction<'_>, id: Uuid) -> Result<(), MyError> {
+    let stmt = include_str!("../../../sql/record_pix_key_challenge_attempt.sql");
+    let stmt = tx.prepare(stmt).await.map_err(map_db_error)?;
+
+    tx.execute(&stmt, &[&id]).await.map_err(map_db_error)?;
+
+    Ok(())
+}
+
+pub async fn confirm_pix_key_challenge(tx: &Transaction<'_>, id: Uuid) -> Result<(), MyError> {
+    let stmt = include_str!("../../../sql/confirm_pix_key_challenge.sql");
+    let stmt = tx.prepare(stmt).await.map_err(map_db_error)?;
+
+    tx.execute(&stmt, &[&id]).await.map_err(map_db_error)?;
+
+    Ok(())
+}
+
+/// Locks the latest challenge sent to the claimer of a key claim.
+pub async fn lock_key_claim_challenge(
+    tx: &Transaction<'_>,
+    key_claim_id: Uuid,
+    account_id: Uuid,
+) -> Result<PixKeyChallenge, MyError> {
+    let stmt = include_str!("../../../sql/lock_key_claim_challenge.sql");
+    let stmt = stmt.replace("$table_fields", &PixKeyChallenge::sql_table_fields());
+    let stmt = tx.prepare(&stmt).await.map_err(map_db_error)?;
+
+    let row = tx
+        .query_opt(&stmt, &[&key_claim_id, &account_id])
+        .await
+        .map_err(map_db_error)?
+        .ok_or(MyError::NotFound)?;
+
+    PixKeyChallenge::from_row_ref(&row).map_err(|e| MyError::Internal(InternalError { msg: e.to_string() }))
+}
+
+/// Whether the claimer confirmed a code sent to `key_value` for this claim.
+pub async fn has_confirmed_key_claim_challenge(
+    tx: &Transaction<'_>,
+    key_claim_id: Uuid,
+    account_id: Uuid,
+    key_value: &str,
+) -> Result<bool, MyError> {
+    let stmt = include_str!("../../../sql/has_confirmed_key_claim_challenge.sql");
+    let stmt = tx.prepare(stmt).await.map_err(map_db_error)?;
+
+    let row = tx
+        .query_one(&stmt, &[&key_claim_id, &account_id, &key_value])
+        .await
+        .map_err(map_db_error)?;
+
+    Ok(row.get(0))
+}
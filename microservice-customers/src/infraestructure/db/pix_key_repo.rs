@@ -1,14 +1,14 @@
-use deadpool_postgres::Client;
+use deadpool_postgres::{Client, Transaction};
 use tokio_pg_mapper::FromTokioPostgresRow;
 use uuid::Uuid;
 
-use crate::{application::dto::pix_key_dto::CreatePixKeyRequest, domain::pix_key::PixKey, infraestructure::error::{map_db_error, InternalError, MyError}};
+use crate::{application::dto::pix_key_dto::CreatePixKeyRequest, domain::pix_key::{PixKey, PixKeyType}, infraestructure::error::{map_db_error, InternalError, MyError}};
 
 
 
 
 pub async fn create_pix_key(
-    client: &Client,
+    client: &Transaction<'_>,
     req: &CreatePixKeyRequest,
     account_id: Uuid,
 ) -> Result<PixKey, MyError>{
@@ -24,4 +24,119 @@ pub async fn create_pix_key(
     let row = rows.first().ok_or(MyError::Internal(InternalError { msg: "Nenhum registro retornado".into() }))?;
 
     PixKey::from_row_ref(row).map_err(|e| MyError::Internal(InternalError { msg: e.to_string() }))
-}
\ No newline at end of file
+}
+pub async fn get_pix_keys_by_account_id(client: &Client, account_id: Uuid) -> Result<Vec<PixKey>, MyError> {
+    let stmt = include_str!("../../../sql/get_pix_keys_by_account_id.sql");
+    let stmt = stmt.replace("$table_fields", &PixKey::sql_table_fields());
+    let stmt = client.prepare(&stmt).await.map_err(map_db_error)?;
+
+    client
+        .query(&stmt, &[&account_id])
+        .await
+        .map_err(map_db_error)?
+        .iter()
+        .map(|row| PixKey::from_row_ref(row).map_err(|e| MyError::Internal(InternalError { msg: e.to_string() })))
+        .collect()
+}
+
+/// Scoped to the account so a customer can never read another customer's key.
+pub async fn get_pix_key_by_id(client: &Client, id: Uuid, account_id: Uuid) -> Result<PixKey, MyError> {
+    let stmt = include_str!("../../../sql/get_pix_key_by_id.sql");
+    let stmt = stmt.replace("$table_fields", &PixKey::sql_table_fields());
+    let stmt = client.prepare(&stmt).await.map_err(map_db_error)?;
+
+    let row = client
+        .query_opt(&stmt, &[&id, &account_id])
+        .await
+        .map_err(map_db_error)?
+        .ok_or(MyError::NotFound)?;
+
+    PixKey::from_row_ref(&row).map_err(|e| MyError::Internal(InternalError { msg: e.to_string() }))
+}
+
+/// Locks the key until the transaction ends, so deleting it and opening a
+/// claim over it never interleave.
+pub async fn lock_pix_key(tx: &Transaction<'_>, id: Uuid) -> Result<PixKey, MyError> {
+    let stmt = include_str!("../../../sql/lock_pix_key.sql");
+    let stmt = stmt.replace("$table_fields", &PixKey::sql_table_fields());
+    let stmt = tx.prepare(&stmt).await.map_err(map_db_error)?;
+
+    let row = tx
+        .query_opt(&stmt, &[&id])
+        .await
+        .map_err(map_db_error)?
+        .ok_or(MyError::NotFound)?;
+
+    PixKey::from_row_ref(&row).map_err(|e| MyError::Internal(InternalError { msg: e.to_string() }))
+}
+
+/// Only active keys are deactivated; the value becomes available for a new key.
+pub async fn deactivate_pix_key(tx: &Transaction<'_>, id: Uuid, account_id: Uuid) -> Result<PixKey, MyError> {
+    let raw_sql = include_str!("../../../sql/deactivate_pix_key.sql");
+    let sql = raw_sql.replace("$table_fields", &PixKey::sql_table_fields());
+    let stmt = tx.prepare(&sql).await.map_err(map_db_error)?;
+
+    let row = tx
+        .query_opt(&stmt, &[&id, &account_id])
+        .await
+        .map_err(map_db_error)?
+        .ok_or(MyError::NotFound)?;
+
+    PixKey::from_row_ref(&row).map_err(|e| MyError::Internal(InternalError { msg: e.to_string() }))
+}
+
+pub async fn count_active_pix_keys(tx: &Transaction<'_>, account_id: Uuid) -> Result<i64, MyError> {
+    let stmt = include_str!("../../../sql/count_active_pix_keys.sql");
+    let stmt = tx.prepare(stmt).await.map_err(map_db_error)?;
+
+    let row = tx.query_one(&stmt, &[&account_id]).await.map_err(map_db_error)?;
+
+    Ok(row.get(0))
+}
+
+pub async fn get_active_pix_key_by_value(client: &Client, key_value: &str) -> Result<Option<PixKey>, MyError> {
+    let stmt = include_str!("../../../sql/get_active_pix_key_by_value.sql");
+    let stmt = stmt.replace("$table_fields", &PixKey::sql_table_fields());
+    let stmt = client.prepare(&stmt).await.map_err(map_db_error)?;
+
+    client
+        .query_opt(&stmt, &[&key_value])
+        .await
+        .map_err(map_db_error)?
+        .map(|row| PixKey::from_row_ref(&row).map_err(|e| MyError::Internal(InternalError { msg: e.to_string() })))
+        .transpose()
+}
+
+/// Deactivates a key inside a larger transaction, e.g. when a claim hands its
+/// value to another account. Returns whether the key was still active.
+pub async fn release_pix_key(tx: &Transaction<'_>, id: Uuid) -> Result<bool, MyError> {
+    let stmt = include_str!("../../../sql/release_pix_key.sql");
+    let stmt = tx.prepare(stmt).await.map_err(map_db_error)?;
+
+    let released = tx.execute(&stmt, &[&id]).await.map_err(map_db_error)?;
+
+    Ok(released == 1)
+}
+
+pub async fn deactivate_pix_keys_by_customer_id(tx: &Transaction<'_>, customer_id: Uuid) -> Result<u64, MyError> {
+    let stmt = include_str!("../../../sql/deactivate_pix_keys_by_customer_id.sql");
+    let stmt = tx.prepare(stmt).await.map_err(map_db_error)?;
+
+    tx.execute(&stmt, &[&customer_id]).await.map_err(map_db_error)
+}
+
+/// Deactivates the customer's keys holding `key_value`, e.g. after the email
+/// or phone they were bound to changed.
+pub async fn deactivate_pix_keys_by_value(
+    tx: &Transaction<'_>,
+    customer_id: Uuid,
+    key_type: &PixKeyType,
+    key_value: &str,
+) -> Result<u64, MyError> {
+    let stmt = include_str!("../../../sql/deactivate_pix_keys_by_value.sql");
+    let stmt = tx.prepare(stmt).await.map_err(map_db_error)?;
+
+    tx.execute(&stmt, &[&customer_id, key_type, &key_value])
+        .await
+        .map_err(map_db_error)
+}
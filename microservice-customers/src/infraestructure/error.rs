@@ -15,6 +15,16 @@ pub struct ConflictError {
     pub msg: String,
 }
 
+#[derive(Debug, Display, Error, Serialize)]
+pub struct KeyLimitError {
+    pub msg: String,
+}
+
+#[derive(Debug, Display, Error, Serialize)]
+pub struct RateLimitError {
+    pub msg: String,
+}
+
 #[derive(Debug, Display, Error, Serialize)]
 pub struct InternalError {
     pub msg: String,
@@ -27,6 +37,8 @@ pub enum MyError {
     PGMError(PGMError),
     PoolError(PoolError),
     Conflict(ConflictError),
+    KeyLimitExceeded(KeyLimitError),
+    RateLimited(RateLimitError),
     Internal(InternalError),
     ApiError(ApiError),
 }
@@ -74,6 +86,12 @@ impl ResponseError for MyError {
             MyError::Conflict(conflict_error) => HttpResponse::Conflict().json(
                 ErrorResponse::new("conflict".to_string(), conflict_error.msg.clone()),
             ),
+            MyError::KeyLimitExceeded(key_limit_error) => HttpResponse::UnprocessableEntity().json(
+                ErrorResponse::new("key_limit_exceeded".to_string(), key_limit_error.msg.clone()),
+            ),
+            MyError::RateLimited(rate_limit_error) => HttpResponse::TooManyRequests().json(
+                ErrorResponse::new("rate_limited".to_string(), rate_limit_error.msg.clone()),
+            ),
             MyError::Internal(internal_error) => HttpResponse::InternalServerError().json(
                 ErrorResponse::new("internal_error".to_string(), internal_error.msg.clone()),
             ),
@@ -4,13 +4,15 @@ use actix_web::{web, Error, HttpRequest, HttpResponse};
 use chrono::Utc;
 use uuid::Uuid;
 
-use crate::application::dto::customer_dto::{CreateCustomerRequest, CustomerResponse};
+use crate::application::dto::customer_dto::{CreateCustomerRequest, CustomerResponse, UpdateCustomerRequest};
 use crate::application::jwt_service::JwtService;
 use crate::application::{customer_service};
 use crate::infraestructure::error::{ApiError, MyError};
 use crate::{domain::customer::Customer, infraestructure::db::customer_repo};
 use deadpool_postgres::{Client, Pool};
 
+use super::authenticated_customer;
+
 pub async fn create_customer(
     customer: web::Json<CreateCustomerRequest>,
     db_pool: web::Data<Pool>,
@@ -46,6 +48,50 @@ pub async fn get_customer_by_id(
     Ok(HttpResponse::Ok().json(customer))
 }
 
+pub async fn update_customer(
+    req: HttpRequest,
+    customer: web::Json<UpdateCustomerRequest>,
+    db_pool: web::Data<Pool>,
+    path: web::Path<Uuid>,
+    jwt_service: web::Data<Arc<JwtService>>,
+) -> Result<HttpResponse, Error> {
+    let id = path.into_inner();
+    let customer_uuid = authenticated_customer(&req, jwt_service.get_ref())?;
+
+    let mut client: Client = db_pool.get().await.map_err(MyError::PoolError)?;
+
+    match customer_service::update_customer(&mut client, customer_uuid, id, customer.into_inner()).await {
+        Ok(updated_customer) => {
+            log::info!("Customer updated successfully: {:?}", updated_customer.id);
+            Ok(HttpResponse::Ok().json(CustomerResponse::from(updated_customer)))
+        }
+        Err(e) => {
+            log::error!("Error updating customer: {:?}", e);
+            Err(e.into())
+        }
+    }
+}
+
+pub async fn deactivate_customer(
+    req: HttpRequest,
+    db_pool: web::Data<Pool>,
+    path: web::Path<Uuid>,
+    jwt_service: web::Data<Arc<JwtService>>,
+) -> Result<HttpResponse, Error> {
+    let id = path.into_inner();
+    let customer_uuid = authenticated_customer(&req, jwt_service.get_ref())?;
+
+    let mut client: Client = db_pool.get().await.map_err(MyError::PoolError)?;
+
+    match customer_service::deactivate_customer(&mut client, customer_uuid, id).await {
+        Ok(customer) => Ok(HttpResponse::Ok().json(CustomerResponse::from(customer))),
+        Err(e) => {
+            log::error!("Error deactivating customer: {:?}", e);
+            Err(e.into())
+        }
+    }
+}
+
 pub async fn get_customers(
     req: HttpRequest,
     db_pool: web::Data<Pool>,
@@ -0,0 +1,29 @@
+use std::sync::Arc;
+
+use actix_web::{web, Error, HttpRequest, HttpResponse};
+use deadpool_postgres::{Client, Pool};
+
+use crate::{
+    application::{dict_service, dto::dict_dto::DictKeyResponse, jwt_service::JwtService},
+    infraestructure::error::MyError,
+};
+
+use super::authenticated_customer;
+
+pub async fn lookup_key(
+    req: HttpRequest,
+    db_pool: web::Data<Pool>,
+    path: web::Path<String>,
+    jwt_service: web::Data<Arc<JwtService>>,
+) -> Result<HttpResponse, Error> {
+    let key_value = path.into_inner();
+    let customer_uuid = authenticated_customer(&req, jwt_service.get_ref())?;
+
+    let client: Client = db_pool.get().await.map_err(MyError::PoolError)?;
+
+    let (entry, remaining) = dict_service::lookup_key(&client, customer_uuid, &key_value).await?;
+
+    Ok(HttpResponse::Ok()
+        .append_header(("X-RateLimit-Remaining", remaining.to_string()))
+        .json(DictKeyResponse::from(entry)))
+}
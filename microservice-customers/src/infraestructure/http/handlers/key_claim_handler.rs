@@ -0,0 +1,166 @@
+use std::sync::Arc;
+
+use actix_web::{web, Error, HttpRequest, HttpResponse};
+use deadpool_postgres::{Client, Pool};
+use uuid::Uuid;
+
+use crate::{
+    application::{
+        dto::{
+            key_claim_dto::{CreateKeyClaimRequest, KeyClaimResponse, VerifyKeyClaimRequest},
+            pix_key_dto::PixKeyChallengeResponse,
+        },
+        jwt_service::JwtService,
+        key_claim_service,
+    },
+    infraestructure::{error::MyError, notification::notifier::Notifier},
+};
+
+use super::authenticated_customer;
+
+pub async fn open_claim(
+    req: HttpRequest,
+    db_pool: web::Data<Pool>,
+    claim: web::Json<CreateKeyClaimRequest>,
+    jwt_service: web::Data<Arc<JwtService>>,
+    notifier: web::Data<Arc<dyn Notifier>>,
+) -> Result<HttpResponse, Error> {
+    let customer_uuid = authenticated_customer(&req, jwt_service.get_ref())?;
+
+    let mut client: Client = db_pool.get().await.map_err(MyError::PoolError)?;
+
+    match key_claim_service::open_claim(&mut client, notifier.get_ref().as_ref(), customer_uuid, claim.into_inner()).await {
+        Ok(claim) => {
+            log::info!("Key claim opened: {:?}", claim.id);
+            Ok(HttpResponse::Created()
+                .append_header(("Location", format!("/key-claims/{}", claim.id)))
+                .json(KeyClaimResponse::from(claim)))
+        }
+        Err(e) => {
+            log::error!("Error opening key claim: {:?}", e);
+            Err(e.into())
+        }
+    }
+}
+
+pub async fn list_claims(
+    req: HttpRequest,
+    db_pool: web::Data<Pool>,
+    jwt_service: web::Data<Arc<JwtService>>,
+) -> Result<HttpResponse, Error> {
+    let customer_uuid = authenticated_customer(&req, jwt_service.get_ref())?;
+
+    let client: Client = db_pool.get().await.map_err(MyError::PoolError)?;
+
+    let claims = key_claim_service::list_claims(&client, customer_uuid).await?;
+
+    Ok(HttpResponse::Ok().json(
+        claims
+            .into_iter()
+            .map(KeyClaimResponse::from)
+            .collect::<Vec<KeyClaimResponse>>(),
+    ))
+}
+
+pub async fn get_claim(
+    req: HttpRequest,
+    db_pool: web::Data<Pool>,
+    path: web::Path<Uuid>,
+    jwt_service: web::Data<Arc<JwtService>>,
+) -> Result<HttpResponse, Error> {
+    let id = path.into_inner();
+    let customer_uuid = authenticated_customer(&req, jwt_service.get_ref())?;
+
+    let client: Client = db_pool.get().await.map_err(MyError::PoolError)?;
+
+    let claim = key_claim_service::get_claim(&client, customer_uuid, id).await?;
+
+    Ok(HttpResponse::Ok().json(KeyClaimResponse::from(claim)))
+}
+
+pub async fn verify_claim(
+    req: HttpRequest,
+    db_pool: web::Data<Pool>,
+    path: web::Path<Uuid>,
+    verification: web::Json<VerifyKeyClaimRequest>,
+    jwt_service: web::Data<Arc<JwtService>>,
+) -> Result<HttpResponse, Error> {
+    let id = path.into_inner();
+    let customer_uuid = authenticated_customer(&req, jwt_service.get_ref())?;
+
+    let mut client: Client = db_pool.get().await.map_err(MyError::PoolError)?;
+
+    let claim = key_claim_service::verify_claim(&mut client, customer_uuid, id, &verification.code).await?;
+
+    Ok(HttpResponse::Ok().json(KeyClaimResponse::from(claim)))
+}
+
+pub async fn resend_claim_code(
+    req: HttpRequest,
+    db_pool: web::Data<Pool>,
+    path: web::Path<Uuid>,
+    jwt_service: web::Data<Arc<JwtService>>,
+    notifier: web::Data<Arc<dyn Notifier>>,
+) -> Result<HttpResponse, Error> {
+    let id = path.into_inner();
+    let customer_uuid = authenticated_customer(&req, jwt_service.get_ref())?;
+
+    let mut client: Client = db_pool.get().await.map_err(MyError::PoolError)?;
+
+    let challenge =
+        key_claim_service::resend_claim_code(&mut client, notifier.get_ref().as_ref(), customer_uuid, id).await?;
+
+    Ok(HttpResponse::Accepted().json(PixKeyChallengeResponse::from(challenge)))
+}
+
+pub async fn confirm_claim(
+    req: HttpRequest,
+    db_pool: web::Data<Pool>,
+    path: web::Path<Uuid>,
+    jwt_service: web::Data<Arc<JwtService>>,
+) -> Result<HttpResponse, Error> {
+    let id = path.into_inner();
+    let customer_uuid = authenticated_customer(&req, jwt_service.get_ref())?;
+
+    let mut client: Client = db_pool.get().await.map_err(MyError::PoolError)?;
+
+    let claim = key_claim_service::confirm_claim(&mut client, customer_uuid, id).await?;
+
+    Ok(HttpResponse::Ok().json(KeyClaimResponse::from(claim)))
+}
+
+pub async fn cancel_claim(
+    req: HttpRequest,
+    db_pool: web::Data<Pool>,
+    path: web::Path<Uuid>,
+    jwt_service: web::Data<Arc<JwtService>>,
+) -> Result<HttpResponse, Error> {
+    let id = path.into_inner();
+    let customer_uuid = authenticated_customer(&req, jwt_service.get_ref())?;
+
+    let mut client: Client = db_pool.get().await.map_err(MyError::PoolError)?;
+
+    let claim = key_claim_service::cancel_claim(&mut client, customer_uuid, id).await?;
+
+    Ok(HttpResponse::Ok().json(KeyClaimResponse::from(claim)))
+}
+
+pub async fn complete_claim(
+    req: HttpRequest,
+    db_pool: web::Data<Pool>,
+    path: web::Path<Uuid>,
+    jwt_service: web::Data<Arc<JwtService>>,
+) -> Result<HttpResponse, Error> {
+    let id = path.into_inner();
+    let customer_uuid = authenticated_customer(&req, jwt_service.get_ref())?;
+
+    let mut client: Client = db_pool.get().await.map_err(MyError::PoolError)?;
+
+    match key_claim_service::complete_claim(&mut client, customer_uuid, id).await {
+        Ok(claim) => Ok(HttpResponse::Ok().json(KeyClaimResponse::from(claim))),
+        Err(e) => {
+            log::error!("Error completing key claim {}: {:?}", id, e);
+            Err(e.into())
+        }
+    }
+}
@@ -1,3 +1,22 @@
+use actix_web::HttpRequest;
+use uuid::Uuid;
+
+use crate::{
+    application::jwt_service::{self, JwtService},
+    infraestructure::error::{ApiError, MyError},
+};
+
 pub mod account_handler;
 pub mod pix_handler;
-pub mod customer_handler;
\ No newline at end of file
+pub mod customer_handler;
+pub mod dict_handler;
+pub mod key_claim_handler;
+
+/// Customer id carried by the request's bearer token.
+pub fn authenticated_customer(req: &HttpRequest, jwt_service: &JwtService) -> Result<Uuid, MyError> {
+    jwt_service::extract_customer_uuid_from_request(req, jwt_service).map_err(|_| {
+        MyError::ApiError(ApiError {
+            msg: "Invalid or missing JWT token".to_string(),
+        })
+    })
+}
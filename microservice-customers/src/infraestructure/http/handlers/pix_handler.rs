@@ -2,12 +2,69 @@ use std::sync::Arc;
 
 use actix_web::{Error, web, HttpRequest, HttpResponse};
 use deadpool_postgres::{Client, Pool};
+use uuid::Uuid;
 
-use crate::{application::{dto::pix_key_dto::{CreatePixKeyRequest, PixKeyResponse}, jwt_service::JwtService, pix_service}, infraestructure::error::{ApiError, MyError}};
+use crate::{application::{dto::pix_key_dto::{ConfirmPixKeyChallengeRequest, CreatePixKeyRequest, PixKeyChallengeResponse, PixKeyResponse}, jwt_service::JwtService, pix_service::{self, PixKeyRegistration}}, infraestructure::{error::{ApiError, MyError}, notification::notifier::Notifier}};
 
+use super::authenticated_customer;
 
-pub async fn list_pix_keys() -> HttpResponse {
-    HttpResponse::Ok().json("List of PIX keys")
+
+pub async fn list_pix_keys(
+    req: HttpRequest,
+    db_pool: web::Data<Pool>,
+    jwt_service: web::Data<Arc<JwtService>>,
+) -> Result<HttpResponse, Error> {
+    let customer_uuid = authenticated_customer(&req, jwt_service.get_ref())?;
+
+    let client: Client = db_pool.get().await.map_err(MyError::PoolError)?;
+
+    let pix_keys = pix_service::list_pix_keys(&client, customer_uuid).await?;
+
+    Ok(HttpResponse::Ok().json(
+        pix_keys
+            .into_iter()
+            .map(PixKeyResponse::from)
+            .collect::<Vec<PixKeyResponse>>(),
+    ))
+}
+
+pub async fn get_pix_key(
+    req: HttpRequest,
+    db_pool: web::Data<Pool>,
+    path: web::Path<Uuid>,
+    jwt_service: web::Data<Arc<JwtService>>,
+) -> Result<HttpResponse, Error> {
+    let id = path.into_inner();
+    let customer_uuid = authenticated_customer(&req, jwt_service.get_ref())?;
+
+    let client: Client = db_pool.get().await.map_err(MyError::PoolError)?;
+
+    let pix_key = pix_service::get_pix_key(&client, customer_uuid, id).await?;
+
+    Ok(HttpResponse::Ok().json(PixKeyResponse::from(pix_key)))
+}
+
+pub async fn delete_pix_key(
+    req: HttpRequest,
+    db_pool: web::Data<Pool>,
+    path: web::Path<Uuid>,
+    jwt_service: web::Data<Arc<JwtService>>,
+) -> Result<HttpResponse, Error> {
+    let id = path.into_inner();
+    let customer_uuid = authenticated_customer(&req, jwt_service.get_ref())?;
+
+    let mut client: Client = db_pool.get().await.map_err(MyError::PoolError)?;
+
+    match pix_service::deactivate_pix_key(&mut client, customer_uuid, id).await {
+        Ok(pix_key) => {
+            log::info!("PIX key deactivated: {:?}", pix_key.id);
+            Ok(HttpResponse::Ok().json(PixKeyResponse::from(pix_key)))
+        }
+        Err(e) => {
+            log::error!("Error deactivating PIX key {}: {:?}", id, e);
+            Err(e.into())
+        }
+    }
 }
 
 pub async fn create_pix_key(
@@ -15,6 +72,7 @@ pub async fn create_pix_key(
     db_pool: web::Data<Pool>,
     pix_key: web::Json<CreatePixKeyRequest>,
     jwt_service: web::Data<Arc<JwtService>>,
+    notifier: web::Data<Arc<dyn Notifier>>,
 ) -> Result<HttpResponse, Error> {
 
     println!("\n\n\nCreating PIX key...\n\n\n");
@@ -39,16 +97,46 @@ pub async fn create_pix_key(
 
     println!("\n\n\nExtracted UUID: {}\n\n\n\n", customer_uuid);
 
-    match pix_service::create_pix_key(&mut client, &mut pix_key_info, customer_uuid).await {
-        Ok(new_pix_key) => {
+    match pix_service::create_pix_key(&mut client, notifier.get_ref().as_ref(), &mut pix_key_info, customer_uuid).await {
+        Ok(PixKeyRegistration::Created(new_pix_key)) => {
             log::info!("PIX key created successfully: {:?}", new_pix_key.id);
             Ok(HttpResponse::Created()
                 .append_header(("Location", format!("/pix-keys/{}", new_pix_key.id)))
                 .json(PixKeyResponse::from(new_pix_key)))
         }
+        Ok(PixKeyRegistration::ChallengeSent(challenge)) => {
+            log::info!("PIX key verification code sent: {:?}", challenge.id);
+            Ok(HttpResponse::Accepted().json(PixKeyChallengeResponse::from(challenge)))
+        }
         Err(e) => {
             log::error!("Error creating PIX key: {:?}", e);
             Err(e.into())
         }
     }
+}
+
+pub async fn confirm_pix_key_challenge(
+    req: HttpRequest,
+    db_pool: web::Data<Pool>,
+    path: web::Path<Uuid>,
+    confirmation: web::Json<ConfirmPixKeyChallengeRequest>,
+    jwt_service: web::Data<Arc<JwtService>>,
+) -> Result<HttpResponse, Error> {
+    let challenge_id = path.into_inner();
+    let customer_uuid = authenticated_customer(&req, jwt_service.get_ref())?;
+
+    let mut client: Client = db_pool.get().await.map_err(MyError::PoolError)?;
+
+    match pix_service::confirm_pix_key_challenge(&mut client, customer_uuid, challenge_id, &confirmation.code).await {
+        Ok(new_pix_key) => {
+            log::info!("PIX key created after verification: {:?}", new_pix_key.id);
+            Ok(HttpResponse::Created()
+                .append_header(("Location", format!("/pix-keys/{}", new_pix_key.id)))
+                .json(PixKeyResponse::from(new_pix_key)))
+        }
+        Err(e) => {
+            log::error!("Error confirming PIX key challenge {}: {:?}", challenge_id, e);
+            Err(e.into())
+        }
+    }
 }
\ No newline at end of file
@@ -2,7 +2,7 @@ use actix_web::web;
 
 use crate::infraestructure::auth::auth::JwtMiddleware;
 
-use super::handlers::{account_handler, customer_handler, pix_handler};
+use super::handlers::{account_handler, customer_handler, dict_handler, key_claim_handler, pix_handler};
 
 pub fn config(cfg: &mut web::ServiceConfig) {
     cfg.service(
@@ -10,7 +10,9 @@ pub fn config(cfg: &mut web::ServiceConfig) {
             .wrap(JwtMiddleware)
             .route("", web::get().to(customer_handler::get_customers))
             .route("", web::post().to(customer_handler::create_customer))
-            .route("/{id}", web::get().to(customer_handler::get_customer_by_id)),
+            .route("/{id}", web::get().to(customer_handler::get_customer_by_id))
+            .route("/{id}", web::patch().to(customer_handler::update_customer))
+            .route("/{id}/deactivate", web::post().to(customer_handler::deactivate_customer)),
     )
     .service(
         web::scope("/accounts")
@@ -24,6 +26,29 @@ pub fn config(cfg: &mut web::ServiceConfig) {
         web::scope("/pix-keys")
             .wrap(JwtMiddleware)
             .route("", web::post().to(pix_handler::create_pix_key))
-            .route("", web::get().to(pix_handler::list_pix_keys)),
+            .route("", web::get().to(pix_handler::list_pix_keys))
+            .route("/{id}", web::get().to(pix_handler::get_pix_key))
+            .route("/{id}", web::delete().to(pix_handler::delete_pix_key))
+            .route(
+                "/challenges/{id}/confirm",
+                web::post().to(pix_handler::confirm_pix_key_challenge),
+            ),
+    )
+    .service(
+        web::scope("/key-claims")
+            .wrap(JwtMiddleware)
+            .route("", web::post().to(key_claim_handler::open_claim))
+            .route("", web::get().to(key_claim_handler::list_claims))
+            .route("/{id}", web::get().to(key_claim_handler::get_claim))
+            .route("/{id}/verify", web::post().to(key_claim_handler::verify_claim))
+            .route("/{id}/code", web::post().to(key_claim_handler::resend_claim_code))
+            .route("/{id}/confirm", web::post().to(key_claim_handler::confirm_claim))
+            .route("/{id}/cancel", web::post().to(key_claim_handler::cancel_claim))
+            .route("/{id}/complete", web::post().to(key_claim_handler::complete_claim)),
+    )
+    .service(
+        web::scope("/dict")
+            .wrap(JwtMiddleware)
+            .route("/keys/{value}", web::get().to(dict_handler::lookup_key)),
     );
 }
@@ -0,0 +1,29 @@
+use std::{sync::Arc, time::Duration};
+
+use deadpool_postgres::Pool;
+
+use crate::{
+    application::key_claim_service,
+    infraestructure::{error::MyError, notification::notifier::Notifier},
+};
+
+pub const CHECK_INTERVAL_SECS: u64 = 60;
+
+/// Resolves key claims whose 7-day resolution period has lapsed.
+pub async fn run_key_claim_timer(pool: Pool, notifier: Arc<dyn Notifier>) {
+    loop {
+        match tick(&pool, notifier.as_ref()).await {
+            Ok(0) => {}
+            Ok(resolved) => log::info!("Resolved {} expired key claims", resolved),
+            Err(e) => log::error!("Key claim timer failed: {:?}", e),
+        }
+
+        tokio::time::sleep(Duration::from_secs(CHECK_INTERVAL_SECS)).await;
+    }
+}
+
+async fn tick(pool: &Pool, notifier: &dyn Notifier) -> Result<usize, MyError> {
+    let mut client = pool.get().await.map_err(MyError::PoolError)?;
+
+    key_claim_service::resolve_expired_claims(&mut client, notifier).await
+}
@@ -0,0 +1,89 @@
+use std::time::Duration;
+
+use deadpool_postgres::Pool;
+use rdkafka::{
+    config::ClientConfig,
+    producer::{FutureProducer, FutureRecord},
+    util::Timeout,
+};
+
+use crate::infraestructure::{
+    db::account_repo,
+    error::{InternalError, MyError},
+    messaging::balance_consumer::kafka_brokers,
+};
+
+pub const ACCOUNTS_TOPIC: &str = "accounts";
+
+/// Outbox rows sent per transaction.
+pub const OUTBOX_BATCH_SIZE: i64 = 100;
+
+/// How often the publisher drains the outbox.
+pub const OUTBOX_POLL_INTERVAL_MILLIS: u64 = 1000;
+
+pub const PUBLISH_TIMEOUT_SECS: u64 = 5;
+
+/// Sends every queued account block and returns how many were sent. A batch is
+/// only deleted after the broker acknowledged all of it; a failure resends the
+/// batch, which the ledger records only once per account.
+pub async fn publish_pending(pool: &Pool, producer: &FutureProducer) -> Result<usize, MyError> {
+    let mut client = pool.get().await.map_err(MyError::PoolError)?;
+    let messaging_error = |msg: String| MyError::Internal(InternalError { msg });
+    let mut published = 0;
+
+    loop {
+        let transaction = client.build_transaction().start().await?;
+        let batch =
+            account_repo::lock_account_outbox_batch(&transaction, OUTBOX_BATCH_SIZE).await?;
+
+        if batch.is_empty() {
+            break;
+        }
+
+        for (_, event) in &batch {
+            let key = event.account_id.to_string();
+            let payload =
+                serde_json::to_string(event).map_err(|e| messaging_error(e.to_string()))?;
+
+            producer
+                .send(
+                    FutureRecord::to(ACCOUNTS_TOPIC).key(&key).payload(&payload),
+                    Timeout::After(Duration::from_secs(PUBLISH_TIMEOUT_SECS)),
+                )
+                .await
+                .map_err(|(e, _)| messaging_error(e.to_string()))?;
+        }
+
+        let ids: Vec<i64> = batch.iter().map(|(id, _)| *id).collect();
+        account_repo::delete_account_outbox(&transaction, &ids).await?;
+        transaction.commit().await?;
+
+        published += batch.len();
+    }
+
+    Ok(published)
+}
+
+pub async fn run_account_publisher(pool: Pool) {
+    let producer: FutureProducer = match ClientConfig::new()
+        .set("bootstrap.servers", kafka_brokers())
+        .set("message.timeout.ms", "5000")
+        .create()
+    {
+        Ok(producer) => producer,
+        Err(e) => {
+            log::error!("Failed to create account event producer: {}", e);
+            return;
+        }
+    };
+
+    let mut interval = tokio::time::interval(Duration::from_millis(OUTBOX_POLL_INTERVAL_MILLIS));
+
+    loop {
+        interval.tick().await;
+
+        if let Err(e) = publish_pending(&pool, &producer).await {
+            log::error!("Account publisher failed: {:?}", e);
+        }
+    }
+}
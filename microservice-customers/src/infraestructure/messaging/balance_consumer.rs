@@ -0,0 +1,94 @@
+use std::time::Duration;
+
+use deadpool_postgres::Pool;
+use rdkafka::{
+    config::ClientConfig,
+    consumer::{CommitMode, Consumer, StreamConsumer},
+    Message,
+};
+
+use crate::{
+    application::{account_service, dto::account_dto::BalanceChangedEvent},
+    infraestructure::error::MyError,
+};
+
+pub const BALANCES_TOPIC: &str = "balances";
+pub const CONSUMER_GROUP: &str = "microservice-customers";
+pub const RETRY_DELAY_SECS: u64 = 5;
+
+pub fn kafka_brokers() -> String {
+    std::env::var("KAFKA_BROKERS").unwrap_or_else(|_| "localhost:29092".to_string())
+}
+
+/// Keeps accounts.available_balance and ledger_balance in line with the ledger.
+/// The offset is committed once the event is applied (or found stale), so a
+/// crash only replays events the version check already ignores.
+pub async fn run_balance_consumer(pool: Pool) {
+    let consumer: StreamConsumer = match ClientConfig::new()
+        .set("bootstrap.servers", kafka_brokers())
+        .set("group.id", CONSUMER_GROUP)
+        .set("enable.auto.commit", "false")
+        .set("auto.offset.reset", "earliest")
+        .create()
+    {
+        Ok(consumer) => consumer,
+        Err(e) => {
+            log::error!("Failed to create balance event consumer: {}", e);
+            return;
+        }
+    };
+
+    if let Err(e) = consumer.subscribe(&[BALANCES_TOPIC]) {
+        log::error!("Failed to subscribe to {}: {}", BALANCES_TOPIC, e);
+        return;
+    }
+
+    loop {
+        let message = match consumer.recv().await {
+            Ok(message) => message,
+            Err(e) => {
+                log::error!("Balance event consumer error: {}", e);
+                tokio::time::sleep(Duration::from_secs(RETRY_DELAY_SECS)).await;
+                continue;
+            }
+        };
+
+        match message
+            .payload()
+            .map(serde_json::from_slice::<BalanceChangedEvent>)
+        {
+            Some(Ok(event)) => {
+                while let Err(e) = apply(&pool, &event).await {
+                    log::error!(
+                        "Failed to apply balance event for account {}: {:?}",
+                        event.account_id,
+                        e
+                    );
+                    tokio::time::sleep(Duration::from_secs(RETRY_DELAY_SECS)).await;
+                }
+            }
+            _ => log::error!(
+                "Discarding invalid balance event at offset {}",
+                message.offset()
+            ),
+        }
+
+        if let Err(e) = consumer.commit_message(&message, CommitMode::Async) {
+            log::error!("Failed to commit offset {}: {}", message.offset(), e);
+        }
+    }
+}
+
+async fn apply(pool: &Pool, event: &BalanceChangedEvent) -> Result<(), MyError> {
+    let client = pool.get().await.map_err(MyError::PoolError)?;
+
+    if !account_service::apply_balance_change(&client, event).await? {
+        log::debug!(
+            "Skipped balance event v{} for account {}: stale or unknown account",
+            event.version,
+            event.account_id
+        );
+    }
+
+    Ok(())
+}
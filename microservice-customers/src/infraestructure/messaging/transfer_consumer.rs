@@ -0,0 +1,90 @@
+use std::time::Duration;
+
+use deadpool_postgres::Pool;
+use rdkafka::{
+    config::ClientConfig,
+    consumer::{CommitMode, Consumer, StreamConsumer},
+    Message,
+};
+
+use crate::{
+    application::{dict_service, dto::transfer_dto::TransferEvent},
+    infraestructure::error::MyError,
+};
+
+use super::balance_consumer::{kafka_brokers, RETRY_DELAY_SECS};
+
+pub const TRANSFERS_TOPIC: &str = "transfers";
+pub const CONSUMER_GROUP: &str = "microservice-customers-dict";
+
+/// Refills DICT lookup buckets from posted transfers. Refills are recorded
+/// per transfer, so replayed events are ignored.
+pub async fn run_transfer_consumer(pool: Pool) {
+    let consumer: StreamConsumer = match ClientConfig::new()
+        .set("bootstrap.servers", kafka_brokers())
+        .set("group.id", CONSUMER_GROUP)
+        .set("enable.auto.commit", "false")
+        .set("auto.offset.reset", "earliest")
+        .create()
+    {
+        Ok(consumer) => consumer,
+        Err(e) => {
+            log::error!("Failed to create transfer event consumer: {}", e);
+            return;
+        }
+    };
+
+    if let Err(e) = consumer.subscribe(&[TRANSFERS_TOPIC]) {
+        log::error!("Failed to subscribe to {}: {}", TRANSFERS_TOPIC, e);
+        return;
+    }
+
+    loop {
+        let message = match consumer.recv().await {
+            Ok(message) => message,
+            Err(e) => {
+                log::error!("Transfer event consumer error: {}", e);
+                tokio::time::sleep(Duration::from_secs(RETRY_DELAY_SECS)).await;
+                continue;
+            }
+        };
+
+        match message
+            .payload()
+            .map(serde_json::from_slice::<TransferEvent>)
+        {
+            Some(Ok(event)) => {
+                while let Err(e) = apply(&pool, &event).await {
+                    log::error!(
+                        "Failed to refill lookups for transfer {}: {:?}",
+                        event.transfer_id,
+                        e
+                    );
+                    tokio::time::sleep(Duration::from_secs(RETRY_DELAY_SECS)).await;
+                }
+            }
+            _ => log::error!(
+                "Discarding invalid transfer event at offset {}",
+                message.offset()
+            ),
+        }
+
+        if let Err(e) = consumer.commit_message(&message, CommitMode::Async) {
+            log::error!("Failed to commit offset {}: {}", message.offset(), e);
+        }
+    }
+}
+
+async fn apply(pool: &Pool, event: &TransferEvent) -> Result<(), MyError> {
+    let client = pool.get().await.map_err(MyError::PoolError)?;
+
+    if dict_service::refill_from_transfer(&client, event).await? {
+        log::debug!(
+            "Refilled lookups for payer account {} from transfer {}",
+            event.debit_account_id,
+            event.transfer_id
+        );
+    }
+
+    Ok(())
+}
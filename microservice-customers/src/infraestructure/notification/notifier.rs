@@ -0,0 +1,61 @@
+use std::{
+    fs::OpenOptions,
+    io::Write,
+    sync::{Arc, Mutex},
+};
+
+use chrono::Utc;
+
+use crate::domain::pix_key::PixKeyType;
+
+/// Delivers messages to an email address or phone number. The channel is the
+/// type of key being verified.
+pub trait Notifier: Send + Sync {
+    fn send(&self, channel: &PixKeyType, destination: &str, message: &str) -> Result<(), std::io::Error>;
+}
+
+/// Writes messages to the application log.
+pub struct LogNotifier;
+
+impl Notifier for LogNotifier {
+    fn send(&self, channel: &PixKeyType, destination: &str, message: &str) -> Result<(), std::io::Error> {
+        log::info!("[{}] to {}: {}", channel, destination, message);
+        Ok(())
+    }
+}
+
+/// Appends messages to a file, one per line, for local development.
+pub struct FileNotifier {
+    path: String,
+    lock: Mutex<()>,
+}
+
+impl FileNotifier {
+    pub fn new(path: String) -> Self {
+        Self {
+            path,
+            lock: Mutex::new(()),
+        }
+    }
+}
+
+impl Notifier for FileNotifier {
+    fn send(&self, channel: &PixKeyType, destination: &str, message: &str) -> Result<(), std::io::Error> {
+        let _guard = self.lock.lock().unwrap_or_else(|e| e.into_inner());
+
+        let mut file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        writeln!(file, "{} [{}] to {}: {}", Utc::now().to_rfc3339(), channel, destination, message)
+    }
+}
+
+/// Picks the sink from NOTIFIER (`log` or `file`); the file sink writes to
+/// NOTIFIER_FILE.
+pub fn notifier_from_env() -> Arc<dyn Notifier> {
+    match std::env::var("NOTIFIER").as_deref() {
+        Ok("file") => {
+            let path = std::env::var("NOTIFIER_FILE").unwrap_or_else(|_| "notifications.log".to_string());
+            Arc::new(FileNotifier::new(path))
+        }
+        _ => Arc::new(LogNotifier),
+    }
+}
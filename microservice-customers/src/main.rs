@@ -8,6 +8,9 @@ use confik::{Configuration, EnvSource};
 use dotenvy::dotenv;
 use env_logger::Env;
 use infraestructure::http::routes::config as routes_config;
+use infraestructure::jobs::key_claim_timer;
+use infraestructure::messaging::{account_publisher, balance_consumer, transfer_consumer};
+use infraestructure::notification::notifier::notifier_from_env;
 use tokio_postgres::NoTls;
 
 use crate::application::jwt_service::JwtService;
@@ -19,7 +22,9 @@ pub mod shared;
 mod application {
     pub mod account_service;
     pub mod customer_service;
+    pub mod dict_service;
     pub mod dto;
+    pub mod key_claim_service;
     pub mod pix_service;
     pub mod jwt_service;
 }
@@ -27,8 +32,11 @@ mod application {
 mod domain {
     pub mod account;
     pub mod customer;
+    pub mod dict;
     pub mod enums;
+    pub mod key_claim;
     pub mod pix_key;
+    pub mod pix_key_challenge;
 }
 
 mod infraestructure {
@@ -39,12 +47,26 @@ mod infraestructure {
     pub mod db {
         pub mod account_repo;
         pub mod customer_repo;
+        pub mod dict_repo;
+        pub mod key_claim_repo;
+        pub mod pix_key_challenge_repo;
         pub mod pix_key_repo;
     }
 
     pub mod auth{
         pub mod auth;
     }
+    pub mod jobs {
+        pub mod key_claim_timer;
+    }
+    pub mod messaging {
+        pub mod account_publisher;
+        pub mod balance_consumer;
+        pub mod transfer_consumer;
+    }
+    pub mod notification {
+        pub mod notifier;
+    }
     pub mod error;
 }
 
@@ -73,11 +95,18 @@ async fn main() -> std::io::Result<()> {
     let pool = config.pg.create_pool(None, NoTls).unwrap();
 
     let jwt_service = Arc::new(JwtService::new());
+    let notifier = notifier_from_env();
+
+    actix_web::rt::spawn(account_publisher::run_account_publisher(pool.clone()));
+    actix_web::rt::spawn(balance_consumer::run_balance_consumer(pool.clone()));
+    actix_web::rt::spawn(key_claim_timer::run_key_claim_timer(pool.clone(), Arc::clone(&notifier)));
+    actix_web::rt::spawn(transfer_consumer::run_transfer_consumer(pool.clone()));
 
     let server = HttpServer::new(move || {
         App::new()
             .wrap(Logger::default())
             .app_data(web::Data::new(Arc::clone(&jwt_service)))
+            .app_data(web::Data::new(Arc::clone(&notifier)))
             .app_data(web::Data::new(pool.clone()))
             .configure(routes_config)
     })
@@ -2,6 +2,7 @@
 pub enum CnpjValidationError {
     InvalidLength(usize),
     NonDigitCharacters,
+    InvalidCharacters,
     AllDigitsEqual,
     InvalidCheckDigits,
 }
@@ -10,9 +11,11 @@ impl std::fmt::Display for CnpjValidationError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             CnpjValidationError::InvalidLength(len) =>
-                write!(f, "CNPJ deve ter 14 dígitos (recebido {})", len),
+                write!(f, "CNPJ deve ter 14 caracteres (recebido {})", len),
             CnpjValidationError::NonDigitCharacters =>
-                write!(f, "CNPJ deve conter apenas dígitos"),
+                write!(f, "Dígitos verificadores do CNPJ devem ser numéricos"),
+            CnpjValidationError::InvalidCharacters =>
+                write!(f, "CNPJ deve conter apenas letras e dígitos"),
             CnpjValidationError::AllDigitsEqual =>
                 write!(f, "CNPJ não pode ter todos dígitos iguais"),
             CnpjValidationError::InvalidCheckDigits =>
@@ -21,46 +24,103 @@ impl std::fmt::Display for CnpjValidationError {
     }
 }
 
+pub const CNPJ_LENGTH: usize = 14;
+/// Positions that may hold letters in the alphanumeric format; the last two
+/// are always numeric check digits.
+const BASE_LENGTH: usize = 12;
+
 pub fn validate_cnpj(cnpj: &str) -> Result<(), CnpjValidationError> {
-    let digits: Vec<char> = cnpj.chars().filter(|c| c.is_ascii_digit()).collect();
+    parse_cnpj(cnpj).map(|_| ())
+}
 
-    // Verifica se há caracteres não numéricos
-    if digits.len() != cnpj.chars().count() {
-        return Err(CnpjValidationError::NonDigitCharacters);
+/// Parses a numeric ("04.252.011/0001-10") or alphanumeric ("12.ABC.345/01DE-35")
+/// CNPJ into the 14-character form we store: no punctuation, letters
+/// uppercased.
+pub fn parse_cnpj(cnpj: &str) -> Result<String, CnpjValidationError> {
+    // Remove a formatação usual (pontos, barra, hífen e espaços)
+    let chars: Vec<char> = cnpj
+        .chars()
+        .filter(|c| !matches!(c, '.' | '/' | '-' | ' '))
+        .map(|c| c.to_ascii_uppercase())
+        .collect();
+
+    if chars.iter().any(|c| !c.is_ascii_alphanumeric()) {
+        return Err(CnpjValidationError::InvalidCharacters);
     }
 
     // Verifica o tamanho
-    if digits.len() != 14 {
-        return Err(CnpjValidationError::InvalidLength(digits.len()));
+    if chars.len() != CNPJ_LENGTH {
+        return Err(CnpjValidationError::InvalidLength(chars.len()));
     }
 
-    // Verifica se todos os dígitos são iguais
-    if digits.iter().all(|&c| c == digits[0]) {
-        return Err(CnpjValidationError::AllDigitsEqual);
+    // Os dígitos verificadores continuam numéricos
+    if !chars[BASE_LENGTH..].iter().all(|c| c.is_ascii_digit()) {
+        return Err(CnpjValidationError::NonDigitCharacters);
     }
 
-    // Converte para vetor de inteiros
-    let nums: Vec<u32> = digits.iter().map(|c| c.to_digit(10).unwrap()).collect();
+    // Verifica se todos os caracteres são iguais
+    if chars.iter().all(|&c| c == chars[0]) {
+        return Err(CnpjValidationError::AllDigitsEqual);
+    }
 
-    // Calcula verificadores
-    let first_verifier = calculate_verifier(&nums, 12);
-    let second_verifier = calculate_verifier(&nums, 13);
+    let base: String = chars[..BASE_LENGTH].iter().collect();
+    let check_digits: String = chars[BASE_LENGTH..].iter().collect();
 
-    if nums[12] != first_verifier || nums[13] != second_verifier {
+    if calculate_check_digits(&base)? != check_digits {
         return Err(CnpjValidationError::InvalidCheckDigits);
     }
 
-    Ok(())
+    Ok(chars.into_iter().collect())
 }
 
-fn calculate_verifier(digits: &[u32], length: usize) -> u32 {
-    let weights = if length == 12 {
-        vec![5, 4, 3, 2, 9, 8, 7, 6, 5, 4, 3, 2]
+/// Formats a parsed CNPJ as "XX.XXX.XXX/XXXX-DD". Anything that is not a
+/// 14-character CNPJ is returned unchanged.
+pub fn format_cnpj(cnpj: &str) -> String {
+    if cnpj.len() != CNPJ_LENGTH || !cnpj.is_ascii() {
+        return cnpj.to_string();
+    }
+
+    format!(
+        "{}.{}.{}/{}-{}",
+        &cnpj[0..2],
+        &cnpj[2..5],
+        &cnpj[5..8],
+        &cnpj[8..12],
+        &cnpj[12..14]
+    )
+}
+
+/// Check digits for the 12 leading positions. Each character is worth its
+/// ASCII code minus 48, so digits keep their value and 'A' is 17; the
+/// modulo 11 weights are the same for both formats.
+pub fn calculate_check_digits(base: &str) -> Result<String, CnpjValidationError> {
+    let mut values: Vec<u32> = base
+        .chars()
+        .map(|c| match c.to_ascii_uppercase() {
+            c @ ('0'..='9' | 'A'..='Z') => Ok(c as u32 - 48),
+            _ => Err(CnpjValidationError::InvalidCharacters),
+        })
+        .collect::<Result<_, _>>()?;
+
+    if values.len() != BASE_LENGTH {
+        return Err(CnpjValidationError::InvalidLength(values.len()));
+    }
+
+    let first_verifier = calculate_verifier(&values);
+    values.push(first_verifier);
+    let second_verifier = calculate_verifier(&values);
+
+    Ok(format!("{}{}", first_verifier, second_verifier))
+}
+
+fn calculate_verifier(values: &[u32]) -> u32 {
+    let weights: &[u32] = if values.len() == BASE_LENGTH {
+        &[5, 4, 3, 2, 9, 8, 7, 6, 5, 4, 3, 2]
     } else {
-        vec![6, 5, 4, 3, 2, 9, 8, 7, 6, 5, 4, 3, 2]
+        &[6, 5, 4, 3, 2, 9, 8, 7, 6, 5, 4, 3, 2]
     };
 
-    let sum: u32 = digits[..length].iter().zip(weights.iter()).map(|(d, w)| d * w).sum();
+    let sum: u32 = values.iter().zip(weights.iter()).map(|(v, w)| v * w).sum();
     let resto = sum % 11;
 
     if resto < 2 { 0 } else { 11 - resto }
@@ -70,6 +130,20 @@ fn calculate_verifier(digits: &[u32], length: usize) -> u32 {
 mod tests {
     use super::*;
 
+    /// Valid CNPJs in both formats, written as users type them.
+    const VALID_CORPUS: [(&str, &str); 10] = [
+        ("04.252.011/0001-10", "04252011000110"),
+        ("04252011000110", "04252011000110"),
+        ("40.688.134/0001-61", "40688134000161"),
+        ("40688134000161", "40688134000161"),
+        ("12.ABC.345/01DE-35", "12ABC34501DE35"),
+        ("12abc34501de35", "12ABC34501DE35"),
+        ("A1.B2C.3D4/E5F6-68", "A1B2C3D4E5F668"),
+        ("ZZ.ZZZ.ZZZ/0001-91", "ZZZZZZZZ000191"),
+        ("00.ABC.DEF/0001-57", "00ABCDEF000157"),
+        ("Q1 W2E 3R4 0001 35", "Q1W2E3R4000135"),
+    ];
+
     #[test]
     fn test_valid_cnpjs() {
         assert_eq!(validate_cnpj("04.252.011/0001-10"), Ok(()));
@@ -78,12 +152,35 @@ mod tests {
         assert_eq!(validate_cnpj("40688134000161"), Ok(()));
     }
 
+    #[test]
+    fn test_valid_corpus() {
+        for (input, parsed) in VALID_CORPUS {
+            assert_eq!(parse_cnpj(input), Ok(parsed.to_string()), "{}", input);
+        }
+    }
+
     #[test]
     fn test_non_digit_characters() {
         assert_eq!(
             validate_cnpj("04.252.011/0001-AA"),
             Err(CnpjValidationError::NonDigitCharacters)
         );
+        assert_eq!(
+            validate_cnpj("12.ABC.345/01DE-3E"),
+            Err(CnpjValidationError::NonDigitCharacters)
+        );
+    }
+
+    #[test]
+    fn test_invalid_characters() {
+        assert_eq!(
+            validate_cnpj("12.ABÇ.345/01DE-35"),
+            Err(CnpjValidationError::InvalidCharacters)
+        );
+        assert_eq!(
+            validate_cnpj("12*ABC*345/01DE-35"),
+            Err(CnpjValidationError::InvalidCharacters)
+        );
     }
 
     #[test]
@@ -112,5 +209,38 @@ mod tests {
             validate_cnpj("04.252.011/0001-00"),
             Err(CnpjValidationError::InvalidCheckDigits)
         );
+        assert_eq!(
+            validate_cnpj("12.ABC.345/01DE-53"),
+            Err(CnpjValidationError::InvalidCheckDigits)
+        );
+        // Mesmo valor numérico, letra diferente
+        assert_eq!(
+            validate_cnpj("12.ABD.345/01DE-35"),
+            Err(CnpjValidationError::InvalidCheckDigits)
+        );
+    }
+
+    #[test]
+    fn test_calculate_check_digits() {
+        assert_eq!(calculate_check_digits("12ABC34501DE"), Ok("35".to_string()));
+        assert_eq!(calculate_check_digits("042520110001"), Ok("10".to_string()));
+        assert_eq!(
+            calculate_check_digits("12ABC345"),
+            Err(CnpjValidationError::InvalidLength(8))
+        );
+    }
+
+    #[test]
+    fn test_format_cnpj() {
+        assert_eq!(format_cnpj("04252011000110"), "04.252.011/0001-10");
+        assert_eq!(format_cnpj("12ABC34501DE35"), "12.ABC.345/01DE-35");
+        assert_eq!(format_cnpj("123"), "123");
+    }
+
+    #[test]
+    fn test_format_and_parse_round_trip() {
+        for (_, parsed) in VALID_CORPUS {
+            assert_eq!(parse_cnpj(&format_cnpj(parsed)), Ok(parsed.to_string()));
+        }
     }
 }
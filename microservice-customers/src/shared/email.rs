@@ -1,47 +1,95 @@
-
-
-
-
-
 #[derive(Debug, PartialEq)]
 pub enum EmailValidationError {
     InvalidLength(usize),
     MissingAtSymbol,
+    MultipleAtSymbols,
     SpecialCharacters,
+    InvalidLocalPart,
+    InvalidDomain,
 }
 
 impl std::fmt::Display for EmailValidationError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             EmailValidationError::InvalidLength(len) => 
-                write!(f, "Email must be at most 100 characters (got {})", len),
+                write!(f, "Email must be at most {} characters (got {})", MAX_EMAIL_LENGTH, len),
             EmailValidationError::MissingAtSymbol => 
                 write!(f, "Email must contain the '@' character"),
+            EmailValidationError::MultipleAtSymbols =>
+                write!(f, "Email must contain exactly one '@' character"),
             EmailValidationError::SpecialCharacters => 
                 write!(f, "Email cannot contain special characters"),
+            EmailValidationError::InvalidLocalPart =>
+                write!(f, "Email must have a name before the '@' without leading, trailing or consecutive dots"),
+            EmailValidationError::InvalidDomain =>
+                write!(f, "Email must have a valid domain after the '@'"),
         }
     }
 }
 
+/// DICT limit for email keys, applied to customer emails as well.
+pub const MAX_EMAIL_LENGTH: usize = 77;
+/// RFC 5321 limit for the part before the '@'.
+const MAX_LOCAL_PART_LENGTH: usize = 64;
+const MAX_DOMAIN_LABEL_LENGTH: usize = 63;
 
 pub fn validate_email(email: &str) -> Result<(), EmailValidationError> {
+    parse_email(email).map(|_| ())
+}
+
+/// Parses an address into the normalized form we store: trimmed and
+/// lowercased, so addresses differing only in case are the same customer
+/// email and the same key.
+///
+/// Accepts the RFC 5322 dot-atom form restricted to the characters mail
+/// providers actually hand out: letters, digits and `. _ - +` before the '@',
+/// and a hostname with at least two labels after it.
+pub fn parse_email(email: &str) -> Result<String, EmailValidationError> {
+    let email = email.trim();
+
     // Verifica o tamanho
-    if email.len() > 100 {
+    if email.len() > MAX_EMAIL_LENGTH {
         return Err(EmailValidationError::InvalidLength(email.len()));
     }
 
-    // Verifica se contém '@'
-    if !email.contains('@') {
-        return Err(EmailValidationError::MissingAtSymbol);
-    }
+    let (local, domain) = match email.split_once('@') {
+        Some((_, rest)) if rest.contains('@') => return Err(EmailValidationError::MultipleAtSymbols),
+        Some(parts) => parts,
+        None => return Err(EmailValidationError::MissingAtSymbol),
+    };
 
-    // Não pode conter caracteres especiais exceto . _ - @
-    let allowed = |c: char| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-' | '@');
-    if !email.chars().all(allowed) {
+    // Não pode conter caracteres especiais exceto . _ - +
+    let allowed_local = |c: char| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-' | '+');
+    let allowed_domain = |c: char| c.is_ascii_alphanumeric() || matches!(c, '.' | '-');
+    if !local.chars().all(allowed_local) || !domain.chars().all(allowed_domain) {
         return Err(EmailValidationError::SpecialCharacters);
     }
 
-    Ok(())
+    if local.is_empty()
+        || local.len() > MAX_LOCAL_PART_LENGTH
+        || local.starts_with('.')
+        || local.ends_with('.')
+        || local.contains("..")
+    {
+        return Err(EmailValidationError::InvalidLocalPart);
+    }
+
+    let labels: Vec<&str> = domain.split('.').collect();
+    let valid_label = |label: &&str| {
+        !label.is_empty()
+            && label.len() <= MAX_DOMAIN_LABEL_LENGTH
+            && !label.starts_with('-')
+            && !label.ends_with('-')
+    };
+    let valid_tld = labels
+        .last()
+        .is_some_and(|tld| tld.len() >= 2 && tld.chars().all(|c| c.is_ascii_alphabetic()));
+
+    if labels.len() < 2 || !labels.iter().all(valid_label) || !valid_tld {
+        return Err(EmailValidationError::InvalidDomain);
+    }
+
+    Ok(email.to_ascii_lowercase())
 }
 
 
@@ -55,12 +103,19 @@ mod tests {
         assert_eq!(validate_email("usuario@email.com"), Ok(()));
         assert_eq!(validate_email("nome.sobrenome-123@email.com"), Ok(()));
         assert_eq!(validate_email("a@b.co"), Ok(()));
+        assert_eq!(validate_email("user+tag@x.com"), Ok(()));
+        assert_eq!(validate_email("joao_silva@mail.empresa.com.br"), Ok(()));
     }
 
     #[test]
     fn test_invalid_length() {
         let long_email = format!("{}@email.com", "a".repeat(95));
         assert_eq!(validate_email(&long_email), Err(EmailValidationError::InvalidLength(long_email.len())));
+
+        let limit_email = format!("{}@{}.com", "a".repeat(60), "b".repeat(12));
+        assert_eq!(validate_email(&limit_email), Ok(()));
+        let over_limit_email = format!("{}@{}.com", "a".repeat(60), "b".repeat(13));
+        assert_eq!(validate_email(&over_limit_email), Err(EmailValidationError::InvalidLength(MAX_EMAIL_LENGTH + 1)));
     }
 
     #[test]
@@ -68,10 +123,40 @@ mod tests {
         assert_eq!(validate_email("usuarioemail.com"), Err(EmailValidationError::MissingAtSymbol));
     }
 
+    #[test]
+    fn test_multiple_at_symbols() {
+        assert_eq!(validate_email("@@"), Err(EmailValidationError::MultipleAtSymbols));
+        assert_eq!(validate_email("user@name@email.com"), Err(EmailValidationError::MultipleAtSymbols));
+    }
+
     #[test]
     fn test_special_characters() {
         assert_eq!(validate_email("user!@email.com"), Err(EmailValidationError::SpecialCharacters));
         assert_eq!(validate_email("user#email@email.com"), Err(EmailValidationError::SpecialCharacters));
         assert_eq!(validate_email("user$email@email.com"), Err(EmailValidationError::SpecialCharacters));
+        assert_eq!(validate_email("user@em_ail.com"), Err(EmailValidationError::SpecialCharacters));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_invalid_local_part() {
+        assert_eq!(validate_email("@email.com"), Err(EmailValidationError::InvalidLocalPart));
+        assert_eq!(validate_email(".user@email.com"), Err(EmailValidationError::InvalidLocalPart));
+        assert_eq!(validate_email("user.@email.com"), Err(EmailValidationError::InvalidLocalPart));
+        assert_eq!(validate_email("us..er@email.com"), Err(EmailValidationError::InvalidLocalPart));
+    }
+
+    #[test]
+    fn test_invalid_domain() {
+        assert_eq!(validate_email("user@"), Err(EmailValidationError::InvalidDomain));
+        assert_eq!(validate_email("user@localhost"), Err(EmailValidationError::InvalidDomain));
+        assert_eq!(validate_email("user@email..com"), Err(EmailValidationError::InvalidDomain));
+        assert_eq!(validate_email("user@-email.com"), Err(EmailValidationError::InvalidDomain));
+        assert_eq!(validate_email("user@email.c"), Err(EmailValidationError::InvalidDomain));
+        assert_eq!(validate_email("user@email.123"), Err(EmailValidationError::InvalidDomain));
+    }
+
+    #[test]
+    fn test_parse_normalizes() {
+        assert_eq!(parse_email("  User.Name@Email.COM "), Ok("user.name@email.com".to_string()));
+    }
+}
@@ -0,0 +1,58 @@
+/// Masks a CPF the way DICT shows it to payers: only the middle six digits
+/// are visible, e.g. "***.456.789-**".
+pub fn mask_cpf(cpf: &str) -> String {
+    let digits: Vec<char> = cpf.chars().filter(|c| c.is_ascii_digit()).collect();
+
+    if digits.len() != 11 {
+        return "***.***.***-**".to_string();
+    }
+
+    let middle: String = digits[3..9].iter().collect();
+    format!("***.{}.{}-**", &middle[..3], &middle[3..])
+}
+
+/// Keeps the first name and the initial of every other name, masking the rest:
+/// "Maria Souza Lima" becomes "Maria S**** L***".
+pub fn mask_name(full_name: &str) -> String {
+    full_name
+        .split_whitespace()
+        .enumerate()
+        .map(|(i, part)| {
+            if i == 0 {
+                return part.to_string();
+            }
+
+            let mut chars = part.chars();
+            match chars.next() {
+                Some(initial) => format!("{}{}", initial, "*".repeat(chars.count())),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mask_cpf() {
+        assert_eq!(mask_cpf("12345678901"), "***.456.789-**");
+        assert_eq!(mask_cpf("529.982.247-25"), "***.982.247-**");
+    }
+
+    #[test]
+    fn test_mask_cpf_with_invalid_length() {
+        assert_eq!(mask_cpf("123"), "***.***.***-**");
+        assert_eq!(mask_cpf(""), "***.***.***-**");
+    }
+
+    #[test]
+    fn test_mask_name() {
+        assert_eq!(mask_name("Maria Souza Lima"), "Maria S**** L***");
+        assert_eq!(mask_name("  João   da Silva "), "João d* S****");
+        assert_eq!(mask_name("Ana"), "Ana");
+        assert_eq!(mask_name(""), "");
+    }
+}
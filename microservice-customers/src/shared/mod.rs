@@ -5,4 +5,6 @@ pub mod sha3;
 pub mod cpf;
 pub mod cnpj;
 pub mod email;
-pub mod phone;
\ No newline at end of file
+pub mod phone;
+pub mod mask;
+pub mod otp;
\ No newline at end of file
@@ -0,0 +1,43 @@
+use sha3::{Digest, Sha3_256};
+use uuid::Uuid;
+
+pub const CODE_DIGITS: usize = 6;
+
+/// Random numeric code. v4 UUIDs come from the OS CSPRNG, so their random
+/// bits are a safe source without an extra dependency.
+pub fn generate_code() -> String {
+    let modulus = 10u128.pow(CODE_DIGITS as u32);
+    format!("{:0width$}", Uuid::new_v4().as_u128() % modulus, width = CODE_DIGITS)
+}
+
+/// Hash stored in place of the code, salted with the challenge id so equal
+/// codes never share a hash.
+pub fn hash_code(challenge_id: Uuid, code: &str) -> String {
+    let mut hasher = Sha3_256::new();
+    hasher.update(challenge_id.as_bytes());
+    hasher.update(code.trim().as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_code() {
+        for _ in 0..100 {
+            let code = generate_code();
+            assert_eq!(code.len(), CODE_DIGITS);
+            assert!(code.chars().all(|c| c.is_ascii_digit()));
+        }
+    }
+
+    #[test]
+    fn test_hash_code() {
+        let id = Uuid::new_v4();
+        assert_eq!(hash_code(id, "123456"), hash_code(id, " 123456 "));
+        assert_eq!(hash_code(id, "123456").len(), 64);
+        assert_ne!(hash_code(id, "123456"), hash_code(id, "123457"));
+        assert_ne!(hash_code(id, "123456"), hash_code(Uuid::new_v4(), "123456"));
+    }
+}
@@ -2,6 +2,9 @@
 pub enum PhoneValidationError {
     InvalidLength(usize),
     SpecialCharacters,
+    InvalidCountryCode,
+    InvalidAreaCode(String),
+    InvalidNumber,
 }
 
 impl std::fmt::Display for PhoneValidationError {
@@ -11,11 +14,39 @@ impl std::fmt::Display for PhoneValidationError {
                 write!(f, "Phone number must be at most 20 characters (got {})", len),
             PhoneValidationError::SpecialCharacters => 
                 write!(f, "Phone number cannot contain special characters"),
+            PhoneValidationError::InvalidCountryCode =>
+                write!(f, "Only Brazilian phone numbers (+55) are accepted"),
+            PhoneValidationError::InvalidAreaCode(ddd) =>
+                write!(f, "Invalid area code (DDD) {}", ddd),
+            PhoneValidationError::InvalidNumber =>
+                write!(f, "Phone number must have 9 digits starting with 9 (mobile) or 8 digits starting with 2 to 5 (landline)"),
         }
     }
 }
 
+pub const COUNTRY_CODE: &str = "55";
+
+/// Area codes (DDD) assigned by Anatel.
+const AREA_CODES: [u8; 67] = [
+    11, 12, 13, 14, 15, 16, 17, 18, 19,
+    21, 22, 24, 27, 28,
+    31, 32, 33, 34, 35, 37, 38,
+    41, 42, 43, 44, 45, 46, 47, 48, 49,
+    51, 53, 54, 55,
+    61, 62, 63, 64, 65, 66, 67, 68, 69,
+    71, 73, 74, 75, 77, 79,
+    81, 82, 83, 84, 85, 86, 87, 88, 89,
+    91, 92, 93, 94, 95, 96, 97, 98, 99,
+];
+
 pub fn validate_phone(phone: &str) -> Result<(), PhoneValidationError> {
+    parse_phone(phone).map(|_| ())
+}
+
+/// Parses a Brazilian phone number written in any usual way ("(11) 91234-5678",
+/// "11912345678", "+55 11 91234-5678") into the E.164 form used by phone keys:
+/// "+5511912345678".
+pub fn parse_phone(phone: &str) -> Result<String, PhoneValidationError> {
     // Verifica o tamanho
     if phone.len() > 20 {
         return Err(PhoneValidationError::InvalidLength(phone.len()));
@@ -27,7 +58,46 @@ pub fn validate_phone(phone: &str) -> Result<(), PhoneValidationError> {
         return Err(PhoneValidationError::SpecialCharacters);
     }
 
-    Ok(())
+    let trimmed = phone.trim();
+    let international = trimmed.starts_with('+');
+
+    // '+' só é aceito como prefixo internacional
+    if trimmed.chars().skip(1).any(|c| c == '+') {
+        return Err(PhoneValidationError::SpecialCharacters);
+    }
+
+    let digits: String = trimmed.chars().filter(|c| c.is_ascii_digit()).collect();
+
+    let national = if international || digits.len() > 11 {
+        digits
+            .strip_prefix(COUNTRY_CODE)
+            .ok_or(PhoneValidationError::InvalidCountryCode)?
+    } else {
+        digits.as_str()
+    };
+
+    if national.len() != 10 && national.len() != 11 {
+        return Err(PhoneValidationError::InvalidNumber);
+    }
+
+    let (ddd, number) = national.split_at(2);
+
+    if !AREA_CODES.contains(&ddd.parse::<u8>().unwrap_or(0)) {
+        return Err(PhoneValidationError::InvalidAreaCode(ddd.to_string()));
+    }
+
+    // Celulares têm 9 dígitos começando com 9; fixos, 8 dígitos começando de 2 a 5
+    let valid_number = match number.len() {
+        9 => number.starts_with('9'),
+        8 => matches!(number.chars().next(), Some('2'..='5')),
+        _ => false,
+    };
+
+    if !valid_number {
+        return Err(PhoneValidationError::InvalidNumber);
+    }
+
+    Ok(format!("+{}{}{}", COUNTRY_CODE, ddd, number))
 }
 
 #[cfg(test)]
@@ -52,5 +122,35 @@ mod tests {
         assert_eq!(validate_phone("11@91234-5678"), Err(PhoneValidationError::SpecialCharacters));
         assert_eq!(validate_phone("11#91234-5678"), Err(PhoneValidationError::SpecialCharacters));
         assert_eq!(validate_phone("11$91234-5678"), Err(PhoneValidationError::SpecialCharacters));
+        assert_eq!(validate_phone("11+91234-5678"), Err(PhoneValidationError::SpecialCharacters));
+    }
+
+    #[test]
+    fn test_parse_normalizes_to_e164() {
+        assert_eq!(parse_phone("(11) 91234-5678"), Ok("+5511912345678".to_string()));
+        assert_eq!(parse_phone("+5511912345678"), Ok("+5511912345678".to_string()));
+        assert_eq!(parse_phone("+55 (21) 98765-4321"), Ok("+5521987654321".to_string()));
+        assert_eq!(parse_phone("5511912345678"), Ok("+5511912345678".to_string()));
+        assert_eq!(parse_phone("(61) 3344-5566"), Ok("+556133445566".to_string()));
+    }
+
+    #[test]
+    fn test_invalid_country_code() {
+        assert_eq!(parse_phone("+1 415 555 2671"), Err(PhoneValidationError::InvalidCountryCode));
+        assert_eq!(parse_phone("441234567890"), Err(PhoneValidationError::InvalidCountryCode));
+    }
+
+    #[test]
+    fn test_invalid_area_code() {
+        assert_eq!(parse_phone("(10) 91234-5678"), Err(PhoneValidationError::InvalidAreaCode("10".to_string())));
+        assert_eq!(parse_phone("+55 20 91234-5678"), Err(PhoneValidationError::InvalidAreaCode("20".to_string())));
+    }
+
+    #[test]
+    fn test_mobile_nine_digit_rule() {
+        assert_eq!(parse_phone("(11) 81234-5678"), Err(PhoneValidationError::InvalidNumber));
+        assert_eq!(parse_phone("(11) 1234-5678"), Err(PhoneValidationError::InvalidNumber));
+        assert_eq!(parse_phone("(11) 1234-567"), Err(PhoneValidationError::InvalidNumber));
+        assert_eq!(parse_phone("+55"), Err(PhoneValidationError::InvalidNumber));
     }
 }
@@ -0,0 +1,25 @@
+-- ============================
+-- Balance projection
+-- ============================
+
+-- TABLE: account_balances (one row per account, updated with every posting)
+CREATE TABLE account_balances (
+    account_id      UUID PRIMARY KEY,
+    balance         NUMERIC(19,4) NOT NULL DEFAULT 0,
+    entries_count   BIGINT NOT NULL DEFAULT 0,
+    version         BIGINT NOT NULL DEFAULT 0,
+    last_entry_at   TIMESTAMPTZ,
+    updated_at      TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- TABLE: balance_snapshots (balance including every entry with created_at <= taken_at)
+CREATE TABLE balance_snapshots (
+    id              UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    account_id      UUID NOT NULL,
+    balance         NUMERIC(19,4) NOT NULL,
+    entries_count   BIGINT NOT NULL,
+    taken_at        TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX idx_balance_snapshots_account_taken ON balance_snapshots(account_id, taken_at DESC);
+CREATE INDEX idx_ledger_entries_account_created ON ledger_entries(account_id, created_at);
@@ -0,0 +1,31 @@
+-- ============================
+-- Statement generation
+-- ============================
+
+-- period_end is exclusive: a statement covers entries with period_start <= created_at < period_end
+ALTER TABLE statements
+    ADD COLUMN period_type    VARCHAR(10) NOT NULL DEFAULT 'DAILY' CHECK (period_type IN ('DAILY', 'MONTHLY')),
+    ADD COLUMN total_credits  NUMERIC(19,4) NOT NULL DEFAULT 0,
+    ADD COLUMN total_debits   NUMERIC(19,4) NOT NULL DEFAULT 0,
+    ADD COLUMN entries_count  BIGINT NOT NULL DEFAULT 0;
+
+ALTER TABLE statements ALTER COLUMN period_type DROP DEFAULT;
+
+CREATE UNIQUE INDEX uq_statements_account_period ON statements(account_id, period_type, period_start);
+
+-- TABLE: statement_runs (one per closed period; last_account_id is the resume cursor)
+CREATE TABLE statement_runs (
+    id                  UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    period_type         VARCHAR(10) NOT NULL CHECK (period_type IN ('DAILY', 'MONTHLY')),
+    period_start        TIMESTAMPTZ NOT NULL,
+    period_end          TIMESTAMPTZ NOT NULL,
+    status              VARCHAR(10) NOT NULL DEFAULT 'RUNNING' CHECK (status IN ('RUNNING', 'COMPLETED')),
+    last_account_id     UUID,
+    accounts_processed  BIGINT NOT NULL DEFAULT 0,
+    started_at          TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    finished_at         TIMESTAMPTZ,
+    UNIQUE (period_type, period_start)
+);
+
+-- Accounts only get statements for periods that end after they were opened
+ALTER TABLE account_balances ADD COLUMN created_at TIMESTAMPTZ NOT NULL DEFAULT NOW();
@@ -0,0 +1,22 @@
+-- ============================
+-- Funds holds
+-- ============================
+
+-- TABLE: holds (reserve funds while a transfer is PENDING)
+CREATE TABLE holds (
+    id              UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    account_id      UUID NOT NULL,
+    transfer_id     UUID NOT NULL UNIQUE,
+    amount          NUMERIC(19,4) NOT NULL CHECK (amount > 0),
+    status          VARCHAR(10) NOT NULL DEFAULT 'ACTIVE'
+                    CHECK (status IN ('ACTIVE', 'CAPTURED', 'RELEASED', 'EXPIRED')),
+    expires_at      TIMESTAMPTZ NOT NULL,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    resolved_at     TIMESTAMPTZ
+);
+
+CREATE INDEX idx_holds_account_id ON holds(account_id);
+CREATE INDEX idx_holds_active_expiry ON holds(expires_at) WHERE status = 'ACTIVE';
+
+-- available balance = balance - held_amount; ledger balance is untouched by holds
+ALTER TABLE account_balances ADD COLUMN held_amount NUMERIC(19,4) NOT NULL DEFAULT 0 CHECK (held_amount >= 0);
@@ -0,0 +1,9 @@
+-- ============================
+-- Overdraft protection
+-- ============================
+
+-- A posting may take the available balance down to -overdraft_limit, never further
+ALTER TABLE account_balances
+    ADD COLUMN overdraft_limit          NUMERIC(19,4) NOT NULL DEFAULT 0 CHECK (overdraft_limit >= 0),
+    ADD COLUMN overdraft_approved_by    VARCHAR(255),
+    ADD COLUMN overdraft_approved_at    TIMESTAMPTZ;
@@ -0,0 +1,16 @@
+-- ============================
+-- Tamper-evident hash chain
+-- ============================
+
+-- entry_hash = SHA3-256 over the entry content and prev_hash, the entry_hash of the
+-- previous entry (account_seq - 1) of the same account. Entries posted before this
+-- migration have no hash and sit outside the chain.
+ALTER TABLE ledger_entries
+    ADD COLUMN account_seq  BIGINT,
+    ADD COLUMN prev_hash    CHAR(64),
+    ADD COLUMN entry_hash   CHAR(64);
+
+CREATE UNIQUE INDEX uq_ledger_entries_account_seq ON ledger_entries(account_id, account_seq);
+
+-- Tip of each account's chain, updated together with the balance
+ALTER TABLE account_balances ADD COLUMN last_entry_hash CHAR(64);
@@ -0,0 +1,40 @@
+-- ============================
+-- Transfers vs ledger reconciliation
+-- ============================
+
+-- One row per reconciliation of a business day. A day can be reconciled
+-- again after fixes; each attempt keeps its own report.
+CREATE TABLE reconciliation_runs (
+    id                  UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    business_date       DATE NOT NULL,
+    period_start        TIMESTAMPTZ NOT NULL,
+    period_end          TIMESTAMPTZ NOT NULL,
+    status              VARCHAR(16) NOT NULL DEFAULT 'RUNNING'
+                        CHECK (status IN ('RUNNING', 'COMPLETED', 'FAILED')),
+    transfers_checked   BIGINT NOT NULL DEFAULT 0,
+    transfers_matched   BIGINT NOT NULL DEFAULT 0,
+    issues_found        BIGINT NOT NULL DEFAULT 0,
+    error               TEXT,
+    started_at          TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    finished_at         TIMESTAMPTZ
+);
+
+CREATE INDEX idx_reconciliation_runs_date ON reconciliation_runs(business_date, started_at);
+
+-- Report lines: every discrepancy found by a run
+CREATE TABLE reconciliation_items (
+    id                  UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    run_id              UUID NOT NULL REFERENCES reconciliation_runs(id),
+    transfer_id         UUID NOT NULL,
+    issue               VARCHAR(32) NOT NULL
+                        CHECK (issue IN ('MISSING_ENTRY', 'DUPLICATE_ENTRY', 'AMOUNT_MISMATCH',
+                                         'UNEXPECTED_ENTRY', 'MISSING_TRANSFER')),
+    side                entry_type,
+    account_id          UUID,
+    expected_amount     NUMERIC(19,4),
+    actual_amount       NUMERIC(19,4),
+    details             TEXT NOT NULL,
+    created_at          TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_reconciliation_items_run ON reconciliation_items(run_id);
@@ -0,0 +1,28 @@
+-- ============================
+-- Transfer events consumed from Kafka
+-- ============================
+
+-- Written in the same transaction as the postings of an event, so each
+-- (transfer, event type) is applied exactly once however often it is delivered.
+CREATE TABLE processed_events (
+    transfer_id     UUID NOT NULL,
+    event_type      VARCHAR(16) NOT NULL CHECK (event_type IN ('CREATED', 'POSTED', 'REVERSED')),
+    processed_at    TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (transfer_id, event_type)
+);
+
+-- Events that could not be applied, kept verbatim for inspection and replay
+CREATE TABLE dead_letter_events (
+    id              UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    topic           VARCHAR(255) NOT NULL,
+    kafka_partition INT NOT NULL,
+    kafka_offset    BIGINT NOT NULL,
+    event_key       TEXT,
+    payload         TEXT NOT NULL,
+    transfer_id     UUID,
+    event_type      VARCHAR(16),
+    error           TEXT NOT NULL,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_dead_letter_events_transfer_id ON dead_letter_events(transfer_id);
@@ -0,0 +1,15 @@
+-- ============================
+-- Balance-changed outbox
+-- ============================
+
+-- Filled in the transaction that changes a balance and drained by the
+-- publisher, which sends each row to the `balances` topic and deletes it.
+-- `version` is account_balances.version, so consumers can drop stale events.
+CREATE TABLE balance_outbox (
+    id                  BIGSERIAL PRIMARY KEY,
+    account_id          UUID NOT NULL,
+    ledger_balance      NUMERIC(19,4) NOT NULL,
+    available_balance   NUMERIC(19,4) NOT NULL,
+    version             BIGINT NOT NULL,
+    created_at          TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
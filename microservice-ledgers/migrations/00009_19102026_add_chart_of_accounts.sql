@@ -0,0 +1,22 @@
+-- ============================
+-- Chart of accounts
+-- ============================
+
+-- Internal ledger accounts. Their id is the account_id used in ledger_entries
+-- and account_balances; postings can also reference them by code. Any
+-- account_id not listed here is a customer account (a liability of the bank).
+CREATE TABLE ledger_accounts (
+    id              UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    code            VARCHAR(64) NOT NULL UNIQUE,
+    name            VARCHAR(255) NOT NULL,
+    account_class   VARCHAR(16) NOT NULL
+                    CHECK (account_class IN ('ASSET', 'LIABILITY', 'EQUITY', 'REVENUE', 'EXPENSE')),
+    normal_balance  entry_type NOT NULL,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+INSERT INTO ledger_accounts (code, name, account_class, normal_balance) VALUES
+    ('SETTLEMENT', 'Settlement with other Pix participants', 'ASSET', 'DEBIT'),
+    ('SUSPENSE', 'Suspense: funds pending investigation', 'LIABILITY', 'CREDIT'),
+    ('FEE_REVENUE', 'Fee revenue', 'REVENUE', 'CREDIT'),
+    ('WRITE_OFFS', 'Write-offs of unrecoverable balances', 'EXPENSE', 'DEBIT');
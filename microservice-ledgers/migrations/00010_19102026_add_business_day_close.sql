@@ -0,0 +1,58 @@
+-- ============================
+-- End-of-day close
+-- ============================
+
+-- Accounting date of each entry: always the Brasília date of created_at, since
+-- postings are booked on the current business day. Reopening a closed day only
+-- lets it be closed again; corrections to it are posted on the current day.
+ALTER TABLE ledger_entries ADD COLUMN business_date DATE;
+UPDATE ledger_entries SET business_date = (created_at AT TIME ZONE 'America/Sao_Paulo')::date;
+ALTER TABLE ledger_entries ALTER COLUMN business_date SET NOT NULL;
+
+CREATE INDEX idx_ledger_entries_business_date ON ledger_entries(business_date);
+
+-- Postings take a shared lock on their day's row and closing takes an
+-- exclusive one, so a close waits for in-flight postings and blocks later ones.
+CREATE TABLE business_days (
+    business_date   DATE PRIMARY KEY,
+    status          VARCHAR(16) NOT NULL DEFAULT 'OPEN'
+                    CHECK (status IN ('OPEN', 'CLOSED', 'REOPENED')),
+    closed_at       TIMESTAMPTZ,
+    closed_by       VARCHAR(255),
+    updated_at      TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Audit trail of every close and reopen
+CREATE TABLE business_day_events (
+    id              UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    business_date   DATE NOT NULL REFERENCES business_days(business_date),
+    action          VARCHAR(16) NOT NULL CHECK (action IN ('CLOSE', 'REOPEN')),
+    actor           VARCHAR(255) NOT NULL,
+    reason          TEXT,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_business_day_events_date ON business_day_events(business_date, created_at);
+
+-- Trial balance produced by each close. A reopened day closed again gets a new report.
+CREATE TABLE day_closings (
+    id              UUID PRIMARY KEY,
+    business_date   DATE NOT NULL REFERENCES business_days(business_date),
+    total_debits    NUMERIC(19,4) NOT NULL,
+    total_credits   NUMERIC(19,4) NOT NULL,
+    entries_count   BIGINT NOT NULL,
+    balanced        BOOLEAN NOT NULL,
+    closed_by       VARCHAR(255) NOT NULL,
+    closed_at       TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX idx_day_closings_date ON day_closings(business_date, closed_at);
+
+CREATE TABLE day_closing_lines (
+    closing_id      UUID NOT NULL REFERENCES day_closings(id),
+    account_class   VARCHAR(16) NOT NULL,
+    debits          NUMERIC(19,4) NOT NULL,
+    credits         NUMERIC(19,4) NOT NULL,
+    entries_count   BIGINT NOT NULL,
+    PRIMARY KEY (closing_id, account_class)
+);
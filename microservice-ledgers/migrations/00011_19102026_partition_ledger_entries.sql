@@ -0,0 +1,92 @@
+-- ============================
+-- Monthly partitions of ledger_entries
+-- ============================
+
+-- Partitions cover calendar months in UTC and are named ledger_entries_pYYYYMM.
+-- Creating one that already exists is a no-op.
+CREATE OR REPLACE FUNCTION create_ledger_entries_partition(month DATE) RETURNS TEXT AS $$
+DECLARE
+    range_start     TIMESTAMPTZ := date_trunc('month', month::timestamp) AT TIME ZONE 'UTC';
+    range_end       TIMESTAMPTZ := (date_trunc('month', month::timestamp) + INTERVAL '1 month') AT TIME ZONE 'UTC';
+    partition_name  TEXT := 'ledger_entries_p' || to_char(month, 'YYYYMM');
+BEGIN
+    IF to_regclass(partition_name) IS NULL THEN
+        EXECUTE format(
+            'CREATE TABLE %I PARTITION OF ledger_entries FOR VALUES FROM (%L) TO (%L)',
+            partition_name, range_start, range_end
+        );
+    END IF;
+
+    RETURN partition_name;
+END;
+$$ LANGUAGE plpgsql;
+
+-- Makes sure the current month and the next `months_ahead` months have a partition.
+-- Called at startup and by the partition maintenance job.
+CREATE OR REPLACE FUNCTION ensure_ledger_entries_partitions(months_ahead INT) RETURNS VOID AS $$
+BEGIN
+    PERFORM create_ledger_entries_partition((date_trunc('month', NOW() AT TIME ZONE 'UTC') + make_interval(months => m))::date)
+       FROM generate_series(0, months_ahead) AS m;
+END;
+$$ LANGUAGE plpgsql;
+
+-- Rebuild the table as a partitioned one. Unique constraints must include the
+-- partition key, so the primary key becomes (id, created_at) and account_seq is
+-- no longer unique across the table: it is assigned under the balance row lock.
+ALTER TABLE ledger_entries RENAME TO ledger_entries_unpartitioned;
+
+CREATE TABLE ledger_entries (
+    id              UUID NOT NULL DEFAULT uuid_generate_v4(),
+    transfer_id     UUID NOT NULL,
+    account_id      UUID NOT NULL,
+    entry_type      entry_type NOT NULL,
+    amount          NUMERIC(19,4) NOT NULL,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    account_seq     BIGINT,
+    prev_hash       CHAR(64),
+    entry_hash      CHAR(64),
+    business_date   DATE NOT NULL
+) PARTITION BY RANGE (created_at);
+
+SELECT create_ledger_entries_partition(month::date)
+  FROM generate_series(
+           date_trunc('month', COALESCE((SELECT MIN(created_at) FROM ledger_entries_unpartitioned), NOW()) AT TIME ZONE 'UTC'),
+           date_trunc('month', NOW() AT TIME ZONE 'UTC'),
+           INTERVAL '1 month'
+       ) AS month;
+
+SELECT ensure_ledger_entries_partitions(3);
+
+INSERT INTO ledger_entries (id, transfer_id, account_id, entry_type, amount, created_at,
+                            account_seq, prev_hash, entry_hash, business_date)
+     SELECT id, transfer_id, account_id, entry_type, amount, created_at,
+            account_seq, prev_hash, entry_hash, business_date
+       FROM ledger_entries_unpartitioned;
+
+DROP TABLE ledger_entries_unpartitioned;
+
+ALTER TABLE ledger_entries ADD PRIMARY KEY (id, created_at);
+
+CREATE INDEX idx_ledger_entries_transfer_id ON ledger_entries(transfer_id);
+CREATE INDEX idx_ledger_entries_account_created ON ledger_entries(account_id, created_at);
+CREATE INDEX idx_ledger_entries_account_seq ON ledger_entries(account_id, account_seq);
+CREATE INDEX idx_ledger_entries_business_date ON ledger_entries(business_date);
+
+-- Snapshots also record the chain position they were taken at, so balances and
+-- chain verification can start from them once older partitions are gone.
+ALTER TABLE balance_snapshots
+    ADD COLUMN account_seq  BIGINT,
+    ADD COLUMN entry_hash   CHAR(64);
+
+-- Partitions exported to a compressed file and detached. Everything before the
+-- latest range_end is only available through the archive files and the
+-- balance snapshots written when each partition was archived.
+CREATE TABLE ledger_partition_archives (
+    partition_name  VARCHAR(63) PRIMARY KEY,
+    range_start     TIMESTAMPTZ NOT NULL,
+    range_end       TIMESTAMPTZ NOT NULL,
+    rows_count      BIGINT NOT NULL,
+    file_path       TEXT NOT NULL,
+    checksum_sha3   CHAR(64) NOT NULL,
+    archived_at     TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
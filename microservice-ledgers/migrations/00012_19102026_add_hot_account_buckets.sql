@@ -0,0 +1,32 @@
+-- ============================
+-- Hot accounts
+-- ============================
+
+-- Accounts that receive too many credits to serialize on their balance row.
+-- Credits to them update one of `buckets` counter rows instead; anything that
+-- takes money out folds the buckets back into account_balances first.
+CREATE TABLE hot_accounts (
+    account_id      UUID PRIMARY KEY,
+    buckets         INT NOT NULL CHECK (buckets BETWEEN 2 AND 64),
+    designated_by   VARCHAR(255) NOT NULL,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE account_balance_buckets (
+    account_id      UUID NOT NULL REFERENCES hot_accounts(account_id) ON DELETE CASCADE,
+    bucket          INT NOT NULL,
+    balance         NUMERIC(19,4) NOT NULL DEFAULT 0,
+    entries_count   BIGINT NOT NULL DEFAULT 0,
+    updated_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (account_id, bucket)
+);
+
+-- Bucketed credits are not linked into the account's hash chain when posted,
+-- since that needs the balance row. They wait here until the next fold.
+CREATE TABLE hot_account_pending_entries (
+    entry_id        UUID PRIMARY KEY,
+    account_id      UUID NOT NULL,
+    created_at      TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX idx_hot_pending_account ON hot_account_pending_entries(account_id, created_at);
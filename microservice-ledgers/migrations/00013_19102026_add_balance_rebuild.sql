@@ -0,0 +1,40 @@
+-- ============================
+-- Balance rebuild
+-- ============================
+
+-- One replay of ledger_entries into the shadow tables below. scope is
+-- "all", "account:<uuid>" or "shard:<index>/<count>".
+CREATE TABLE rebuild_runs (
+    id                  UUID PRIMARY KEY,
+    scope               VARCHAR(64) NOT NULL,
+    status              VARCHAR(16) NOT NULL CHECK (status IN ('BUILDING', 'BUILT', 'SWAPPED')),
+    accounts_count      BIGINT NOT NULL DEFAULT 0,
+    entries_replayed    BIGINT NOT NULL DEFAULT 0,
+    created_at          TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    built_at            TIMESTAMPTZ,
+    swapped_at          TIMESTAMPTZ
+);
+
+-- Shadow of account_balances: only the columns derived from entries
+CREATE TABLE account_balances_rebuild (
+    run_id          UUID NOT NULL REFERENCES rebuild_runs(id) ON DELETE CASCADE,
+    account_id      UUID NOT NULL,
+    balance         NUMERIC(19,4) NOT NULL,
+    entries_count   BIGINT NOT NULL,
+    last_entry_at   TIMESTAMPTZ,
+    last_entry_hash CHAR(64),
+    PRIMARY KEY (run_id, account_id)
+);
+
+-- Shadow of the balance_snapshots that are not archive baselines
+CREATE TABLE balance_snapshots_rebuild (
+    run_id          UUID NOT NULL REFERENCES rebuild_runs(id) ON DELETE CASCADE,
+    account_id      UUID NOT NULL,
+    balance         NUMERIC(19,4) NOT NULL,
+    entries_count   BIGINT NOT NULL,
+    taken_at        TIMESTAMPTZ NOT NULL,
+    account_seq     BIGINT,
+    entry_hash      CHAR(64)
+);
+
+CREATE INDEX idx_balance_snapshots_rebuild_run ON balance_snapshots_rebuild(run_id, account_id);
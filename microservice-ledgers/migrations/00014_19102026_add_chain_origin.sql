@@ -0,0 +1,14 @@
+-- ============================
+-- Hash chain origin
+-- ============================
+
+-- account_seq of the link before an account's first chained entry. Accounts opened
+-- after the chain was introduced start at 0, so their first entry is account_seq 1;
+-- accounts with unhashed entries from before it start after those entries.
+ALTER TABLE account_balances ADD COLUMN chain_origin_seq BIGINT NOT NULL DEFAULT 0;
+
+UPDATE account_balances b
+   SET chain_origin_seq = COALESCE(
+           (SELECT MIN(e.account_seq) - 1 FROM ledger_entries e
+             WHERE e.account_id = b.account_id AND e.account_seq IS NOT NULL),
+           b.entries_count);
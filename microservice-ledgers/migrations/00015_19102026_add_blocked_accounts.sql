@@ -0,0 +1,11 @@
+-- ============================
+-- Blocked accounts
+-- ============================
+
+-- Accounts blocked by microservice-customers (published on the `accounts` topic).
+-- Postings and holds that touch one of them are refused.
+CREATE TABLE blocked_accounts (
+    account_id      UUID PRIMARY KEY,
+    blocked_at      TIMESTAMPTZ NOT NULL,
+    recorded_at     TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,91 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AccountBalance {
+    pub account_id: Uuid,
+    pub balance: Decimal,
+    pub held_amount: Decimal,
+    pub available_balance: Decimal,
+    pub overdraft_limit: Decimal,
+    pub entries_count: i64,
+    pub version: i64,
+    pub last_entry_at: Option<DateTime<Utc>>,
+    pub last_entry_hash: Option<String>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl AccountBalance {
+    pub fn hold(&mut self, amount: Decimal) {
+        self.held_amount += amount;
+        self.available_balance = self.balance - self.held_amount;
+    }
+
+    pub fn release(&mut self, amount: Decimal) {
+        self.held_amount -= amount;
+        self.available_balance = self.balance - self.held_amount;
+    }
+
+    pub fn apply(&mut self, signed_amount: Decimal) {
+        self.balance += signed_amount;
+        self.available_balance = self.balance - self.held_amount;
+    }
+
+    /// Adds credits still sitting in hot-account buckets.
+    pub fn include_unfolded(&mut self, amount: Decimal, entries_count: i64) {
+        self.apply(amount);
+        self.entries_count += entries_count;
+    }
+
+    /// Whether `amount` can leave the account without going past its overdraft limit.
+    pub fn can_spend(&self, amount: Decimal) -> bool {
+        self.available_balance - amount >= -self.overdraft_limit
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct BalanceCheck {
+    pub account_id: Uuid,
+    pub projected_balance: Decimal,
+    pub entries_sum: Decimal,
+    pub snapshot_balance: Decimal,
+    pub consistent: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BalanceMismatch {
+    pub account_id: Uuid,
+    pub projected_balance: Decimal,
+    pub entries_sum: Decimal,
+}
+
+#[derive(Debug, Serialize)]
+pub struct HistoricalBalance {
+    pub account_id: Uuid,
+    pub balance: Decimal,
+    pub as_of: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DailyMovement {
+    pub date: NaiveDate,
+    pub credits: Decimal,
+    pub debits: Decimal,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DailyBalance {
+    pub date: NaiveDate,
+    pub opening_balance: Decimal,
+    pub credits: Decimal,
+    pub debits: Decimal,
+    pub closing_balance: Decimal,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct OverdraftInput {
+    pub limit: Decimal,
+    pub approved_by: String,
+}
@@ -0,0 +1,59 @@
+use crate::balance::balance::{
+    AccountBalance, BalanceCheck, BalanceMismatch, DailyBalance, HistoricalBalance,
+    OverdraftInput,
+};
+use crate::balance::service;
+use crate::utils::error::LedgerError;
+use rocket::{get, put, routes, serde::json::Json, Either, Route};
+
+#[get("/accounts/<id>/balance?<as_of>")]
+async fn get_balance(
+    id: &str,
+    as_of: Option<&str>,
+) -> Result<Either<Json<AccountBalance>, Json<HistoricalBalance>>, LedgerError> {
+    match as_of {
+        Some(as_of) => service::get_balance_as_of(id, as_of)
+            .await
+            .map(|b| Either::Right(Json(b))),
+        None => service::get_balance(id).await.map(|b| Either::Left(Json(b))),
+    }
+}
+
+#[get("/accounts/<id>/balance/daily?<from>&<to>")]
+async fn get_daily_balances(
+    id: &str,
+    from: &str,
+    to: &str,
+) -> Result<Json<Vec<DailyBalance>>, LedgerError> {
+    service::get_daily_balances(id, from, to).await.map(Json)
+}
+
+#[put("/accounts/<id>/overdraft", format = "json", data = "<input>")]
+async fn set_overdraft(
+    id: &str,
+    input: Json<OverdraftInput>,
+) -> Result<Json<AccountBalance>, LedgerError> {
+    service::set_overdraft_limit(id, input.into_inner())
+        .await
+        .map(Json)
+}
+
+#[get("/accounts/<id>/balance/check")]
+async fn check_balance(id: &str) -> Result<Json<BalanceCheck>, LedgerError> {
+    service::check_balance(id).await.map(Json)
+}
+
+#[get("/balances/check")]
+async fn check_all() -> Result<Json<Vec<BalanceMismatch>>, LedgerError> {
+    service::check_all_balances().await.map(Json)
+}
+
+pub fn balance_routes() -> Vec<Route> {
+    routes![
+        get_balance,
+        get_daily_balances,
+        set_overdraft,
+        check_balance,
+        check_all
+    ]
+}
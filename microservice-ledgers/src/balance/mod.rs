@@ -0,0 +1,4 @@
+pub mod balance;
+pub mod controller;
+pub mod persistence;
+pub mod service;
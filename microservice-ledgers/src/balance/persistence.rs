@@ -0,0 +1,280 @@
+use crate::balance::balance::{AccountBalance, BalanceMismatch, DailyMovement};
+use crate::configuration::db::connect_to_db;
+use crate::hot::persistence::find_bucket_totals;
+use crate::utils::business_day::BUSINESS_TZ_NAME;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use tokio_postgres::{Error, Row, Transaction};
+use uuid::Uuid;
+
+const BALANCE_FIELDS: &str =
+    "account_id, balance, held_amount, overdraft_limit, entries_count, version, last_entry_at, \
+     last_entry_hash, updated_at";
+
+fn balance_from_row(row: &Row) -> AccountBalance {
+    let balance: Decimal = row.get(1);
+    let held_amount: Decimal = row.get(2);
+
+    AccountBalance {
+        account_id: row.get(0),
+        balance,
+        held_amount,
+        available_balance: balance - held_amount,
+        overdraft_limit: row.get(3),
+        entries_count: row.get(4),
+        version: row.get(5),
+        last_entry_at: row.get(6),
+        last_entry_hash: row.get(7),
+        updated_at: row.get(8),
+    }
+}
+
+/// Creates the missing projection rows and locks all of them, always in
+/// account_id order so concurrent postings cannot deadlock each other.
+pub async fn lock_balances(
+    tx: &Transaction<'_>,
+    account_ids: &[Uuid],
+) -> Result<Vec<AccountBalance>, Error> {
+    tx.execute(
+        "INSERT INTO account_balances (account_id)
+             SELECT UNNEST($1::uuid[]) ON CONFLICT (account_id) DO NOTHING",
+        &[&account_ids],
+    )
+    .await?;
+
+    let rows = tx
+        .query(
+            &format!(
+                "SELECT {} FROM account_balances WHERE account_id = ANY($1)
+                  ORDER BY account_id FOR UPDATE",
+                BALANCE_FIELDS
+            ),
+            &[&account_ids],
+        )
+        .await?;
+
+    Ok(rows.iter().map(balance_from_row).collect())
+}
+
+/// Persists the balance row and queues a balance-changed event for the customers service.
+pub async fn update_balance(tx: &Transaction<'_>, balance: &AccountBalance) -> Result<(), Error> {
+    tx.execute(
+        "UPDATE account_balances
+            SET balance = $2, held_amount = $3, entries_count = $4, version = $5,
+                last_entry_at = $6, last_entry_hash = $7, updated_at = NOW()
+          WHERE account_id = $1",
+        &[
+            &balance.account_id,
+            &balance.balance,
+            &balance.held_amount,
+            &balance.entries_count,
+            &balance.version,
+            &balance.last_entry_at,
+            &balance.last_entry_hash,
+        ],
+    )
+    .await?;
+
+    // Same transaction as the update, so the event goes out if and only if it commits.
+    tx.execute(
+        "INSERT INTO balance_outbox (account_id, ledger_balance, available_balance, version)
+             VALUES ($1, $2, $3, $4)",
+        &[
+            &balance.account_id,
+            &balance.balance,
+            &balance.available_balance,
+            &balance.version,
+        ],
+    )
+    .await?;
+
+    Ok(())
+}
+
+pub async fn update_overdraft_limit(
+    tx: &Transaction<'_>,
+    account_id: Uuid,
+    limit: Decimal,
+    approved_by: &str,
+) -> Result<(), Error> {
+    tx.execute(
+        "UPDATE account_balances
+            SET overdraft_limit = $2, overdraft_approved_by = $3, overdraft_approved_at = NOW(),
+                updated_at = NOW()
+          WHERE account_id = $1",
+        &[&account_id, &limit, &approved_by],
+    )
+    .await?;
+
+    Ok(())
+}
+
+pub async fn insert_snapshot(
+    tx: &Transaction<'_>,
+    balance: &AccountBalance,
+    taken_at: DateTime<Utc>,
+) -> Result<(), Error> {
+    tx.execute(
+        "INSERT INTO balance_snapshots (account_id, balance, entries_count, taken_at,
+                                        account_seq, entry_hash)
+             VALUES ($1, $2, $3, $4, $5, $6)",
+        &[
+            &balance.account_id,
+            &balance.balance,
+            &balance.entries_count,
+            &taken_at,
+            &balance.entries_count,
+            &balance.last_entry_hash,
+        ],
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// The balance row plus any hot-account credits not folded into it yet.
+pub async fn find_balance(account_id: Uuid) -> Result<Option<AccountBalance>, Error> {
+    let client = connect_to_db().await?;
+    let row = client
+        .query_opt(
+            &format!(
+                "SELECT {} FROM account_balances WHERE account_id = $1",
+                BALANCE_FIELDS
+            ),
+            &[&account_id],
+        )
+        .await?;
+
+    let Some(mut balance) = row.as_ref().map(balance_from_row) else {
+        return Ok(None);
+    };
+
+    let (unfolded, unfolded_entries) = find_bucket_totals(&client, account_id).await?;
+    balance.include_unfolded(unfolded, unfolded_entries);
+
+    Ok(Some(balance))
+}
+
+/// Nearest snapshot taken at or before `at`, plus the entries posted after it.
+pub async fn find_balance_as_of(account_id: Uuid, at: DateTime<Utc>) -> Result<Decimal, Error> {
+    let client = connect_to_db().await?;
+    let row = client
+        .query_one(
+            "WITH snap AS (
+                 SELECT balance, taken_at FROM balance_snapshots
+                  WHERE account_id = $1 AND taken_at <= $2
+                  ORDER BY taken_at DESC LIMIT 1
+             )
+             SELECT COALESCE((SELECT balance FROM snap), 0)
+                  + COALESCE((
+                        SELECT SUM(CASE WHEN entry_type = 'CREDIT' THEN amount ELSE -amount END)
+                          FROM ledger_entries
+                         WHERE account_id = $1
+                           AND created_at <= $2
+                           AND created_at > COALESCE((SELECT taken_at FROM snap), '-infinity')
+                    ), 0)",
+            &[&account_id, &at],
+        )
+        .await?;
+
+    Ok(row.get(0))
+}
+
+/// Credits and debits per business day for entries in `[from, to)`.
+pub async fn find_daily_movements(
+    account_id: Uuid,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+) -> Result<Vec<DailyMovement>, Error> {
+    let client = connect_to_db().await?;
+    let rows = client
+        .query(
+            "SELECT (created_at AT TIME ZONE $4)::date AS day,
+                    COALESCE(SUM(amount) FILTER (WHERE entry_type = 'CREDIT'), 0),
+                    COALESCE(SUM(amount) FILTER (WHERE entry_type = 'DEBIT'), 0)
+               FROM ledger_entries
+              WHERE account_id = $1 AND created_at >= $2 AND created_at < $3
+              GROUP BY day ORDER BY day",
+            &[&account_id, &from, &to, &BUSINESS_TZ_NAME],
+        )
+        .await?;
+
+    Ok(rows
+        .iter()
+        .map(|row| DailyMovement {
+            date: row.get(0),
+            credits: row.get(1),
+            debits: row.get(2),
+        })
+        .collect())
+}
+
+/// Sum of every entry of the account. Entries in archived partitions are
+/// counted through the snapshot written when they were archived.
+pub async fn sum_entries(account_id: Uuid) -> Result<Decimal, Error> {
+    let client = connect_to_db().await?;
+    let row = client
+        .query_one(
+            "WITH base AS (
+                 SELECT balance, taken_at FROM balance_snapshots
+                  WHERE account_id = $1
+                    AND taken_at < (SELECT MAX(range_end) FROM ledger_partition_archives)
+                  ORDER BY taken_at DESC LIMIT 1
+             )
+             SELECT COALESCE((SELECT balance FROM base), 0)
+                  + COALESCE((
+                        SELECT SUM(CASE WHEN entry_type = 'CREDIT' THEN amount ELSE -amount END)
+                          FROM ledger_entries
+                         WHERE account_id = $1
+                           AND created_at > COALESCE((SELECT taken_at FROM base), '-infinity')
+                    ), 0)",
+            &[&account_id],
+        )
+        .await?;
+
+    Ok(row.get(0))
+}
+
+/// Accounts whose projected balance differs from the full sum of their entries,
+/// archived ones included through their snapshots.
+pub async fn find_inconsistent_balances() -> Result<Vec<BalanceMismatch>, Error> {
+    let client = connect_to_db().await?;
+    let rows = client
+        .query(
+            "WITH base AS (
+                 SELECT DISTINCT ON (account_id) account_id, balance, taken_at
+                   FROM balance_snapshots
+                  WHERE taken_at < (SELECT MAX(range_end) FROM ledger_partition_archives)
+                  ORDER BY account_id, taken_at DESC
+             ),
+             totals AS (
+                 SELECT b.account_id,
+                        b.balance + COALESCE((
+                            SELECT SUM(k.balance) FROM account_balance_buckets k
+                             WHERE k.account_id = b.account_id
+                        ), 0) AS projected,
+                        COALESCE(base.balance, 0) + COALESCE(e.total, 0) AS total
+                   FROM account_balances b
+                   LEFT JOIN base ON base.account_id = b.account_id
+                   LEFT JOIN LATERAL (
+                        SELECT SUM(CASE WHEN entry_type = 'CREDIT' THEN amount ELSE -amount END)
+                                   AS total
+                          FROM ledger_entries le
+                         WHERE le.account_id = b.account_id
+                           AND le.created_at > COALESCE(base.taken_at, '-infinity')
+                   ) e ON TRUE
+             )
+             SELECT account_id, projected, total FROM totals WHERE projected <> total",
+            &[],
+        )
+        .await?;
+
+    Ok(rows
+        .iter()
+        .map(|row| BalanceMismatch {
+            account_id: row.get(0),
+            projected_balance: row.get(1),
+            entries_sum: row.get(2),
+        })
+        .collect())
+}
@@ -0,0 +1,243 @@
+use crate::balance::{
+    balance::{
+        AccountBalance, BalanceCheck, BalanceMismatch, DailyBalance, DailyMovement,
+        HistoricalBalance, OverdraftInput,
+    },
+    persistence::{
+        find_balance, find_balance_as_of, find_daily_movements, find_inconsistent_balances,
+        insert_snapshot, lock_balances, sum_entries, update_balance, update_overdraft_limit,
+    },
+};
+use crate::configuration::db::connect_to_db;
+use crate::ledger::ledger::LedgerEntry;
+use crate::partition::service::ensure_not_archived;
+use crate::utils::business_day::{day_start, parse_as_of, parse_date};
+use crate::utils::error::{parse_uuid, LedgerError};
+use chrono::{Duration, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+use tokio_postgres::Transaction;
+use uuid::Uuid;
+
+/// Longest range served by the daily balance series.
+pub const MAX_DAILY_SERIES_DAYS: i64 = 366;
+
+/// A snapshot is written every time an account crosses a multiple of this many entries.
+pub const SNAPSHOT_EVERY_ENTRIES: i64 = 1000;
+
+/// Applies freshly inserted entries to the locked projection rows. Must run in
+/// the same transaction as the inserts so the projection never drifts.
+pub async fn apply_entries(
+    tx: &Transaction<'_>,
+    balances: &mut [AccountBalance],
+    entries: &[LedgerEntry],
+) -> Result<(), tokio_postgres::Error> {
+    let mut by_account: HashMap<Uuid, Vec<&LedgerEntry>> = HashMap::new();
+    for entry in entries {
+        by_account.entry(entry.account_id).or_default().push(entry);
+    }
+
+    for balance in balances.iter_mut() {
+        let Some(account_entries) = by_account.get(&balance.account_id) else {
+            continue;
+        };
+
+        let previous_count = advance_balance(balance, account_entries.iter().copied());
+        persist_balance(tx, balance, previous_count).await?;
+    }
+
+    Ok(())
+}
+
+/// Moves the balance past `entries`, which must be the account's, in chain
+/// order. Returns the entries count before them.
+pub fn advance_balance<'a>(
+    balance: &mut AccountBalance,
+    entries: impl IntoIterator<Item = &'a LedgerEntry>,
+) -> i64 {
+    let previous_count = balance.entries_count;
+    for entry in entries {
+        balance.apply(entry.entry_type.signed(entry.amount));
+        balance.last_entry_at = Some(entry.created_at);
+        balance.last_entry_hash = entry.entry_hash.clone();
+        balance.entries_count += 1;
+    }
+    balance.version += 1;
+
+    previous_count
+}
+
+/// Writes an advanced balance row, which also queues its balance-changed event,
+/// plus a snapshot when it crossed a boundary.
+pub async fn persist_balance(
+    tx: &Transaction<'_>,
+    balance: &AccountBalance,
+    previous_count: i64,
+) -> Result<(), tokio_postgres::Error> {
+    update_balance(tx, balance).await?;
+
+    if crosses_snapshot_boundary(previous_count, balance.entries_count) {
+        if let Some(taken_at) = balance.last_entry_at {
+            insert_snapshot(tx, balance, taken_at).await?;
+        }
+    }
+
+    Ok(())
+}
+
+pub fn crosses_snapshot_boundary(previous_count: i64, new_count: i64) -> bool {
+    previous_count / SNAPSHOT_EVERY_ENTRIES != new_count / SNAPSHOT_EVERY_ENTRIES
+}
+
+pub async fn get_balance(input_uuid: &str) -> Result<AccountBalance, LedgerError> {
+    let account_id = parse_uuid(input_uuid)?;
+
+    Ok(find_balance(account_id)
+        .await?
+        .unwrap_or_else(|| empty_balance(account_id)))
+}
+
+pub async fn get_balance_as_of(
+    input_uuid: &str,
+    as_of: &str,
+) -> Result<HistoricalBalance, LedgerError> {
+    let account_id = parse_uuid(input_uuid)?;
+    let as_of = parse_as_of(as_of)?;
+    ensure_not_archived(as_of).await?;
+
+    Ok(HistoricalBalance {
+        account_id,
+        balance: find_balance_as_of(account_id, as_of).await?,
+        as_of,
+    })
+}
+
+/// Opening and closing balance for every Brasília business day in `[from, to]`.
+pub async fn get_daily_balances(
+    input_uuid: &str,
+    from: &str,
+    to: &str,
+) -> Result<Vec<DailyBalance>, LedgerError> {
+    let account_id = parse_uuid(input_uuid)?;
+    let from = parse_date(from)?;
+    let to = parse_date(to)?;
+
+    if to < from {
+        return Err(LedgerError::Invalid(
+            "'to' must not be before 'from'".to_string(),
+        ));
+    }
+
+    if (to - from).num_days() >= MAX_DAILY_SERIES_DAYS {
+        return Err(LedgerError::Invalid(format!(
+            "The series is limited to {} days",
+            MAX_DAILY_SERIES_DAYS
+        )));
+    }
+
+    let range_start = day_start(from);
+    ensure_not_archived(range_start).await?;
+    let opening = find_balance_as_of(account_id, range_start - Duration::microseconds(1)).await?;
+    let movements =
+        find_daily_movements(account_id, range_start, day_start(to + Duration::days(1))).await?;
+
+    Ok(build_daily_series(opening, from, to, &movements))
+}
+
+pub fn build_daily_series(
+    opening: Decimal,
+    from: NaiveDate,
+    to: NaiveDate,
+    movements: &[DailyMovement],
+) -> Vec<DailyBalance> {
+    let mut series = Vec::new();
+    let mut balance = opening;
+
+    for date in from.iter_days().take_while(|d| *d <= to) {
+        let (credits, debits) = movements
+            .iter()
+            .find(|m| m.date == date)
+            .map(|m| (m.credits, m.debits))
+            .unwrap_or((Decimal::ZERO, Decimal::ZERO));
+
+        let closing = balance + credits - debits;
+        series.push(DailyBalance {
+            date,
+            opening_balance: balance,
+            credits,
+            debits,
+            closing_balance: closing,
+        });
+        balance = closing;
+    }
+
+    series
+}
+
+/// Approves (or, with a zero limit, revokes) the overdraft line of an account.
+pub async fn set_overdraft_limit(
+    input_uuid: &str,
+    input: OverdraftInput,
+) -> Result<AccountBalance, LedgerError> {
+    let account_id = parse_uuid(input_uuid)?;
+
+    if input.limit < Decimal::ZERO || input.limit.scale() > 4 {
+        return Err(LedgerError::Invalid(
+            "The overdraft limit must be zero or positive with at most 4 decimal places"
+                .to_string(),
+        ));
+    }
+
+    if input.approved_by.trim().is_empty() {
+        return Err(LedgerError::Invalid("approved_by is required".to_string()));
+    }
+
+    let mut client = connect_to_db().await?;
+    let tx = client.transaction().await?;
+
+    lock_balances(&tx, &[account_id]).await?;
+    update_overdraft_limit(&tx, account_id, input.limit, input.approved_by.trim()).await?;
+
+    tx.commit().await?;
+
+    get_balance(input_uuid).await
+}
+
+pub async fn check_balance(input_uuid: &str) -> Result<BalanceCheck, LedgerError> {
+    let account_id = parse_uuid(input_uuid)?;
+
+    let projected_balance = find_balance(account_id)
+        .await?
+        .map(|b| b.balance)
+        .unwrap_or(Decimal::ZERO);
+    let entries_sum = sum_entries(account_id).await?;
+    let snapshot_balance = find_balance_as_of(account_id, Utc::now()).await?;
+
+    Ok(BalanceCheck {
+        account_id,
+        projected_balance,
+        entries_sum,
+        snapshot_balance,
+        consistent: projected_balance == entries_sum && snapshot_balance == entries_sum,
+    })
+}
+
+pub async fn check_all_balances() -> Result<Vec<BalanceMismatch>, LedgerError> {
+    Ok(find_inconsistent_balances().await?)
+}
+
+fn empty_balance(account_id: Uuid) -> AccountBalance {
+    let now = Utc::now();
+    AccountBalance {
+        account_id,
+        balance: Decimal::ZERO,
+        held_amount: Decimal::ZERO,
+        available_balance: Decimal::ZERO,
+        overdraft_limit: Decimal::ZERO,
+        entries_count: 0,
+        version: 0,
+        last_entry_at: None,
+        last_entry_hash: None,
+        updated_at: now,
+    }
+}
@@ -0,0 +1,117 @@
+use crate::ledger::ledger::EntryType;
+use bytes::BytesMut;
+use chrono::{DateTime, Utc};
+use postgres_types::{to_sql_checked, FromSql, IsNull, ToSql, Type};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use uuid::Uuid;
+
+/// Code of the trial balance line that aggregates every customer account.
+pub const CUSTOMER_ACCOUNTS_CODE: &str = "CUSTOMER_ACCOUNTS";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AccountClass {
+    ASSET,
+    LIABILITY,
+    EQUITY,
+    REVENUE,
+    EXPENSE,
+}
+
+impl AccountClass {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AccountClass::ASSET => "ASSET",
+            AccountClass::LIABILITY => "LIABILITY",
+            AccountClass::EQUITY => "EQUITY",
+            AccountClass::REVENUE => "REVENUE",
+            AccountClass::EXPENSE => "EXPENSE",
+        }
+    }
+
+    /// The side that increases accounts of this class. Contra accounts
+    /// override it when they are created.
+    pub fn normal_balance(&self) -> EntryType {
+        match self {
+            AccountClass::ASSET | AccountClass::EXPENSE => EntryType::DEBIT,
+            AccountClass::LIABILITY | AccountClass::EQUITY | AccountClass::REVENUE => {
+                EntryType::CREDIT
+            }
+        }
+    }
+}
+
+// Stored as VARCHAR guarded by a CHECK constraint.
+impl ToSql for AccountClass {
+    fn to_sql(
+        &self,
+        ty: &Type,
+        out: &mut BytesMut,
+    ) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        self.as_str().to_sql(ty, out)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        <&str as ToSql>::accepts(ty)
+    }
+
+    to_sql_checked!();
+}
+
+impl<'a> FromSql<'a> for AccountClass {
+    fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        match <&str as FromSql>::from_sql(ty, raw)? {
+            "ASSET" => Ok(AccountClass::ASSET),
+            "LIABILITY" => Ok(AccountClass::LIABILITY),
+            "EQUITY" => Ok(AccountClass::EQUITY),
+            "REVENUE" => Ok(AccountClass::REVENUE),
+            "EXPENSE" => Ok(AccountClass::EXPENSE),
+            other => Err(format!("Unknown account_class: {}", other).into()),
+        }
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        <&str as FromSql>::accepts(ty)
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LedgerAccount {
+    pub id: Uuid,
+    pub code: String,
+    pub name: String,
+    pub account_class: AccountClass,
+    pub normal_balance: EntryType,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct LedgerAccountInput {
+    pub code: String,
+    pub name: String,
+    pub account_class: AccountClass,
+    pub normal_balance: Option<EntryType>,
+}
+
+/// One row of the trial balance. A balance sits in the debit or the credit
+/// column depending on its sign, whatever the account's normal side is.
+#[derive(Debug, Clone, Serialize)]
+pub struct TrialBalanceLine {
+    pub code: String,
+    pub name: String,
+    pub account_class: AccountClass,
+    pub normal_balance: EntryType,
+    pub accounts: i64,
+    pub debit: Decimal,
+    pub credit: Decimal,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TrialBalance {
+    pub as_of: DateTime<Utc>,
+    pub lines: Vec<TrialBalanceLine>,
+    pub total_debits: Decimal,
+    pub total_credits: Decimal,
+    pub balanced: bool,
+}
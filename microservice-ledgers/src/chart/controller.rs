@@ -0,0 +1,31 @@
+use crate::chart::chart::{LedgerAccount, LedgerAccountInput, TrialBalance};
+use crate::chart::service;
+use crate::utils::error::LedgerError;
+use rocket::{get, post, response::status, routes, serde::json::Json, Route};
+
+#[post("/ledger-accounts", format = "json", data = "<input>")]
+async fn create(
+    input: Json<LedgerAccountInput>,
+) -> Result<status::Created<Json<LedgerAccount>>, LedgerError> {
+    let account = service::create_account(input.into_inner()).await?;
+    Ok(status::Created::new(format!("/ledger-accounts/{}", account.code)).body(Json(account)))
+}
+
+#[get("/ledger-accounts")]
+async fn list() -> Result<Json<Vec<LedgerAccount>>, LedgerError> {
+    service::list_accounts().await.map(Json)
+}
+
+#[get("/ledger-accounts/<code>")]
+async fn find_one(code: &str) -> Result<Json<LedgerAccount>, LedgerError> {
+    service::get_account(code).await.map(Json)
+}
+
+#[get("/trial-balance")]
+async fn trial_balance() -> Result<Json<TrialBalance>, LedgerError> {
+    service::trial_balance().await.map(Json)
+}
+
+pub fn chart_routes() -> Vec<Route> {
+    routes![create, list, find_one, trial_balance]
+}
@@ -0,0 +1,4 @@
+pub mod chart;
+pub mod controller;
+pub mod persistence;
+pub mod service;
@@ -0,0 +1,143 @@
+use crate::chart::chart::{AccountClass, LedgerAccount, TrialBalanceLine};
+use crate::configuration::db::connect_to_db;
+use crate::ledger::ledger::EntryType;
+use rust_decimal::Decimal;
+use tokio_postgres::{Error, Row, Transaction};
+use uuid::Uuid;
+
+const LEDGER_ACCOUNT_FIELDS: &str = "id, code, name, account_class, normal_balance, created_at";
+
+fn ledger_account_from_row(row: &Row) -> LedgerAccount {
+    LedgerAccount {
+        id: row.get(0),
+        code: row.get(1),
+        name: row.get(2),
+        account_class: row.get(3),
+        normal_balance: row.get(4),
+        created_at: row.get(5),
+    }
+}
+
+/// Returns None when the code is already taken.
+pub async fn insert_ledger_account(
+    code: &str,
+    name: &str,
+    account_class: AccountClass,
+    normal_balance: EntryType,
+) -> Result<Option<LedgerAccount>, Error> {
+    let client = connect_to_db().await?;
+    let row = client
+        .query_opt(
+            &format!(
+                "INSERT INTO ledger_accounts (code, name, account_class, normal_balance)
+                     VALUES ($1, $2, $3, $4)
+                 ON CONFLICT (code) DO NOTHING RETURNING {}",
+                LEDGER_ACCOUNT_FIELDS
+            ),
+            &[&code, &name, &account_class, &normal_balance],
+        )
+        .await?;
+
+    Ok(row.as_ref().map(ledger_account_from_row))
+}
+
+pub async fn find_all() -> Result<Vec<LedgerAccount>, Error> {
+    let client = connect_to_db().await?;
+    let rows = client
+        .query(
+            &format!(
+                "SELECT {} FROM ledger_accounts ORDER BY code",
+                LEDGER_ACCOUNT_FIELDS
+            ),
+            &[],
+        )
+        .await?;
+
+    Ok(rows.iter().map(ledger_account_from_row).collect())
+}
+
+pub async fn find_by_code(code: &str) -> Result<Option<LedgerAccount>, Error> {
+    let client = connect_to_db().await?;
+    let row = client
+        .query_opt(
+            &format!(
+                "SELECT {} FROM ledger_accounts WHERE code = $1",
+                LEDGER_ACCOUNT_FIELDS
+            ),
+            &[&code],
+        )
+        .await?;
+
+    Ok(row.as_ref().map(ledger_account_from_row))
+}
+
+pub async fn find_ids_by_codes(codes: &[String]) -> Result<Vec<(String, Uuid)>, Error> {
+    let client = connect_to_db().await?;
+    let rows = client
+        .query(
+            "SELECT code, id FROM ledger_accounts WHERE code = ANY($1)",
+            &[&codes],
+        )
+        .await?;
+
+    Ok(rows.iter().map(|row| (row.get(0), row.get(1))).collect())
+}
+
+/// The subset of `account_ids` that are internal accounts.
+pub async fn find_internal_ids(
+    tx: &Transaction<'_>,
+    account_ids: &[Uuid],
+) -> Result<Vec<Uuid>, Error> {
+    let rows = tx
+        .query(
+            "SELECT id FROM ledger_accounts WHERE id = ANY($1)",
+            &[&account_ids],
+        )
+        .await?;
+
+    Ok(rows.iter().map(|row| row.get(0)).collect())
+}
+
+/// One line per internal account, from the projected balances.
+pub async fn find_internal_lines(tx: &Transaction<'_>) -> Result<Vec<TrialBalanceLine>, Error> {
+    let rows = tx
+        .query(
+            "SELECT la.code, la.name, la.account_class, la.normal_balance,
+                    COALESCE(GREATEST(-ab.balance, 0), 0), COALESCE(GREATEST(ab.balance, 0), 0)
+               FROM ledger_accounts la
+               LEFT JOIN account_balances ab ON ab.account_id = la.id
+              ORDER BY la.code",
+            &[],
+        )
+        .await?;
+
+    Ok(rows
+        .iter()
+        .map(|row| TrialBalanceLine {
+            code: row.get(0),
+            name: row.get(1),
+            account_class: row.get(2),
+            normal_balance: row.get(3),
+            accounts: 1,
+            debit: row.get(4),
+            credit: row.get(5),
+        })
+        .collect())
+}
+
+/// Customer accounts summed per column: overdrawn accounts add to the debit
+/// side, accounts in credit to the credit side.
+pub async fn find_customer_totals(tx: &Transaction<'_>) -> Result<(i64, Decimal, Decimal), Error> {
+    let row = tx
+        .query_one(
+            "SELECT COUNT(*),
+                    COALESCE(SUM(GREATEST(-ab.balance, 0)), 0),
+                    COALESCE(SUM(GREATEST(ab.balance, 0)), 0)
+               FROM account_balances ab
+              WHERE NOT EXISTS (SELECT 1 FROM ledger_accounts la WHERE la.id = ab.account_id)",
+            &[],
+        )
+        .await?;
+
+    Ok((row.get(0), row.get(1), row.get(2)))
+}
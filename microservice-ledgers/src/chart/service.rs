@@ -0,0 +1,108 @@
+use crate::chart::{
+    chart::{
+        AccountClass, LedgerAccount, LedgerAccountInput, TrialBalance, TrialBalanceLine,
+        CUSTOMER_ACCOUNTS_CODE,
+    },
+    persistence::{
+        find_all, find_by_code, find_customer_totals, find_internal_lines, insert_ledger_account,
+    },
+};
+use crate::configuration::db::connect_to_db;
+use crate::ledger::ledger::EntryType;
+use crate::utils::error::LedgerError;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use tokio_postgres::IsolationLevel;
+
+pub const MAX_CODE_LENGTH: usize = 64;
+
+/// Codes are referenced from postings, so they are kept to A-Z, 0-9 and '_'.
+pub fn validate_code(code: &str) -> Result<(), LedgerError> {
+    let valid = !code.is_empty()
+        && code.len() <= MAX_CODE_LENGTH
+        && code
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_');
+
+    if !valid || code == CUSTOMER_ACCOUNTS_CODE {
+        return Err(LedgerError::Invalid(format!(
+            "Invalid account code '{}': use up to {} characters from A-Z, 0-9 and _",
+            code, MAX_CODE_LENGTH
+        )));
+    }
+
+    Ok(())
+}
+
+pub async fn create_account(input: LedgerAccountInput) -> Result<LedgerAccount, LedgerError> {
+    validate_code(&input.code)?;
+
+    let name = input.name.trim();
+    if name.is_empty() {
+        return Err(LedgerError::Invalid("Account name is required".to_string()));
+    }
+
+    let normal_balance = input
+        .normal_balance
+        .unwrap_or_else(|| input.account_class.normal_balance());
+
+    insert_ledger_account(&input.code, name, input.account_class, normal_balance)
+        .await?
+        .ok_or_else(|| LedgerError::Conflict(format!("Account code {} already exists", input.code)))
+}
+
+pub async fn list_accounts() -> Result<Vec<LedgerAccount>, LedgerError> {
+    Ok(find_all().await?)
+}
+
+pub async fn get_account(code: &str) -> Result<LedgerAccount, LedgerError> {
+    find_by_code(code)
+        .await?
+        .ok_or_else(|| LedgerError::NotFound(format!("Ledger account {} not found", code)))
+}
+
+/// Every posting is balanced, so total debits equal total credits unless a
+/// balance projection drifted from its entries.
+pub fn build_trial_balance(as_of: DateTime<Utc>, lines: Vec<TrialBalanceLine>) -> TrialBalance {
+    let total_debits: Decimal = lines.iter().map(|l| l.debit).sum();
+    let total_credits: Decimal = lines.iter().map(|l| l.credit).sum();
+
+    TrialBalance {
+        as_of,
+        lines,
+        total_debits,
+        total_credits,
+        balanced: total_debits == total_credits,
+    }
+}
+
+pub fn customer_line(accounts: i64, debit: Decimal, credit: Decimal) -> TrialBalanceLine {
+    TrialBalanceLine {
+        code: CUSTOMER_ACCOUNTS_CODE.to_string(),
+        name: "Customer accounts".to_string(),
+        account_class: AccountClass::LIABILITY,
+        normal_balance: EntryType::CREDIT,
+        accounts,
+        debit,
+        credit,
+    }
+}
+
+/// Trial balance over the current balances, read from one snapshot.
+pub async fn trial_balance() -> Result<TrialBalance, LedgerError> {
+    let mut client = connect_to_db().await?;
+    let tx = client
+        .build_transaction()
+        .isolation_level(IsolationLevel::RepeatableRead)
+        .read_only(true)
+        .start()
+        .await?;
+
+    let mut lines = find_internal_lines(&tx).await?;
+    let (accounts, debit, credit) = find_customer_totals(&tx).await?;
+    lines.push(customer_line(accounts, debit, credit));
+
+    tx.commit().await?;
+
+    Ok(build_trial_balance(Utc::now(), lines))
+}
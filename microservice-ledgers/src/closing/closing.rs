@@ -0,0 +1,74 @@
+use crate::chart::chart::AccountClass;
+use chrono::{DateTime, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+pub const DAY_OPEN: &str = "OPEN";
+pub const DAY_CLOSED: &str = "CLOSED";
+pub const DAY_REOPENED: &str = "REOPENED";
+
+pub const ACTION_CLOSE: &str = "CLOSE";
+pub const ACTION_REOPEN: &str = "REOPEN";
+
+/// Actor recorded for closes made by the scheduler.
+pub const SYSTEM_ACTOR: &str = "system";
+
+#[derive(Debug, Serialize)]
+pub struct BusinessDay {
+    pub business_date: NaiveDate,
+    pub status: String,
+    pub closed_at: Option<DateTime<Utc>>,
+    pub closed_by: Option<String>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BusinessDayEvent {
+    pub id: Uuid,
+    pub action: String,
+    pub actor: String,
+    pub reason: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Debits and credits posted on a day to accounts of one class.
+#[derive(Debug, Clone, Serialize)]
+pub struct ClassTotals {
+    pub account_class: AccountClass,
+    pub debits: Decimal,
+    pub credits: Decimal,
+    pub entries_count: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DayClosing {
+    pub id: Uuid,
+    pub business_date: NaiveDate,
+    pub total_debits: Decimal,
+    pub total_credits: Decimal,
+    pub entries_count: i64,
+    pub balanced: bool,
+    pub closed_by: String,
+    pub closed_at: DateTime<Utc>,
+    pub lines: Vec<ClassTotals>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BusinessDayReport {
+    #[serde(flatten)]
+    pub day: BusinessDay,
+    pub last_closing: Option<DayClosing>,
+    pub events: Vec<BusinessDayEvent>,
+}
+
+#[derive(Deserialize)]
+pub struct CloseInput {
+    pub closed_by: String,
+}
+
+#[derive(Deserialize)]
+pub struct ReopenInput {
+    pub reopened_by: String,
+    pub reason: String,
+}
@@ -0,0 +1,28 @@
+use crate::closing::closing::{
+    BusinessDay, BusinessDayReport, CloseInput, DayClosing, ReopenInput,
+};
+use crate::closing::service;
+use crate::utils::business_day::parse_date;
+use crate::utils::error::LedgerError;
+use rocket::{get, post, routes, serde::json::Json, Route};
+
+#[post("/business-days/<date>/close", format = "json", data = "<input>")]
+async fn close(date: &str, input: Json<CloseInput>) -> Result<Json<DayClosing>, LedgerError> {
+    let date = parse_date(date)?;
+    service::close(date, &input.closed_by).await.map(Json)
+}
+
+#[post("/business-days/<date>/reopen", format = "json", data = "<input>")]
+async fn reopen(date: &str, input: Json<ReopenInput>) -> Result<Json<BusinessDay>, LedgerError> {
+    let date = parse_date(date)?;
+    service::reopen(date, input.into_inner()).await.map(Json)
+}
+
+#[get("/business-days/<date>")]
+async fn find_one(date: &str) -> Result<Json<BusinessDayReport>, LedgerError> {
+    service::get_day(date).await.map(Json)
+}
+
+pub fn closing_routes() -> Vec<Route> {
+    routes![close, reopen, find_one]
+}
@@ -0,0 +1,4 @@
+pub mod closing;
+pub mod controller;
+pub mod persistence;
+pub mod service;
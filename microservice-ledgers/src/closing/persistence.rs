@@ -0,0 +1,294 @@
+use crate::closing::closing::{BusinessDay, BusinessDayEvent, ClassTotals, DayClosing};
+use crate::configuration::db::connect_to_db;
+use chrono::{Datelike, NaiveDate};
+use tokio_postgres::{Client, Error, Row, Transaction};
+use uuid::Uuid;
+
+const DAY_FIELDS: &str = "business_date, status, closed_at, closed_by, updated_at";
+
+const CLOSING_FIELDS: &str = "id, business_date, total_debits, total_credits, entries_count, \
+     balanced, closed_by, closed_at";
+
+fn day_from_row(row: &Row) -> BusinessDay {
+    BusinessDay {
+        business_date: row.get(0),
+        status: row.get(1),
+        closed_at: row.get(2),
+        closed_by: row.get(3),
+        updated_at: row.get(4),
+    }
+}
+
+pub async fn ensure_day(tx: &Transaction<'_>, date: NaiveDate) -> Result<(), Error> {
+    tx.execute(
+        "INSERT INTO business_days (business_date) VALUES ($1) ON CONFLICT DO NOTHING",
+        &[&date],
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Namespace of the advisory locks that guard a business day.
+const DAY_LOCK_NAMESPACE: i64 = 0x4441_5900;
+
+fn day_lock_key(date: NaiveDate) -> i64 {
+    (DAY_LOCK_NAMESPACE << 32) | i64::from(date.num_days_from_ce())
+}
+
+/// Shared advisory lock on the day, held by postings until they commit. Unlike
+/// a row lock it writes nothing, so concurrent postings do not contend on it.
+pub async fn share_day_lock(tx: &Transaction<'_>, date: NaiveDate) -> Result<(), Error> {
+    tx.execute("SELECT pg_advisory_xact_lock_shared($1)", &[&day_lock_key(date)])
+        .await?;
+
+    Ok(())
+}
+
+/// Exclusive counterpart taken by the close: waits for in-flight postings
+/// and holds back new ones until the close commits.
+pub async fn exclusive_day_lock(tx: &Transaction<'_>, date: NaiveDate) -> Result<(), Error> {
+    tx.execute("SELECT pg_advisory_xact_lock($1)", &[&day_lock_key(date)])
+        .await?;
+
+    Ok(())
+}
+
+pub async fn find_day_status(
+    tx: &Transaction<'_>,
+    date: NaiveDate,
+) -> Result<Option<String>, Error> {
+    let row = tx
+        .query_opt(
+            "SELECT status FROM business_days WHERE business_date = $1",
+            &[&date],
+        )
+        .await?;
+
+    Ok(row.map(|row| row.get(0)))
+}
+
+pub async fn lock_day(tx: &Transaction<'_>, date: NaiveDate) -> Result<Option<BusinessDay>, Error> {
+    let row = tx
+        .query_opt(
+            &format!(
+                "SELECT {} FROM business_days WHERE business_date = $1 FOR UPDATE",
+                DAY_FIELDS
+            ),
+            &[&date],
+        )
+        .await?;
+
+    Ok(row.as_ref().map(day_from_row))
+}
+
+pub async fn find_day(date: NaiveDate) -> Result<Option<BusinessDay>, Error> {
+    let client = connect_to_db().await?;
+    let row = client
+        .query_opt(
+            &format!(
+                "SELECT {} FROM business_days WHERE business_date = $1",
+                DAY_FIELDS
+            ),
+            &[&date],
+        )
+        .await?;
+
+    Ok(row.as_ref().map(day_from_row))
+}
+
+/// Past days the scheduler still has to close. Reopened days are left to the admin.
+pub async fn find_open_days_before(
+    client: &Client,
+    date: NaiveDate,
+) -> Result<Vec<NaiveDate>, Error> {
+    let rows = client
+        .query(
+            "SELECT business_date FROM business_days
+              WHERE status = 'OPEN' AND business_date < $1
+              ORDER BY business_date",
+            &[&date],
+        )
+        .await?;
+
+    Ok(rows.iter().map(|row| row.get(0)).collect())
+}
+
+pub async fn mark_closed(
+    tx: &Transaction<'_>,
+    date: NaiveDate,
+    closed_by: &str,
+) -> Result<(), Error> {
+    tx.execute(
+        "UPDATE business_days
+            SET status = 'CLOSED', closed_at = NOW(), closed_by = $2, updated_at = NOW()
+          WHERE business_date = $1",
+        &[&date, &closed_by],
+    )
+    .await?;
+
+    Ok(())
+}
+
+pub async fn mark_reopened(tx: &Transaction<'_>, date: NaiveDate) -> Result<(), Error> {
+    tx.execute(
+        "UPDATE business_days SET status = 'REOPENED', updated_at = NOW()
+          WHERE business_date = $1",
+        &[&date],
+    )
+    .await?;
+
+    Ok(())
+}
+
+pub async fn insert_day_event(
+    tx: &Transaction<'_>,
+    date: NaiveDate,
+    action: &str,
+    actor: &str,
+    reason: Option<&str>,
+) -> Result<(), Error> {
+    tx.execute(
+        "INSERT INTO business_day_events (business_date, action, actor, reason)
+             VALUES ($1, $2, $3, $4)",
+        &[&date, &action, &actor, &reason],
+    )
+    .await?;
+
+    Ok(())
+}
+
+pub async fn find_events(date: NaiveDate) -> Result<Vec<BusinessDayEvent>, Error> {
+    let client = connect_to_db().await?;
+    let rows = client
+        .query(
+            "SELECT id, action, actor, reason, created_at FROM business_day_events
+              WHERE business_date = $1 ORDER BY created_at",
+            &[&date],
+        )
+        .await?;
+
+    Ok(rows
+        .iter()
+        .map(|row| BusinessDayEvent {
+            id: row.get(0),
+            action: row.get(1),
+            actor: row.get(2),
+            reason: row.get(3),
+            created_at: row.get(4),
+        })
+        .collect())
+}
+
+/// Entries of the day per account class. Accounts outside the chart of
+/// accounts are customer accounts, which are liabilities.
+pub async fn find_class_totals(
+    tx: &Transaction<'_>,
+    date: NaiveDate,
+) -> Result<Vec<ClassTotals>, Error> {
+    let rows = tx
+        .query(
+            "SELECT COALESCE(la.account_class, 'LIABILITY'),
+                    COALESCE(SUM(le.amount) FILTER (WHERE le.entry_type = 'DEBIT'), 0),
+                    COALESCE(SUM(le.amount) FILTER (WHERE le.entry_type = 'CREDIT'), 0),
+                    COUNT(*)
+               FROM ledger_entries le
+               LEFT JOIN ledger_accounts la ON la.id = le.account_id
+              WHERE le.business_date = $1
+              GROUP BY 1 ORDER BY 1",
+            &[&date],
+        )
+        .await?;
+
+    Ok(rows
+        .iter()
+        .map(|row| ClassTotals {
+            account_class: row.get(0),
+            debits: row.get(1),
+            credits: row.get(2),
+            entries_count: row.get(3),
+        })
+        .collect())
+}
+
+pub async fn insert_closing(tx: &Transaction<'_>, closing: &DayClosing) -> Result<(), Error> {
+    tx.execute(
+        "INSERT INTO day_closings (id, business_date, total_debits, total_credits, entries_count,
+                                   balanced, closed_by, closed_at)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8)",
+        &[
+            &closing.id,
+            &closing.business_date,
+            &closing.total_debits,
+            &closing.total_credits,
+            &closing.entries_count,
+            &closing.balanced,
+            &closing.closed_by,
+            &closing.closed_at,
+        ],
+    )
+    .await?;
+
+    for line in &closing.lines {
+        tx.execute(
+            "INSERT INTO day_closing_lines (closing_id, account_class, debits, credits, entries_count)
+                 VALUES ($1, $2, $3, $4, $5)",
+            &[
+                &closing.id,
+                &line.account_class,
+                &line.debits,
+                &line.credits,
+                &line.entries_count,
+            ],
+        )
+        .await?;
+    }
+
+    Ok(())
+}
+
+pub async fn find_last_closing(date: NaiveDate) -> Result<Option<DayClosing>, Error> {
+    let client = connect_to_db().await?;
+    let Some(row) = client
+        .query_opt(
+            &format!(
+                "SELECT {} FROM day_closings WHERE business_date = $1
+                  ORDER BY closed_at DESC LIMIT 1",
+                CLOSING_FIELDS
+            ),
+            &[&date],
+        )
+        .await?
+    else {
+        return Ok(None);
+    };
+
+    let id: Uuid = row.get(0);
+    let lines = client
+        .query(
+            "SELECT account_class, debits, credits, entries_count FROM day_closing_lines
+              WHERE closing_id = $1 ORDER BY account_class",
+            &[&id],
+        )
+        .await?
+        .iter()
+        .map(|line| ClassTotals {
+            account_class: line.get(0),
+            debits: line.get(1),
+            credits: line.get(2),
+            entries_count: line.get(3),
+        })
+        .collect();
+
+    Ok(Some(DayClosing {
+        id,
+        business_date: row.get(1),
+        total_debits: row.get(2),
+        total_credits: row.get(3),
+        entries_count: row.get(4),
+        balanced: row.get(5),
+        closed_by: row.get(6),
+        closed_at: row.get(7),
+        lines,
+    }))
+}
@@ -0,0 +1,211 @@
+use crate::closing::{
+    closing::{
+        BusinessDay, BusinessDayReport, ClassTotals, DayClosing, ReopenInput, ACTION_CLOSE,
+        ACTION_REOPEN, DAY_CLOSED, SYSTEM_ACTOR,
+    },
+    persistence::{
+        ensure_day, exclusive_day_lock, find_class_totals, find_day, find_day_status, find_events,
+        find_last_closing, find_open_days_before, insert_closing, insert_day_event, lock_day,
+        mark_closed, mark_reopened, share_day_lock,
+    },
+};
+use crate::configuration::db::connect_to_db;
+use crate::utils::business_day::{business_date, parse_date};
+use crate::utils::error::LedgerError;
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use std::sync::atomic::{AtomicI32, Ordering};
+use tokio_postgres::Transaction;
+use uuid::Uuid;
+
+/// How often the scheduler looks for past days that are still open.
+pub const CLOSE_JOB_INTERVAL_SECS: u64 = 15 * 60;
+
+/// Day last seen open by this process, as days from the common era (0 = none).
+static OPEN_DAY: AtomicI32 = AtomicI32::new(0);
+
+/// Called by every posting inside its transaction, for the business day of its
+/// created_at: postings are always booked on the current day, since
+/// statements, daily balances and `as_of` place entries by created_at. This
+/// only guards the race with a close of the day that just ended. The shared lock makes a
+/// concurrent close wait until the posting commits. A day can only be closed
+/// once it has ended, so while it is still today the cached status is reused
+/// and the business_days row is left alone.
+pub async fn ensure_day_open(tx: &Transaction<'_>, date: NaiveDate) -> Result<(), LedgerError> {
+    share_day_lock(tx, date).await?;
+
+    let days = date.num_days_from_ce();
+    if OPEN_DAY.load(Ordering::Relaxed) == days && business_date(Utc::now()) == date {
+        return Ok(());
+    }
+
+    let status = match find_day_status(tx, date).await? {
+        Some(status) => status,
+        None => {
+            ensure_day(tx, date).await?;
+            find_day_status(tx, date).await?.unwrap_or_default()
+        }
+    };
+
+    if status == DAY_CLOSED {
+        return Err(LedgerError::Conflict(format!(
+            "Business day {} is closed",
+            date
+        )));
+    }
+
+    OPEN_DAY.store(days, Ordering::Relaxed);
+    Ok(())
+}
+
+pub fn build_closing(
+    business_date: NaiveDate,
+    lines: Vec<ClassTotals>,
+    closed_by: &str,
+    closed_at: DateTime<Utc>,
+) -> DayClosing {
+    let total_debits: Decimal = lines.iter().map(|l| l.debits).sum();
+    let total_credits: Decimal = lines.iter().map(|l| l.credits).sum();
+
+    DayClosing {
+        id: Uuid::new_v4(),
+        business_date,
+        total_debits,
+        total_credits,
+        entries_count: lines.iter().map(|l| l.entries_count).sum(),
+        balanced: total_debits == total_credits,
+        closed_by: closed_by.to_string(),
+        closed_at,
+        lines,
+    }
+}
+
+/// Freezes a past business day and writes its trial balance. Closing a day
+/// that is already closed returns its last report.
+pub async fn close(date: NaiveDate, closed_by: &str) -> Result<DayClosing, LedgerError> {
+    let closed_by = closed_by.trim();
+    if closed_by.is_empty() {
+        return Err(LedgerError::Invalid("closed_by is required".to_string()));
+    }
+
+    if date >= business_date(Utc::now()) {
+        return Err(LedgerError::Invalid(format!(
+            "Business day {} has not ended yet",
+            date
+        )));
+    }
+
+    let mut client = connect_to_db().await?;
+    let tx = client.transaction().await?;
+
+    ensure_day(&tx, date).await?;
+    exclusive_day_lock(&tx, date).await?;
+    let day = lock_day(&tx, date)
+        .await?
+        .ok_or_else(|| LedgerError::NotFound(format!("Business day {} not found", date)))?;
+
+    if day.status == DAY_CLOSED {
+        drop(tx);
+        return find_last_closing(date).await?.ok_or_else(|| {
+            LedgerError::NotFound(format!("No closing report for business day {}", date))
+        });
+    }
+
+    let lines = find_class_totals(&tx, date).await?;
+    let closing = build_closing(date, lines, closed_by, Utc::now());
+
+    insert_closing(&tx, &closing).await?;
+    mark_closed(&tx, date, closed_by).await?;
+    insert_day_event(&tx, date, ACTION_CLOSE, closed_by, None).await?;
+
+    tx.commit().await?;
+
+    if !closing.balanced {
+        eprintln!(
+            "Business day {} closed unbalanced: debits {} credits {}",
+            date, closing.total_debits, closing.total_credits
+        );
+    }
+
+    Ok(closing)
+}
+
+/// Reopens a closed day so it can be closed again with a new trial balance.
+/// It only affects reporting: postings are never booked on a past day, so a
+/// correction to it is posted on the current day. The reason is kept in the
+/// audit trail.
+pub async fn reopen(date: NaiveDate, input: ReopenInput) -> Result<BusinessDay, LedgerError> {
+    let reopened_by = input.reopened_by.trim();
+    let reason = input.reason.trim();
+    if reopened_by.is_empty() || reason.is_empty() {
+        return Err(LedgerError::Invalid(
+            "reopened_by and reason are required".to_string(),
+        ));
+    }
+
+    let mut client = connect_to_db().await?;
+    let tx = client.transaction().await?;
+
+    let day = lock_day(&tx, date)
+        .await?
+        .ok_or_else(|| LedgerError::NotFound(format!("Business day {} not found", date)))?;
+
+    if day.status != DAY_CLOSED {
+        return Err(LedgerError::Conflict(format!(
+            "Business day {} is not closed",
+            date
+        )));
+    }
+
+    mark_reopened(&tx, date).await?;
+    insert_day_event(&tx, date, ACTION_REOPEN, reopened_by, Some(reason)).await?;
+
+    tx.commit().await?;
+
+    find_day(date)
+        .await?
+        .ok_or_else(|| LedgerError::NotFound(format!("Business day {} not found", date)))
+}
+
+/// Closes yesterday and any earlier day left open, oldest first.
+pub async fn close_due() -> Result<(), LedgerError> {
+    let today = business_date(Utc::now());
+
+    let mut client = connect_to_db().await?;
+    let tx = client.transaction().await?;
+    ensure_day(&tx, today - Duration::days(1)).await?;
+    tx.commit().await?;
+
+    for date in find_open_days_before(&client, today).await? {
+        close(date, SYSTEM_ACTOR).await?;
+    }
+
+    Ok(())
+}
+
+pub async fn run_scheduler() {
+    let mut interval =
+        tokio::time::interval(std::time::Duration::from_secs(CLOSE_JOB_INTERVAL_SECS));
+
+    loop {
+        interval.tick().await;
+
+        if let Err(e) = close_due().await {
+            eprintln!("End-of-day close failed: {}", e);
+        }
+    }
+}
+
+pub async fn get_day(date: &str) -> Result<BusinessDayReport, LedgerError> {
+    let date = parse_date(date)?;
+
+    let day = find_day(date)
+        .await?
+        .ok_or_else(|| LedgerError::NotFound(format!("Business day {} not found", date)))?;
+
+    Ok(BusinessDayReport {
+        day,
+        last_closing: find_last_closing(date).await?,
+        events: find_events(date).await?,
+    })
+}
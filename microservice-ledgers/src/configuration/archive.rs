@@ -0,0 +1,4 @@
+/// Where archived ledger partitions are written.
+pub fn archive_dir() -> String {
+    std::env::var("LEDGER_ARCHIVE_DIR").unwrap_or_else(|_| "./archive".to_string())
+}
@@ -0,0 +1,3 @@
+pub fn kafka_brokers() -> String {
+    std::env::var("KAFKA_BROKERS").unwrap_or_else(|_| "localhost:29092".to_string())
+}
@@ -37,7 +37,8 @@ pub async fn run_migrations(client: &Client) -> Result<(), Box<dyn std::error::E
     let migration_files = fs::read_dir("./migrations")?;
 
     let mut entries: Vec<_> = migration_files.collect::<Result<_, _>>()?;
-    // entries.reverse();
+    // read_dir has no guaranteed order; migrations must run by file name
+    entries.sort_by_key(|entry| entry.file_name());
 
     for entry in entries {
         let path = entry.path();
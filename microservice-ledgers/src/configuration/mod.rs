@@ -1,2 +1,5 @@
+pub mod archive;
 pub mod db;
+pub mod kafka;
 pub mod migrations;
+pub mod transfers_db;
@@ -0,0 +1,23 @@
+use tokio_postgres::{Client, NoTls};
+
+/// Transfers are spread over three Postgres shards (see infra/docker-compose.yml).
+pub const TRANSFER_SHARD_PORTS: [u16; 3] = [5461, 5462, 5463];
+
+pub async fn connect_to_transfer_shard(port: u16) -> Result<Client, tokio_postgres::Error> {
+    let (client, connection) = tokio_postgres::Config::new()
+        .host("localhost")
+        .port(port)
+        .user("postgres")
+        .password("postgres")
+        .dbname("transfers")
+        .connect(NoTls)
+        .await?;
+
+    tokio::spawn(async move {
+        if let Err(e) = connection.await {
+            eprintln!("Connection error: {}", e);
+        }
+    });
+
+    Ok(client)
+}
@@ -0,0 +1,75 @@
+use crate::configuration::kafka::kafka_brokers;
+use crate::event::{event::EventOrigin, service::process};
+use rdkafka::{
+    config::ClientConfig,
+    consumer::{CommitMode, Consumer, StreamConsumer},
+    Message,
+};
+use std::time::Duration;
+
+pub const TRANSFERS_TOPIC: &str = "transfers";
+pub const ACCOUNTS_TOPIC: &str = "accounts";
+pub const CONSUMER_GROUP: &str = "microservice-ledgers";
+
+/// Pause before retrying a message that failed for a transient reason.
+pub const RETRY_DELAY_SECS: u64 = 5;
+
+/// Consumes transfer and account events and applies them to the ledger. Offsets are
+/// committed only after a message was applied or dead-lettered, so a crash
+/// redelivers it and the processed_events key makes that harmless.
+pub async fn run_consumer() {
+    let consumer: StreamConsumer = match ClientConfig::new()
+        .set("bootstrap.servers", kafka_brokers())
+        .set("group.id", CONSUMER_GROUP)
+        .set("enable.auto.commit", "false")
+        .set("auto.offset.reset", "earliest")
+        .create()
+    {
+        Ok(consumer) => consumer,
+        Err(e) => {
+            eprintln!("Failed to create transfer event consumer: {}", e);
+            return;
+        }
+    };
+
+    if let Err(e) = consumer.subscribe(&[TRANSFERS_TOPIC, ACCOUNTS_TOPIC]) {
+        eprintln!(
+            "Failed to subscribe to {} and {}: {}",
+            TRANSFERS_TOPIC, ACCOUNTS_TOPIC, e
+        );
+        return;
+    }
+
+    loop {
+        let message = match consumer.recv().await {
+            Ok(message) => message,
+            Err(e) => {
+                eprintln!("Transfer event consumer error: {}", e);
+                tokio::time::sleep(Duration::from_secs(RETRY_DELAY_SECS)).await;
+                continue;
+            }
+        };
+
+        let payload = String::from_utf8_lossy(message.payload().unwrap_or_default()).into_owned();
+        let origin = EventOrigin {
+            topic: message.topic().to_string(),
+            partition: message.partition(),
+            offset: message.offset(),
+            key: message
+                .key()
+                .map(|k| String::from_utf8_lossy(k).into_owned()),
+        };
+
+        while let Err(e) = process(&payload, &origin).await {
+            eprintln!(
+                "Retrying {}[{}]@{}: {}",
+                origin.topic, origin.partition, origin.offset, e
+            );
+            tokio::time::sleep(Duration::from_secs(RETRY_DELAY_SECS)).await;
+        }
+
+        if let Err(e) = consumer.commit_message(&message, CommitMode::Async) {
+            eprintln!("Failed to commit offset {}: {}", origin.offset, e);
+        }
+    }
+}
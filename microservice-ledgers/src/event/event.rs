@@ -0,0 +1,89 @@
+use crate::ledger::ledger::{EntryInput, EntryType, PostingInput};
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TransferEventType {
+    CREATED,
+    POSTED,
+    REVERSED,
+}
+
+impl TransferEventType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TransferEventType::CREATED => "CREATED",
+            TransferEventType::POSTED => "POSTED",
+            TransferEventType::REVERSED => "REVERSED",
+        }
+    }
+}
+
+/// Payload published by microservice-transfers on the `transfers` topic.
+#[derive(Debug, Clone, Deserialize)]
+pub struct TransferEvent {
+    pub event_type: TransferEventType,
+    pub transfer_id: Uuid,
+    pub debit_account_id: Uuid,
+    pub credit_account_id: Uuid,
+    pub amount: Decimal,
+}
+
+impl TransferEvent {
+    /// Moves the amount from the payer to the receiver.
+    pub fn transfer_posting(&self) -> PostingInput {
+        self.posting(self.debit_account_id, self.credit_account_id)
+    }
+
+    /// Gives the amount back to the payer.
+    pub fn reversal_posting(&self) -> PostingInput {
+        self.posting(self.credit_account_id, self.debit_account_id)
+    }
+
+    fn posting(&self, from: Uuid, to: Uuid) -> PostingInput {
+        PostingInput {
+            transfer_id: self.transfer_id,
+            entries: vec![
+                EntryInput {
+                    account_id: from,
+                    entry_type: EntryType::DEBIT,
+                    amount: self.amount,
+                },
+                EntryInput {
+                    account_id: to,
+                    entry_type: EntryType::CREDIT,
+                    amount: self.amount,
+                },
+            ],
+        }
+    }
+}
+
+/// Payload published by microservice-customers on the `accounts` topic when
+/// a customer's accounts are blocked.
+#[derive(Debug, Clone, Deserialize)]
+pub struct AccountBlockedEvent {
+    pub account_id: Uuid,
+    pub blocked_at: DateTime<Utc>,
+}
+
+/// Where a message came from on the bus, recorded with dead letters.
+#[derive(Debug, Clone)]
+pub struct EventOrigin {
+    pub topic: String,
+    pub partition: i32,
+    pub offset: i64,
+    pub key: Option<String>,
+}
+
+/// Payload published on the `balances` topic whenever an account balance changes.
+#[derive(Debug, Clone, Serialize)]
+pub struct BalanceChangedEvent {
+    pub account_id: Uuid,
+    pub ledger_balance: Decimal,
+    pub available_balance: Decimal,
+    pub version: i64,
+    pub changed_at: DateTime<Utc>,
+}
@@ -0,0 +1,5 @@
+pub mod consumer;
+pub mod event;
+pub mod persistence;
+pub mod publisher;
+pub mod service;
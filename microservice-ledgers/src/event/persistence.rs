@@ -0,0 +1,122 @@
+use crate::event::event::{AccountBlockedEvent, BalanceChangedEvent, EventOrigin, TransferEvent};
+use tokio_postgres::{Client, Error, Transaction};
+use uuid::Uuid;
+
+/// Claims the (transfer, event type) pair. Returns false when it was already
+/// applied; a concurrent delivery blocks on the primary key until this
+/// transaction ends and then sees the conflict.
+pub async fn mark_processed(
+    tx: &Transaction<'_>,
+    transfer_id: Uuid,
+    event_type: &str,
+) -> Result<bool, Error> {
+    let inserted = tx
+        .execute(
+            "INSERT INTO processed_events (transfer_id, event_type) VALUES ($1, $2)
+             ON CONFLICT DO NOTHING",
+            &[&transfer_id, &event_type],
+        )
+        .await?;
+
+    Ok(inserted == 1)
+}
+
+pub async fn is_processed(
+    tx: &Transaction<'_>,
+    transfer_id: Uuid,
+    event_type: &str,
+) -> Result<bool, Error> {
+    let row = tx
+        .query_one(
+            "SELECT EXISTS (SELECT 1 FROM processed_events
+                             WHERE transfer_id = $1 AND event_type = $2)",
+            &[&transfer_id, &event_type],
+        )
+        .await?;
+
+    Ok(row.get(0))
+}
+
+/// Redeliveries of the same block keep the first blocked_at.
+pub async fn insert_blocked_account(
+    client: &Client,
+    event: &AccountBlockedEvent,
+) -> Result<(), Error> {
+    client
+        .execute(
+            "INSERT INTO blocked_accounts (account_id, blocked_at) VALUES ($1, $2)
+             ON CONFLICT DO NOTHING",
+            &[&event.account_id, &event.blocked_at],
+        )
+        .await?;
+
+    Ok(())
+}
+
+pub async fn insert_dead_letter(
+    client: &Client,
+    origin: &EventOrigin,
+    payload: &str,
+    event: Option<&TransferEvent>,
+    error: &str,
+) -> Result<(), Error> {
+    let transfer_id = event.map(|e| e.transfer_id);
+    let event_type = event.map(|e| e.event_type.as_str());
+
+    client
+        .execute(
+            "INSERT INTO dead_letter_events (topic, kafka_partition, kafka_offset, event_key,
+                                             payload, transfer_id, event_type, error)
+                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8)",
+            &[
+                &origin.topic,
+                &origin.partition,
+                &origin.offset,
+                &origin.key,
+                &payload,
+                &transfer_id,
+                &event_type,
+                &error,
+            ],
+        )
+        .await?;
+
+    Ok(())
+}
+
+/// Oldest unpublished balance changes, locked so concurrent publishers skip them.
+pub async fn lock_outbox_batch(
+    tx: &Transaction<'_>,
+    limit: i64,
+) -> Result<Vec<(i64, BalanceChangedEvent)>, Error> {
+    let rows = tx
+        .query(
+            "SELECT id, account_id, ledger_balance, available_balance, version, created_at
+               FROM balance_outbox ORDER BY id LIMIT $1 FOR UPDATE SKIP LOCKED",
+            &[&limit],
+        )
+        .await?;
+
+    Ok(rows
+        .iter()
+        .map(|row| {
+            (
+                row.get(0),
+                BalanceChangedEvent {
+                    account_id: row.get(1),
+                    ledger_balance: row.get(2),
+                    available_balance: row.get(3),
+                    version: row.get(4),
+                    changed_at: row.get(5),
+                },
+            )
+        })
+        .collect())
+}
+
+pub async fn delete_outbox(tx: &Transaction<'_>, ids: &[i64]) -> Result<(), Error> {
+    tx.execute("DELETE FROM balance_outbox WHERE id = ANY($1)", &[&ids])
+        .await?;
+
+    Ok(())
+}
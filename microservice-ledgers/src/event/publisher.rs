@@ -0,0 +1,83 @@
+use crate::configuration::db::connect_to_db;
+use crate::configuration::kafka::kafka_brokers;
+use crate::event::persistence::{delete_outbox, lock_outbox_batch};
+use crate::utils::error::LedgerError;
+use rdkafka::{
+    config::ClientConfig,
+    producer::{FutureProducer, FutureRecord},
+    util::Timeout,
+};
+use std::time::Duration;
+
+pub const BALANCES_TOPIC: &str = "balances";
+
+/// Outbox rows sent per transaction.
+pub const OUTBOX_BATCH_SIZE: i64 = 500;
+
+/// How often the publisher drains the outbox.
+pub const OUTBOX_POLL_INTERVAL_MILLIS: u64 = 500;
+
+pub const PUBLISH_TIMEOUT_SECS: u64 = 5;
+
+/// Sends every pending balance change and returns how many were sent. A batch
+/// is only deleted after the broker acknowledged all of it; a failure resends
+/// the batch, which consumers absorb through the version check.
+pub async fn publish_pending(producer: &FutureProducer) -> Result<usize, LedgerError> {
+    let mut client = connect_to_db().await?;
+    let mut published = 0;
+
+    loop {
+        let tx = client.transaction().await?;
+        let batch = lock_outbox_batch(&tx, OUTBOX_BATCH_SIZE).await?;
+
+        if batch.is_empty() {
+            break;
+        }
+
+        for (_, event) in &batch {
+            let key = event.account_id.to_string();
+            let payload =
+                serde_json::to_string(event).map_err(|e| LedgerError::Messaging(e.to_string()))?;
+
+            producer
+                .send(
+                    FutureRecord::to(BALANCES_TOPIC).key(&key).payload(&payload),
+                    Timeout::After(Duration::from_secs(PUBLISH_TIMEOUT_SECS)),
+                )
+                .await
+                .map_err(|(e, _)| LedgerError::Messaging(e.to_string()))?;
+        }
+
+        let ids: Vec<i64> = batch.iter().map(|(id, _)| *id).collect();
+        delete_outbox(&tx, &ids).await?;
+        tx.commit().await?;
+
+        published += batch.len();
+    }
+
+    Ok(published)
+}
+
+pub async fn run_balance_publisher() {
+    let producer: FutureProducer = match ClientConfig::new()
+        .set("bootstrap.servers", kafka_brokers())
+        .set("message.timeout.ms", "5000")
+        .create()
+    {
+        Ok(producer) => producer,
+        Err(e) => {
+            eprintln!("Failed to create balance event producer: {}", e);
+            return;
+        }
+    };
+
+    let mut interval = tokio::time::interval(Duration::from_millis(OUTBOX_POLL_INTERVAL_MILLIS));
+
+    loop {
+        interval.tick().await;
+
+        if let Err(e) = publish_pending(&producer).await {
+            eprintln!("Balance publisher failed: {}", e);
+        }
+    }
+}
@@ -0,0 +1,188 @@
+use crate::configuration::db::connect_to_db;
+use crate::event::{
+    consumer::ACCOUNTS_TOPIC,
+    event::{AccountBlockedEvent, EventOrigin, TransferEvent, TransferEventType},
+    persistence::{insert_blocked_account, insert_dead_letter, is_processed, mark_processed},
+};
+use crate::hold::{
+    hold::{Hold, HoldInput, HOLD_ACTIVE, HOLD_RELEASED},
+    persistence::lock_by_transfer_id,
+    service::{capture_locked, place_in, release_locked, validate_hold},
+};
+use crate::ledger::{
+    persistence::has_entries,
+    service::{post_entries, validate_posting},
+};
+use crate::utils::error::LedgerError;
+use tokio_postgres::{error::SqlState, Transaction};
+
+/// Applies one delivery from the bus. Only transient failures are returned, so
+/// the caller retries the same message; anything that would fail again is
+/// parked in dead_letter_events and the message counts as consumed.
+pub async fn process(payload: &str, origin: &EventOrigin) -> Result<(), LedgerError> {
+    if origin.topic == ACCOUNTS_TOPIC {
+        return process_account_event(payload, origin).await;
+    }
+
+    let event = match serde_json::from_str::<TransferEvent>(payload) {
+        Ok(event) => event,
+        Err(e) => {
+            return dead_letter(origin, payload, None, &format!("Invalid event: {}", e)).await
+        }
+    };
+
+    match apply(&event).await {
+        Ok(_) => Ok(()),
+        Err(e) if is_transient(&e) => Err(e),
+        Err(e) => dead_letter(origin, payload, Some(&event), &e.to_string()).await,
+    }
+}
+
+/// Records a blocked account; from then on the ledger refuses to move its money.
+async fn process_account_event(payload: &str, origin: &EventOrigin) -> Result<(), LedgerError> {
+    let event = match serde_json::from_str::<AccountBlockedEvent>(payload) {
+        Ok(event) => event,
+        Err(e) => {
+            return dead_letter(origin, payload, None, &format!("Invalid event: {}", e)).await
+        }
+    };
+
+    let client = connect_to_db().await?;
+    insert_blocked_account(&client, &event).await?;
+
+    Ok(())
+}
+
+/// Connection problems, deadlocks and serialization failures go away on retry;
+/// errors reported by Postgres for the statement itself do not.
+pub fn is_transient(error: &LedgerError) -> bool {
+    match error {
+        LedgerError::Database(e) => match e.code() {
+            None => true,
+            Some(code) => {
+                *code == SqlState::T_R_SERIALIZATION_FAILURE
+                    || *code == SqlState::T_R_DEADLOCK_DETECTED
+            }
+        },
+        _ => false,
+    }
+}
+
+async fn dead_letter(
+    origin: &EventOrigin,
+    payload: &str,
+    event: Option<&TransferEvent>,
+    error: &str,
+) -> Result<(), LedgerError> {
+    eprintln!(
+        "Dead-lettering {}[{}]@{}: {}",
+        origin.topic, origin.partition, origin.offset, error
+    );
+
+    let client = connect_to_db().await?;
+    insert_dead_letter(&client, origin, payload, event, error).await?;
+
+    Ok(())
+}
+
+/// Applies the event exactly once. Returns false when it had already been applied.
+pub async fn apply(event: &TransferEvent) -> Result<bool, LedgerError> {
+    let mut client = connect_to_db().await?;
+    let tx = client.transaction().await?;
+
+    if !mark_processed(&tx, event.transfer_id, event.event_type.as_str()).await? {
+        return Ok(false);
+    }
+
+    match event.event_type {
+        TransferEventType::CREATED => apply_created(&tx, event).await?,
+        TransferEventType::POSTED => apply_posted(&tx, event).await?,
+        TransferEventType::REVERSED => apply_reversed(&tx, event).await?,
+    }
+
+    tx.commit().await?;
+
+    Ok(true)
+}
+
+/// Reserves the amount on the payer while the transfer is pending.
+async fn apply_created(tx: &Transaction<'_>, event: &TransferEvent) -> Result<(), LedgerError> {
+    // A late CREATED must not hold funds for a transfer that already settled.
+    if is_processed(tx, event.transfer_id, TransferEventType::POSTED.as_str()).await?
+        || is_processed(tx, event.transfer_id, TransferEventType::REVERSED.as_str()).await?
+    {
+        return Ok(());
+    }
+
+    let input = HoldInput {
+        account_id: event.debit_account_id,
+        transfer_id: event.transfer_id,
+        amount: event.amount,
+        expires_in_seconds: None,
+    };
+    let ttl = validate_hold(&input)?;
+    place_in(tx, &input, ttl).await?;
+
+    Ok(())
+}
+
+/// Captures the hold placed on CREATED, or posts directly when there is none.
+async fn apply_posted(tx: &Transaction<'_>, event: &TransferEvent) -> Result<(), LedgerError> {
+    if is_processed(tx, event.transfer_id, TransferEventType::REVERSED.as_str()).await? {
+        return Ok(());
+    }
+
+    if let Some(hold) = lock_active_hold(tx, event).await? {
+        if hold.account_id != event.debit_account_id || hold.amount != event.amount {
+            return Err(LedgerError::Conflict(format!(
+                "Hold {} does not match posted transfer {}",
+                hold.id, event.transfer_id
+            )));
+        }
+        capture_locked(tx, &hold, event.credit_account_id).await?;
+        return Ok(());
+    }
+
+    if has_entries(tx, event.transfer_id).await? {
+        return Err(LedgerError::Conflict(format!(
+            "Transfer {} is already posted",
+            event.transfer_id
+        )));
+    }
+
+    let posting = event.transfer_posting();
+    validate_posting(&posting)?;
+    post_entries(tx, &posting, None).await?;
+
+    Ok(())
+}
+
+/// Undoes whatever the ledger holds for the transfer: a pending transfer only
+/// loses its hold, a posted one gets the opposite entries.
+async fn apply_reversed(tx: &Transaction<'_>, event: &TransferEvent) -> Result<(), LedgerError> {
+    if let Some(hold) = lock_active_hold(tx, event).await? {
+        release_locked(tx, &hold, HOLD_RELEASED).await?;
+        return Ok(());
+    }
+
+    if !has_entries(tx, event.transfer_id).await? {
+        return Err(LedgerError::Conflict(format!(
+            "Transfer {} has no ledger entries to reverse",
+            event.transfer_id
+        )));
+    }
+
+    let posting = event.reversal_posting();
+    validate_posting(&posting)?;
+    post_entries(tx, &posting, None).await?;
+
+    Ok(())
+}
+
+async fn lock_active_hold(
+    tx: &Transaction<'_>,
+    event: &TransferEvent,
+) -> Result<Option<Hold>, LedgerError> {
+    let hold = lock_by_transfer_id(tx, event.transfer_id).await?;
+    Ok(hold.filter(|h| h.status == HOLD_ACTIVE))
+}
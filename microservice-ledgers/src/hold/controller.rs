@@ -0,0 +1,38 @@
+use crate::hold::hold::{CaptureInput, Hold, HoldInput};
+use crate::hold::service;
+use crate::ledger::ledger::LedgerEntry;
+use crate::utils::error::LedgerError;
+use rocket::{get, post, response::status, routes, serde::json::Json, Route};
+
+#[post("/holds", format = "json", data = "<input>")]
+async fn place(input: Json<HoldInput>) -> Result<status::Created<Json<Hold>>, LedgerError> {
+    let hold = service::place(input.into_inner()).await?;
+    Ok(status::Created::new(format!("/holds/{}", hold.id)).body(Json(hold)))
+}
+
+#[get("/holds/<id>")]
+async fn find_one(id: &str) -> Result<Json<Hold>, LedgerError> {
+    service::get_hold(id).await.map(Json)
+}
+
+#[post("/holds/<id>/capture", format = "json", data = "<input>")]
+async fn capture(
+    id: &str,
+    input: Json<CaptureInput>,
+) -> Result<Json<Vec<LedgerEntry>>, LedgerError> {
+    service::capture(id, input.into_inner()).await.map(Json)
+}
+
+#[post("/holds/<id>/release")]
+async fn release(id: &str) -> Result<Json<Hold>, LedgerError> {
+    service::release(id).await.map(Json)
+}
+
+#[get("/accounts/<id>/holds")]
+async fn list_active(id: &str) -> Result<Json<Vec<Hold>>, LedgerError> {
+    service::list_active(id).await.map(Json)
+}
+
+pub fn hold_routes() -> Vec<Route> {
+    routes![place, find_one, capture, release, list_active]
+}
@@ -0,0 +1,34 @@
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+pub const HOLD_ACTIVE: &str = "ACTIVE";
+pub const HOLD_CAPTURED: &str = "CAPTURED";
+pub const HOLD_RELEASED: &str = "RELEASED";
+pub const HOLD_EXPIRED: &str = "EXPIRED";
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Hold {
+    pub id: Uuid,
+    pub account_id: Uuid,
+    pub transfer_id: Uuid,
+    pub amount: Decimal,
+    pub status: String,
+    pub expires_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub resolved_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct HoldInput {
+    pub account_id: Uuid,
+    pub transfer_id: Uuid,
+    pub amount: Decimal,
+    pub expires_in_seconds: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CaptureInput {
+    pub credit_account_id: Uuid,
+}
@@ -0,0 +1,4 @@
+pub mod controller;
+pub mod hold;
+pub mod persistence;
+pub mod service;
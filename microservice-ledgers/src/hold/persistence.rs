@@ -0,0 +1,139 @@
+use crate::configuration::db::connect_to_db;
+use crate::hold::hold::Hold;
+use chrono::{DateTime, Utc};
+use tokio_postgres::{Error, Row, Transaction};
+use uuid::Uuid;
+
+const HOLD_FIELDS: &str =
+    "id, account_id, transfer_id, amount, status, expires_at, created_at, resolved_at";
+
+fn hold_from_row(row: &Row) -> Hold {
+    Hold {
+        id: row.get(0),
+        account_id: row.get(1),
+        transfer_id: row.get(2),
+        amount: row.get(3),
+        status: row.get(4),
+        expires_at: row.get(5),
+        created_at: row.get(6),
+        resolved_at: row.get(7),
+    }
+}
+
+pub async fn insert_hold(tx: &Transaction<'_>, hold: &Hold) -> Result<(), Error> {
+    tx.execute(
+        "INSERT INTO holds (id, account_id, transfer_id, amount, status, expires_at, created_at)
+             VALUES ($1, $2, $3, $4, $5, $6, $7)",
+        &[
+            &hold.id,
+            &hold.account_id,
+            &hold.transfer_id,
+            &hold.amount,
+            &hold.status,
+            &hold.expires_at,
+            &hold.created_at,
+        ],
+    )
+    .await?;
+
+    Ok(())
+}
+
+pub async fn find_by_transfer_id(
+    tx: &Transaction<'_>,
+    transfer_id: Uuid,
+) -> Result<Option<Hold>, Error> {
+    let row = tx
+        .query_opt(
+            &format!("SELECT {} FROM holds WHERE transfer_id = $1", HOLD_FIELDS),
+            &[&transfer_id],
+        )
+        .await?;
+
+    Ok(row.as_ref().map(hold_from_row))
+}
+
+pub async fn lock_hold(tx: &Transaction<'_>, id: Uuid) -> Result<Option<Hold>, Error> {
+    let row = tx
+        .query_opt(
+            &format!("SELECT {} FROM holds WHERE id = $1 FOR UPDATE", HOLD_FIELDS),
+            &[&id],
+        )
+        .await?;
+
+    Ok(row.as_ref().map(hold_from_row))
+}
+
+pub async fn lock_by_transfer_id(
+    tx: &Transaction<'_>,
+    transfer_id: Uuid,
+) -> Result<Option<Hold>, Error> {
+    let row = tx
+        .query_opt(
+            &format!(
+                "SELECT {} FROM holds WHERE transfer_id = $1 FOR UPDATE",
+                HOLD_FIELDS
+            ),
+            &[&transfer_id],
+        )
+        .await?;
+
+    Ok(row.as_ref().map(hold_from_row))
+}
+
+/// Locks the next expired active hold, skipping the ones another worker is resolving.
+pub async fn lock_next_expired(
+    tx: &Transaction<'_>,
+    now: DateTime<Utc>,
+) -> Result<Option<Hold>, Error> {
+    let row = tx
+        .query_opt(
+            &format!(
+                "SELECT {} FROM holds WHERE status = 'ACTIVE' AND expires_at <= $1
+                  ORDER BY expires_at LIMIT 1 FOR UPDATE SKIP LOCKED",
+                HOLD_FIELDS
+            ),
+            &[&now],
+        )
+        .await?;
+
+    Ok(row.as_ref().map(hold_from_row))
+}
+
+pub async fn resolve_hold(tx: &Transaction<'_>, id: Uuid, status: &str) -> Result<(), Error> {
+    tx.execute(
+        "UPDATE holds SET status = $2, resolved_at = NOW() WHERE id = $1",
+        &[&id, &status],
+    )
+    .await?;
+
+    Ok(())
+}
+
+pub async fn find_hold(id: Uuid) -> Result<Option<Hold>, Error> {
+    let client = connect_to_db().await?;
+    let row = client
+        .query_opt(
+            &format!("SELECT {} FROM holds WHERE id = $1", HOLD_FIELDS),
+            &[&id],
+        )
+        .await?;
+
+    Ok(row.as_ref().map(hold_from_row))
+}
+
+pub async fn find_active_by_account(account_id: Uuid) -> Result<Vec<Hold>, Error> {
+    let client = connect_to_db().await?;
+    let rows = client
+        .query(
+            &format!(
+                "SELECT {} FROM holds WHERE account_id = $1 AND status = 'ACTIVE'
+                  ORDER BY created_at",
+                HOLD_FIELDS
+            ),
+            &[&account_id],
+        )
+        .await?;
+
+    Ok(rows.iter().map(hold_from_row).collect())
+}
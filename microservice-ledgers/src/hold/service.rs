@@ -0,0 +1,271 @@
+use crate::balance::persistence::{lock_balances, update_balance};
+use crate::configuration::db::connect_to_db;
+use crate::hold::{
+    hold::{
+        CaptureInput, Hold, HoldInput, HOLD_ACTIVE, HOLD_CAPTURED, HOLD_EXPIRED, HOLD_RELEASED,
+    },
+    persistence::{
+        find_active_by_account, find_by_transfer_id, find_hold, insert_hold, lock_hold,
+        lock_next_expired, resolve_hold,
+    },
+};
+use crate::hot::service::fold_locked;
+use crate::ledger::{
+    ledger::{EntryInput, EntryType, LedgerEntry, PostingInput},
+    persistence::find_blocked_ids,
+    service::{post_entries, validate_posting},
+};
+use crate::utils::error::{parse_uuid, LedgerError};
+use chrono::{Duration, Utc};
+use rust_decimal::Decimal;
+use tokio_postgres::Transaction;
+use uuid::Uuid;
+
+pub const HOLD_DEFAULT_TTL_SECS: i64 = 10 * 60;
+pub const HOLD_MAX_TTL_SECS: i64 = 7 * 24 * 60 * 60;
+
+/// How often the expiry job releases holds whose transfer never settled.
+pub const HOLD_EXPIRY_INTERVAL_SECS: u64 = 30;
+
+pub fn validate_hold(input: &HoldInput) -> Result<Duration, LedgerError> {
+    if input.amount <= Decimal::ZERO || input.amount.scale() > 4 {
+        return Err(LedgerError::Invalid(
+            "Hold amount must be positive with at most 4 decimal places".to_string(),
+        ));
+    }
+
+    let ttl = input.expires_in_seconds.unwrap_or(HOLD_DEFAULT_TTL_SECS);
+    if ttl <= 0 || ttl > HOLD_MAX_TTL_SECS {
+        return Err(LedgerError::Invalid(format!(
+            "expires_in_seconds must be between 1 and {}",
+            HOLD_MAX_TTL_SECS
+        )));
+    }
+
+    Ok(Duration::seconds(ttl))
+}
+
+/// Reserves funds for a PENDING transfer. Placing the same hold twice returns
+/// the existing one, so retries from the transfer side are harmless.
+pub async fn place(input: HoldInput) -> Result<Hold, LedgerError> {
+    let ttl = validate_hold(&input)?;
+
+    let mut client = connect_to_db().await?;
+    let tx = client.transaction().await?;
+
+    let hold = place_in(&tx, &input, ttl).await?;
+
+    tx.commit().await?;
+
+    Ok(hold)
+}
+
+/// Places a validated hold inside the caller's transaction.
+pub async fn place_in(
+    tx: &Transaction<'_>,
+    input: &HoldInput,
+    ttl: Duration,
+) -> Result<Hold, LedgerError> {
+    if !find_blocked_ids(tx, &[input.account_id]).await?.is_empty() {
+        return Err(LedgerError::AccountBlocked(format!(
+            "Account {} is blocked",
+            input.account_id
+        )));
+    }
+
+    let mut balances = lock_balances(tx, &[input.account_id]).await?;
+    fold_locked(tx, &mut balances).await?;
+    let balance = balances
+        .first_mut()
+        .ok_or_else(|| LedgerError::NotFound(format!("Account {} not found", input.account_id)))?;
+
+    if let Some(existing) = find_by_transfer_id(tx, input.transfer_id).await? {
+        if existing.account_id == input.account_id && existing.amount == input.amount {
+            return Ok(existing);
+        }
+        return Err(LedgerError::Conflict(format!(
+            "Transfer {} already holds funds with different terms",
+            input.transfer_id
+        )));
+    }
+
+    if !balance.can_spend(input.amount) {
+        return Err(LedgerError::InsufficientFunds(format!(
+            "Account {} has insufficient funds",
+            input.account_id
+        )));
+    }
+
+    balance.hold(input.amount);
+    balance.version += 1;
+    update_balance(tx, balance).await?;
+
+    let now = Utc::now();
+    let hold = Hold {
+        id: Uuid::new_v4(),
+        account_id: input.account_id,
+        transfer_id: input.transfer_id,
+        amount: input.amount,
+        status: HOLD_ACTIVE.to_string(),
+        expires_at: now + ttl,
+        created_at: now,
+        resolved_at: None,
+    };
+    insert_hold(tx, &hold).await?;
+
+    Ok(hold)
+}
+
+/// Turns the hold into ledger entries once the transfer is POSTED.
+pub async fn capture(
+    input_uuid: &str,
+    input: CaptureInput,
+) -> Result<Vec<LedgerEntry>, LedgerError> {
+    let id = parse_uuid(input_uuid)?;
+
+    let mut client = connect_to_db().await?;
+    let tx = client.transaction().await?;
+
+    let hold = lock_active_hold(&tx, id).await?;
+
+    if hold.expires_at <= Utc::now() {
+        release_locked(&tx, &hold, HOLD_EXPIRED).await?;
+        tx.commit().await?;
+        return Err(LedgerError::Conflict(format!("Hold {} has expired", id)));
+    }
+
+    let entries = capture_locked(&tx, &hold, input.credit_account_id).await?;
+
+    tx.commit().await?;
+
+    Ok(entries)
+}
+
+/// Posts a locked, active hold to `credit_account_id` and marks it captured.
+pub async fn capture_locked(
+    tx: &Transaction<'_>,
+    hold: &Hold,
+    credit_account_id: Uuid,
+) -> Result<Vec<LedgerEntry>, LedgerError> {
+    if credit_account_id == hold.account_id {
+        return Err(LedgerError::Invalid(
+            "The credit account must differ from the held account".to_string(),
+        ));
+    }
+
+    let posting = PostingInput {
+        transfer_id: hold.transfer_id,
+        entries: vec![
+            EntryInput {
+                account_id: hold.account_id,
+                entry_type: EntryType::DEBIT,
+                amount: hold.amount,
+            },
+            EntryInput {
+                account_id: credit_account_id,
+                entry_type: EntryType::CREDIT,
+                amount: hold.amount,
+            },
+        ],
+    };
+    validate_posting(&posting)?;
+
+    let entries = post_entries(tx, &posting, Some(hold)).await?;
+    resolve_hold(tx, hold.id, HOLD_CAPTURED).await?;
+
+    Ok(entries)
+}
+
+/// Gives the funds back when the transfer FAILED.
+pub async fn release(input_uuid: &str) -> Result<Hold, LedgerError> {
+    let id = parse_uuid(input_uuid)?;
+
+    let mut client = connect_to_db().await?;
+    let tx = client.transaction().await?;
+
+    let hold = lock_active_hold(&tx, id).await?;
+    release_locked(&tx, &hold, HOLD_RELEASED).await?;
+
+    tx.commit().await?;
+
+    get_hold(input_uuid).await
+}
+
+/// Releases every active hold past its expiry and returns how many were released.
+pub async fn expire_due() -> Result<u64, LedgerError> {
+    let mut client = connect_to_db().await?;
+    let mut expired = 0;
+
+    loop {
+        let tx = client.transaction().await?;
+
+        let Some(hold) = lock_next_expired(&tx, Utc::now()).await? else {
+            break;
+        };
+
+        release_locked(&tx, &hold, HOLD_EXPIRED).await?;
+        tx.commit().await?;
+        expired += 1;
+    }
+
+    Ok(expired)
+}
+
+pub async fn run_expiry_scheduler() {
+    let mut interval =
+        tokio::time::interval(std::time::Duration::from_secs(HOLD_EXPIRY_INTERVAL_SECS));
+
+    loop {
+        interval.tick().await;
+
+        if let Err(e) = expire_due().await {
+            eprintln!("Hold expiry job failed: {}", e);
+        }
+    }
+}
+
+pub async fn get_hold(input_uuid: &str) -> Result<Hold, LedgerError> {
+    let id = parse_uuid(input_uuid)?;
+
+    find_hold(id)
+        .await?
+        .ok_or_else(|| LedgerError::NotFound(format!("Hold {} not found", id)))
+}
+
+pub async fn list_active(input_uuid: &str) -> Result<Vec<Hold>, LedgerError> {
+    let account_id = parse_uuid(input_uuid)?;
+    Ok(find_active_by_account(account_id).await?)
+}
+
+async fn lock_active_hold(tx: &Transaction<'_>, id: Uuid) -> Result<Hold, LedgerError> {
+    let hold = lock_hold(tx, id)
+        .await?
+        .ok_or_else(|| LedgerError::NotFound(format!("Hold {} not found", id)))?;
+
+    if hold.status != HOLD_ACTIVE {
+        return Err(LedgerError::Conflict(format!(
+            "Hold {} is already {}",
+            id, hold.status
+        )));
+    }
+
+    Ok(hold)
+}
+
+pub async fn release_locked(
+    tx: &Transaction<'_>,
+    hold: &Hold,
+    status: &str,
+) -> Result<(), LedgerError> {
+    let mut balances = lock_balances(tx, &[hold.account_id]).await?;
+
+    if let Some(balance) = balances.first_mut() {
+        balance.release(hold.amount);
+        balance.version += 1;
+        update_balance(tx, balance).await?;
+    }
+
+    resolve_hold(tx, hold.id, status).await?;
+
+    Ok(())
+}
@@ -0,0 +1,26 @@
+use crate::hot::hot::{HotAccount, HotAccountInput};
+use crate::hot::service;
+use crate::utils::error::LedgerError;
+use rocket::{delete, get, http::Status, put, routes, serde::json::Json, Route};
+
+#[put("/accounts/<id>/hot", format = "json", data = "<input>")]
+async fn designate(
+    id: &str,
+    input: Json<HotAccountInput>,
+) -> Result<Json<HotAccount>, LedgerError> {
+    service::designate(id, input.into_inner()).await.map(Json)
+}
+
+#[get("/accounts/<id>/hot")]
+async fn find_one(id: &str) -> Result<Json<HotAccount>, LedgerError> {
+    service::get_hot_account(id).await.map(Json)
+}
+
+#[delete("/accounts/<id>/hot")]
+async fn undesignate(id: &str) -> Result<Status, LedgerError> {
+    service::undesignate(id).await.map(|_| Status::NoContent)
+}
+
+pub fn hot_routes() -> Vec<Route> {
+    routes![designate, find_one, undesignate]
+}
@@ -0,0 +1,48 @@
+use crate::ledger::ledger::{EntryType, PostingInput};
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use uuid::Uuid;
+
+pub const MIN_BUCKETS: i32 = 2;
+pub const MAX_BUCKETS: i32 = 64;
+
+#[derive(Debug, Serialize)]
+pub struct HotAccount {
+    pub account_id: Uuid,
+    pub buckets: i32,
+    pub designated_by: String,
+    pub created_at: DateTime<Utc>,
+    /// Credited to the buckets and not yet folded into the balance row.
+    pub unfolded_balance: Decimal,
+    pub unfolded_entries: i64,
+}
+
+#[derive(Deserialize)]
+pub struct HotAccountInput {
+    pub buckets: i32,
+    pub designated_by: String,
+}
+
+/// Bucket a transfer credits. Spreading by transfer keeps retries of the same
+/// transfer on the same row.
+pub fn bucket_for(transfer_id: Uuid, buckets: i32) -> i32 {
+    (transfer_id.as_u128() % buckets as u128) as i32
+}
+
+/// Hot accounts (with their bucket count in `hot`) that the posting only
+/// credits, mapped to the bucket they take. Any hot account it debits goes
+/// through its locked balance row instead.
+pub fn bucketed_accounts(input: &PostingInput, hot: &HashMap<Uuid, i32>) -> BTreeMap<Uuid, i32> {
+    hot.iter()
+        .filter(|(account_id, _)| {
+            input
+                .entries
+                .iter()
+                .filter(|e| e.account_id == **account_id)
+                .all(|e| e.entry_type == EntryType::CREDIT)
+        })
+        .map(|(account_id, buckets)| (*account_id, bucket_for(input.transfer_id, *buckets)))
+        .collect()
+}
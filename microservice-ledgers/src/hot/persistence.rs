@@ -0,0 +1,190 @@
+use crate::configuration::db::connect_to_db;
+use crate::hot::hot::HotAccount;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+use tokio_postgres::{Client, Error, Transaction};
+use uuid::Uuid;
+
+pub async fn upsert_hot_account(
+    tx: &Transaction<'_>,
+    account_id: Uuid,
+    buckets: i32,
+    designated_by: &str,
+) -> Result<(), Error> {
+    tx.execute(
+        "INSERT INTO hot_accounts (account_id, buckets, designated_by) VALUES ($1, $2, $3)
+         ON CONFLICT (account_id) DO UPDATE
+            SET buckets = EXCLUDED.buckets, designated_by = EXCLUDED.designated_by",
+        &[&account_id, &buckets, &designated_by],
+    )
+    .await?;
+
+    Ok(())
+}
+
+pub async fn delete_hot_account(tx: &Transaction<'_>, account_id: Uuid) -> Result<u64, Error> {
+    tx.execute(
+        "DELETE FROM hot_accounts WHERE account_id = $1",
+        &[&account_id],
+    )
+    .await
+}
+
+/// Recreates the (empty) bucket rows. Only call right after a fold.
+pub async fn reset_bucket_rows(
+    tx: &Transaction<'_>,
+    account_id: Uuid,
+    buckets: i32,
+) -> Result<(), Error> {
+    tx.execute(
+        "DELETE FROM account_balance_buckets WHERE account_id = $1",
+        &[&account_id],
+    )
+    .await?;
+
+    tx.execute(
+        "INSERT INTO account_balance_buckets (account_id, bucket)
+             SELECT $1, generate_series(0, $2 - 1)",
+        &[&account_id, &buckets],
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Bucket count of the given accounts that are hot.
+pub async fn find_hot_buckets(
+    tx: &Transaction<'_>,
+    account_ids: &[Uuid],
+) -> Result<HashMap<Uuid, i32>, Error> {
+    let rows = tx
+        .query(
+            "SELECT account_id, buckets FROM hot_accounts WHERE account_id = ANY($1)",
+            &[&account_ids],
+        )
+        .await?;
+
+    Ok(rows.iter().map(|row| (row.get(0), row.get(1))).collect())
+}
+
+pub async fn find_hot_account_ids(client: &Client) -> Result<Vec<Uuid>, Error> {
+    let rows = client
+        .query(
+            "SELECT account_id FROM hot_accounts ORDER BY account_id",
+            &[],
+        )
+        .await?;
+
+    Ok(rows.iter().map(|row| row.get(0)).collect())
+}
+
+pub async fn find_hot_account(account_id: Uuid) -> Result<Option<HotAccount>, Error> {
+    let client = connect_to_db().await?;
+    let row = client
+        .query_opt(
+            "SELECT h.account_id, h.buckets, h.designated_by, h.created_at,
+                    COALESCE(SUM(b.balance), 0), COALESCE(SUM(b.entries_count), 0)::BIGINT
+               FROM hot_accounts h
+               LEFT JOIN account_balance_buckets b ON b.account_id = h.account_id
+              WHERE h.account_id = $1
+              GROUP BY h.account_id",
+            &[&account_id],
+        )
+        .await?;
+
+    Ok(row.map(|row| HotAccount {
+        account_id: row.get(0),
+        buckets: row.get(1),
+        designated_by: row.get(2),
+        created_at: row.get(3),
+        unfolded_balance: row.get(4),
+        unfolded_entries: row.get(5),
+    }))
+}
+
+/// Locks one bucket. Taken before the posting timestamp, like balance rows.
+pub async fn lock_bucket(tx: &Transaction<'_>, account_id: Uuid, bucket: i32) -> Result<(), Error> {
+    tx.execute(
+        "SELECT 1 FROM account_balance_buckets WHERE account_id = $1 AND bucket = $2 FOR UPDATE",
+        &[&account_id, &bucket],
+    )
+    .await?;
+
+    Ok(())
+}
+
+pub async fn credit_bucket(
+    tx: &Transaction<'_>,
+    account_id: Uuid,
+    bucket: i32,
+    amount: Decimal,
+    entries_count: i64,
+) -> Result<(), Error> {
+    tx.execute(
+        "UPDATE account_balance_buckets
+            SET balance = balance + $3, entries_count = entries_count + $4, updated_at = NOW()
+          WHERE account_id = $1 AND bucket = $2",
+        &[&account_id, &bucket, &amount, &entries_count],
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Locks every bucket of the account, so no credit lands while it is folded.
+/// Returns the number of credits waiting in them.
+pub async fn lock_buckets(tx: &Transaction<'_>, account_id: Uuid) -> Result<i64, Error> {
+    let rows = tx
+        .query(
+            "SELECT entries_count FROM account_balance_buckets
+              WHERE account_id = $1 ORDER BY bucket FOR UPDATE",
+            &[&account_id],
+        )
+        .await?;
+
+    Ok(rows.iter().map(|row| row.get::<_, i64>(0)).sum())
+}
+
+pub async fn empty_buckets(tx: &Transaction<'_>, account_id: Uuid) -> Result<(), Error> {
+    tx.execute(
+        "UPDATE account_balance_buckets SET balance = 0, entries_count = 0, updated_at = NOW()
+          WHERE account_id = $1",
+        &[&account_id],
+    )
+    .await?;
+
+    Ok(())
+}
+
+pub async fn insert_pending_entry(
+    tx: &Transaction<'_>,
+    entry_id: Uuid,
+    account_id: Uuid,
+    created_at: DateTime<Utc>,
+) -> Result<(), Error> {
+    tx.execute(
+        "INSERT INTO hot_account_pending_entries (entry_id, account_id, created_at)
+             VALUES ($1, $2, $3)",
+        &[&entry_id, &account_id, &created_at],
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Credited to the buckets and not folded yet: (amount, entries).
+pub async fn find_bucket_totals(
+    client: &Client,
+    account_id: Uuid,
+) -> Result<(Decimal, i64), Error> {
+    let row = client
+        .query_one(
+            "SELECT COALESCE(SUM(balance), 0), COALESCE(SUM(entries_count), 0)::BIGINT
+               FROM account_balance_buckets WHERE account_id = $1",
+            &[&account_id],
+        )
+        .await?;
+
+    Ok((row.get(0), row.get(1)))
+}
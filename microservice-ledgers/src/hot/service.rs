@@ -0,0 +1,161 @@
+use crate::balance::{
+    balance::AccountBalance,
+    persistence::lock_balances,
+    service::{advance_balance, persist_balance},
+};
+use crate::configuration::db::connect_to_db;
+use crate::hot::{
+    hot::{HotAccount, HotAccountInput, MAX_BUCKETS, MIN_BUCKETS},
+    persistence::{
+        delete_hot_account, empty_buckets, find_hot_account, find_hot_account_ids,
+        find_hot_buckets, lock_buckets, reset_bucket_rows, upsert_hot_account,
+    },
+};
+use crate::ledger::{
+    chain::link_entries,
+    ledger::LedgerEntry,
+    persistence::{take_pending_hot_entries, update_entry_links},
+};
+use crate::utils::error::{parse_uuid, LedgerError};
+use tokio_postgres::Transaction;
+use uuid::Uuid;
+
+/// How often bucketed credits are folded into the balance rows. Until then
+/// the balance read includes them, but the chain and the balance-changed event
+/// lag behind: the event for a bucketed credit is published when its fold
+/// commits, up to this many seconds after the posting.
+pub const HOT_FOLD_INTERVAL_SECS: u64 = 5;
+
+/// Links the pending credits after the balance's chain tip, in the order
+/// given (posting order), and moves the balance past them. Returns the
+/// entries count before the fold.
+pub fn fold_pending(balance: &mut AccountBalance, pending: &mut [LedgerEntry]) -> i64 {
+    link_entries(pending, std::slice::from_ref(balance));
+    advance_balance(balance, pending.iter())
+}
+
+/// Moves the bucketed credits of the hot accounts among `balances` into them.
+/// The balance rows must be locked; afterwards the buckets stay locked until
+/// commit, so the balances are the consistent total a debit can be checked against.
+pub async fn fold_locked(
+    tx: &Transaction<'_>,
+    balances: &mut [AccountBalance],
+) -> Result<(), LedgerError> {
+    let account_ids: Vec<Uuid> = balances.iter().map(|b| b.account_id).collect();
+    let hot = find_hot_buckets(tx, &account_ids).await?;
+
+    for balance in balances
+        .iter_mut()
+        .filter(|b| hot.contains_key(&b.account_id))
+    {
+        if lock_buckets(tx, balance.account_id).await? == 0 {
+            continue;
+        }
+
+        let mut pending = take_pending_hot_entries(tx, balance.account_id).await?;
+        if pending.is_empty() {
+            continue;
+        }
+
+        let previous_count = fold_pending(balance, &mut pending);
+        for entry in &pending {
+            update_entry_links(tx, entry).await?;
+        }
+
+        empty_buckets(tx, balance.account_id).await?;
+        // Queues the balance-changed event the bucketed credits did not emit.
+        persist_balance(tx, balance, previous_count).await?;
+    }
+
+    Ok(())
+}
+
+pub async fn designate(
+    input_uuid: &str,
+    input: HotAccountInput,
+) -> Result<HotAccount, LedgerError> {
+    let account_id = parse_uuid(input_uuid)?;
+
+    if !(MIN_BUCKETS..=MAX_BUCKETS).contains(&input.buckets) {
+        return Err(LedgerError::Invalid(format!(
+            "buckets must be between {} and {}",
+            MIN_BUCKETS, MAX_BUCKETS
+        )));
+    }
+
+    if input.designated_by.trim().is_empty() {
+        return Err(LedgerError::Invalid(
+            "designated_by is required".to_string(),
+        ));
+    }
+
+    let mut client = connect_to_db().await?;
+    let tx = client.transaction().await?;
+
+    // Changing the bucket count of a hot account folds the old buckets first.
+    let mut balances = lock_balances(&tx, &[account_id]).await?;
+    fold_locked(&tx, &mut balances).await?;
+
+    upsert_hot_account(&tx, account_id, input.buckets, input.designated_by.trim()).await?;
+    reset_bucket_rows(&tx, account_id, input.buckets).await?;
+
+    tx.commit().await?;
+
+    get_hot_account(input_uuid).await
+}
+
+pub async fn undesignate(input_uuid: &str) -> Result<(), LedgerError> {
+    let account_id = parse_uuid(input_uuid)?;
+
+    let mut client = connect_to_db().await?;
+    let tx = client.transaction().await?;
+
+    let mut balances = lock_balances(&tx, &[account_id]).await?;
+    fold_locked(&tx, &mut balances).await?;
+
+    if delete_hot_account(&tx, account_id).await? == 0 {
+        return Err(LedgerError::NotFound(format!(
+            "Account {} is not a hot account",
+            account_id
+        )));
+    }
+
+    tx.commit().await?;
+
+    Ok(())
+}
+
+pub async fn get_hot_account(input_uuid: &str) -> Result<HotAccount, LedgerError> {
+    let account_id = parse_uuid(input_uuid)?;
+
+    find_hot_account(account_id).await?.ok_or_else(|| {
+        LedgerError::NotFound(format!("Account {} is not a hot account", account_id))
+    })
+}
+
+/// Folds every hot account, one transaction each.
+pub async fn fold_all() -> Result<(), LedgerError> {
+    let mut client = connect_to_db().await?;
+
+    for account_id in find_hot_account_ids(&client).await? {
+        let tx = client.transaction().await?;
+        let mut balances = lock_balances(&tx, &[account_id]).await?;
+        fold_locked(&tx, &mut balances).await?;
+        tx.commit().await?;
+    }
+
+    Ok(())
+}
+
+pub async fn run_fold_scheduler() {
+    let mut interval =
+        tokio::time::interval(std::time::Duration::from_secs(HOT_FOLD_INTERVAL_SECS));
+
+    loop {
+        interval.tick().await;
+
+        if let Err(e) = fold_all().await {
+            eprintln!("Hot account fold failed: {}", e);
+        }
+    }
+}
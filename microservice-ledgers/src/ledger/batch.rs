@@ -0,0 +1,126 @@
+use crate::balance::balance::AccountBalance;
+use crate::ledger::{
+    chain::link_entries,
+    ledger::{BatchPostingResult, BatchPostingStatus, LedgerEntry, PostingInput},
+    service::{check_funds, check_not_blocked, validate_posting},
+};
+use crate::utils::business_day::business_date;
+use crate::utils::error::{ErrorResponse, LedgerError};
+use chrono::{DateTime, Utc};
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+/// What a batch is checked against: the locked balances and the state that
+/// was read once for the whole batch.
+pub struct BatchContext<'a> {
+    pub balances: HashMap<Uuid, AccountBalance>,
+    pub internal_accounts: &'a HashSet<Uuid>,
+    pub blocked_accounts: &'a HashSet<Uuid>,
+    pub posted_transfers: &'a HashSet<Uuid>,
+    pub posted_at: DateTime<Utc>,
+}
+
+pub struct BatchPlan {
+    pub entries: Vec<LedgerEntry>,
+    pub results: Vec<BatchPostingResult>,
+}
+
+/// Runs the same checks as a single posting for every transfer, in order,
+/// against balances that already include the postings accepted before it.
+pub fn plan_batch(postings: &[PostingInput], mut ctx: BatchContext<'_>) -> BatchPlan {
+    let mut seen = HashSet::new();
+    let mut plan = BatchPlan {
+        entries: Vec::new(),
+        results: Vec::with_capacity(postings.len()),
+    };
+
+    for input in postings {
+        let result = if !seen.insert(input.transfer_id)
+            || ctx.posted_transfers.contains(&input.transfer_id)
+        {
+            Err(LedgerError::Conflict(format!(
+                "Transfer {} is already posted",
+                input.transfer_id
+            )))
+        } else {
+            plan_posting(input, &mut ctx)
+        };
+
+        plan.results.push(match result {
+            Ok(entries) => {
+                let entries_count = entries.len();
+                plan.entries.extend(entries);
+                BatchPostingResult {
+                    transfer_id: input.transfer_id,
+                    status: BatchPostingStatus::POSTED,
+                    entries_count,
+                    error: None,
+                }
+            }
+            Err(e) => BatchPostingResult {
+                transfer_id: input.transfer_id,
+                status: BatchPostingStatus::REJECTED,
+                entries_count: 0,
+                error: Some(ErrorResponse::from(&e)),
+            },
+        });
+    }
+
+    plan
+}
+
+fn plan_posting(
+    input: &PostingInput,
+    ctx: &mut BatchContext<'_>,
+) -> Result<Vec<LedgerEntry>, LedgerError> {
+    validate_posting(input)?;
+    check_not_blocked(input, ctx.blocked_accounts)?;
+
+    let business_date = business_date(ctx.posted_at);
+
+    let mut entries: Vec<LedgerEntry> = input
+        .entries
+        .iter()
+        .map(|e| LedgerEntry {
+            id: Uuid::new_v4(),
+            transfer_id: input.transfer_id,
+            account_id: e.account_id,
+            entry_type: e.entry_type,
+            amount: e.amount,
+            created_at: ctx.posted_at,
+            business_date,
+            account_seq: None,
+            prev_hash: None,
+            entry_hash: None,
+        })
+        .collect();
+
+    let account_ids: HashSet<Uuid> = entries.iter().map(|e| e.account_id).collect();
+    let balances: Vec<AccountBalance> = account_ids
+        .iter()
+        .filter_map(|id| ctx.balances.get(id))
+        .cloned()
+        .collect();
+    let customer_balances: Vec<AccountBalance> = balances
+        .iter()
+        .filter(|b| !ctx.internal_accounts.contains(&b.account_id))
+        .cloned()
+        .collect();
+
+    check_funds(&customer_balances, &entries)?;
+    link_entries(&mut entries, &balances);
+    advance(&mut ctx.balances, &entries);
+
+    Ok(entries)
+}
+
+/// Moves the running balances and chain tips past accepted entries.
+fn advance(balances: &mut HashMap<Uuid, AccountBalance>, entries: &[LedgerEntry]) {
+    for entry in entries {
+        if let Some(balance) = balances.get_mut(&entry.account_id) {
+            balance.apply(entry.entry_type.signed(entry.amount));
+            balance.entries_count += 1;
+            balance.last_entry_hash = entry.entry_hash.clone();
+        }
+    }
+}
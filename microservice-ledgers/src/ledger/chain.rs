@@ -0,0 +1,105 @@
+use crate::balance::balance::AccountBalance;
+use crate::ledger::ledger::{BrokenLink, LedgerEntry};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// Assigns sequence numbers and hashes to new entries, continuing each
+/// account's chain from the tip stored in its (locked) balance row. Every
+/// entry applied to a balance is chained, so entries_count is the tip's
+/// account_seq (offset by the unhashed entries older than the chain, see
+/// `chain_origin_seq`).
+pub fn link_entries(entries: &mut [LedgerEntry], balances: &[AccountBalance]) {
+    let mut tips: HashMap<Uuid, (i64, Option<String>)> = balances
+        .iter()
+        .map(|b| (b.account_id, (b.entries_count, b.last_entry_hash.clone())))
+        .collect();
+
+    for entry in entries.iter_mut() {
+        let tip = tips.entry(entry.account_id).or_insert((0, None));
+
+        entry.account_seq = Some(tip.0 + 1);
+        entry.prev_hash = tip.1.clone();
+        let hash = entry.compute_hash();
+        entry.entry_hash = Some(hash.clone());
+
+        *tip = (tip.0 + 1, Some(hash));
+    }
+}
+
+/// Walks one account's chain in account_seq order and stops at the first broken link.
+pub struct ChainVerifier {
+    last_seq: i64,
+    last_hash: Option<String>,
+    pub entries_checked: i64,
+}
+
+impl Default for ChainVerifier {
+    /// A chain that starts with account_seq 1.
+    fn default() -> Self {
+        ChainVerifier::from_origin(0)
+    }
+}
+
+impl ChainVerifier {
+    /// A chain whose first entry must be `origin_seq + 1` with no prev_hash,
+    /// so removing the first entries is detected too.
+    pub fn from_origin(origin_seq: i64) -> Self {
+        ChainVerifier {
+            last_seq: origin_seq,
+            last_hash: None,
+            entries_checked: 0,
+        }
+    }
+
+    /// Continues a chain whose earlier entries were archived, from the last archived link.
+    pub fn resume(account_seq: i64, entry_hash: String) -> Self {
+        ChainVerifier {
+            last_seq: account_seq,
+            last_hash: Some(entry_hash),
+            entries_checked: 0,
+        }
+    }
+
+    pub fn last_seq(&self) -> i64 {
+        self.last_seq
+    }
+
+    pub fn check(&mut self, entry: &LedgerEntry) -> Result<(), BrokenLink> {
+        let broken = |reason: &str| BrokenLink {
+            entry_id: Some(entry.id),
+            account_seq: entry.account_seq,
+            reason: reason.to_string(),
+        };
+
+        if entry.account_seq != Some(self.last_seq + 1) {
+            return Err(broken("Sequence gap: an entry was removed before this one"));
+        }
+
+        if entry.prev_hash != self.last_hash {
+            return Err(broken("prev_hash does not match the previous entry"));
+        }
+
+        if entry.entry_hash.as_deref() != Some(entry.compute_hash().as_str()) {
+            return Err(broken("Entry content does not match its entry_hash"));
+        }
+
+        self.last_seq += 1;
+        self.last_hash = entry.entry_hash.clone();
+        self.entries_checked += 1;
+
+        Ok(())
+    }
+
+    /// The balance row keeps the chain tip, so removing the newest entries is detected too.
+    pub fn finish(&self, tip: Option<&str>) -> Result<(), BrokenLink> {
+        if self.last_hash.as_deref() != tip {
+            return Err(BrokenLink {
+                entry_id: None,
+                account_seq: Some(self.last_seq),
+                reason: "The chain ends before the tip recorded in the balance".to_string(),
+            });
+        }
+
+        Ok(())
+    }
+}
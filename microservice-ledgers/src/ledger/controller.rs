@@ -0,0 +1,36 @@
+use crate::ledger::ledger::{BatchPostingReport, ChainVerification, LedgerEntry, PostingRequest};
+use crate::ledger::service;
+use crate::utils::error::LedgerError;
+use rocket::{get, post, response::status, routes, serde::json::Json, Route};
+
+#[post("/postings", format = "json", data = "<input>")]
+async fn create(
+    input: Json<PostingRequest>,
+) -> Result<status::Created<Json<Vec<LedgerEntry>>>, LedgerError> {
+    let input = input.into_inner();
+    let location = format!("/transfers/{}/entries", input.transfer_id);
+
+    let entries = service::post_request(input).await?;
+    Ok(status::Created::new(location).body(Json(entries)))
+}
+
+#[post("/postings/batch", format = "json", data = "<input>")]
+async fn create_batch(
+    input: Json<Vec<PostingRequest>>,
+) -> Result<Json<BatchPostingReport>, LedgerError> {
+    service::post_batch(input.into_inner()).await.map(Json)
+}
+
+#[get("/transfers/<transfer_id>/entries")]
+async fn find_by_transfer(transfer_id: &str) -> Result<Json<Vec<LedgerEntry>>, LedgerError> {
+    service::find_by_transfer(transfer_id).await.map(Json)
+}
+
+#[get("/accounts/<id>/chain/verify")]
+async fn verify_chain(id: &str) -> Result<Json<ChainVerification>, LedgerError> {
+    service::verify_chain(id).await.map(Json)
+}
+
+pub fn ledger_routes() -> Vec<Route> {
+    routes![create, create_batch, find_by_transfer, verify_chain]
+}
@@ -0,0 +1,167 @@
+use crate::utils::error::ErrorResponse;
+use crate::utils::sha3::sha3_256_hex;
+use bytes::BytesMut;
+use chrono::{DateTime, NaiveDate, Utc};
+use postgres_types::{to_sql_checked, FromSql, IsNull, ToSql, Type};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EntryType {
+    DEBIT,
+    CREDIT,
+}
+
+impl EntryType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EntryType::DEBIT => "DEBIT",
+            EntryType::CREDIT => "CREDIT",
+        }
+    }
+
+    // Balances are kept from the account holder's point of view:
+    // credits increase the balance and debits decrease it.
+    pub fn signed(&self, amount: Decimal) -> Decimal {
+        match self {
+            EntryType::DEBIT => -amount,
+            EntryType::CREDIT => amount,
+        }
+    }
+}
+
+// Maps to the Postgres `entry_type` enum, which travels as its label text.
+impl ToSql for EntryType {
+    fn to_sql(&self, _ty: &Type, out: &mut BytesMut) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        out.extend_from_slice(self.as_str().as_bytes());
+        Ok(IsNull::No)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        ty.name() == "entry_type"
+    }
+
+    to_sql_checked!();
+}
+
+impl<'a> FromSql<'a> for EntryType {
+    fn from_sql(_ty: &Type, raw: &'a [u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        match std::str::from_utf8(raw)? {
+            "DEBIT" => Ok(EntryType::DEBIT),
+            "CREDIT" => Ok(EntryType::CREDIT),
+            other => Err(format!("Unknown entry_type: {}", other).into()),
+        }
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        ty.name() == "entry_type"
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LedgerEntry {
+    pub id: Uuid,
+    pub transfer_id: Uuid,
+    pub account_id: Uuid,
+    pub entry_type: EntryType,
+    pub amount: Decimal,
+    pub created_at: DateTime<Utc>,
+    pub business_date: NaiveDate,
+    pub account_seq: Option<i64>,
+    pub prev_hash: Option<String>,
+    pub entry_hash: Option<String>,
+}
+
+impl LedgerEntry {
+    /// SHA3-256 over every column an auditor cares about plus the previous link.
+    /// Amounts use the stored NUMERIC(19,4) scale and timestamps microseconds,
+    /// so the hash survives a round trip through Postgres.
+    pub fn compute_hash(&self) -> String {
+        let mut amount = self.amount;
+        amount.rescale(4);
+
+        let content = format!(
+            "{}|{}|{}|{}|{}|{}|{}|{}|{}",
+            self.id,
+            self.transfer_id,
+            self.account_id,
+            self.entry_type.as_str(),
+            amount,
+            self.created_at.timestamp_micros(),
+            self.business_date,
+            self.account_seq.unwrap_or_default(),
+            self.prev_hash.as_deref().unwrap_or_default(),
+        );
+
+        sha3_256_hex(content.as_bytes())
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct BrokenLink {
+    pub entry_id: Option<Uuid>,
+    pub account_seq: Option<i64>,
+    pub reason: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ChainVerification {
+    pub account_id: Uuid,
+    pub entries_checked: i64,
+    pub valid: bool,
+    pub first_broken_link: Option<BrokenLink>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct EntryInput {
+    pub account_id: Uuid,
+    pub entry_type: EntryType,
+    pub amount: Decimal,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PostingInput {
+    pub transfer_id: Uuid,
+    pub entries: Vec<EntryInput>,
+}
+
+/// Entry as received by `POST /postings`: the account is either an account id
+/// or the code of an internal account from the chart of accounts.
+#[derive(Debug, Deserialize)]
+pub struct EntryRequest {
+    pub account_id: Option<Uuid>,
+    pub account_code: Option<String>,
+    pub entry_type: EntryType,
+    pub amount: Decimal,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PostingRequest {
+    pub transfer_id: Uuid,
+    pub entries: Vec<EntryRequest>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum BatchPostingStatus {
+    POSTED,
+    REJECTED,
+}
+
+/// Outcome of one transfer in a batch. A rejected posting does not affect the others.
+#[derive(Debug, Serialize)]
+pub struct BatchPostingResult {
+    pub transfer_id: Uuid,
+    pub status: BatchPostingStatus,
+    pub entries_count: usize,
+    pub error: Option<ErrorResponse>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BatchPostingReport {
+    pub posted: usize,
+    pub rejected: usize,
+    pub entries_count: usize,
+    pub results: Vec<BatchPostingResult>,
+}
@@ -0,0 +1,6 @@
+pub mod batch;
+pub mod chain;
+pub mod controller;
+pub mod ledger;
+pub mod persistence;
+pub mod service;
@@ -0,0 +1,271 @@
+use crate::configuration::db::connect_to_db;
+use crate::ledger::ledger::LedgerEntry;
+use futures_util::{pin_mut, Stream, TryStreamExt};
+use tokio_postgres::{binary_copy::BinaryCopyInWriter, types::Type, Client, Error, Row, Transaction};
+use uuid::Uuid;
+
+const ENTRY_FIELDS: &str = "id, transfer_id, account_id, entry_type, amount, created_at, \
+     account_seq, prev_hash, entry_hash, business_date";
+
+fn entry_from_row(row: &Row) -> LedgerEntry {
+    LedgerEntry {
+        id: row.get(0),
+        transfer_id: row.get(1),
+        account_id: row.get(2),
+        entry_type: row.get(3),
+        amount: row.get(4),
+        created_at: row.get(5),
+        account_seq: row.get(6),
+        prev_hash: row.get(7),
+        entry_hash: row.get(8),
+        business_date: row.get(9),
+    }
+}
+
+pub async fn insert_entry(tx: &Transaction<'_>, entry: &LedgerEntry) -> Result<(), Error> {
+    tx.execute(
+        "INSERT INTO ledger_entries (id, transfer_id, account_id, entry_type, amount, created_at,
+                                     account_seq, prev_hash, entry_hash, business_date)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)",
+        &[
+            &entry.id,
+            &entry.transfer_id,
+            &entry.account_id,
+            &entry.entry_type,
+            &entry.amount,
+            &entry.created_at,
+            &entry.account_seq,
+            &entry.prev_hash,
+            &entry.entry_hash,
+            &entry.business_date,
+        ],
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Writes many entries with one binary COPY instead of one INSERT each.
+pub async fn copy_entries(tx: &Transaction<'_>, entries: &[LedgerEntry]) -> Result<u64, Error> {
+    // entry_type is a custom enum, so its type has to come from the server.
+    let entry_type = tx.prepare("SELECT $1::entry_type").await?.params()[0].clone();
+
+    let sink = tx
+        .copy_in(
+            "COPY ledger_entries (id, transfer_id, account_id, entry_type, amount, created_at,
+                                  account_seq, prev_hash, entry_hash, business_date)
+               FROM STDIN BINARY",
+        )
+        .await?;
+    let writer = BinaryCopyInWriter::new(
+        sink,
+        &[
+            Type::UUID,
+            Type::UUID,
+            Type::UUID,
+            entry_type,
+            Type::NUMERIC,
+            Type::TIMESTAMPTZ,
+            Type::INT8,
+            Type::BPCHAR,
+            Type::BPCHAR,
+            Type::DATE,
+        ],
+    );
+    pin_mut!(writer);
+
+    for entry in entries {
+        writer
+            .as_mut()
+            .write(&[
+                &entry.id,
+                &entry.transfer_id,
+                &entry.account_id,
+                &entry.entry_type,
+                &entry.amount,
+                &entry.created_at,
+                &entry.account_seq,
+                &entry.prev_hash,
+                &entry.entry_hash,
+                &entry.business_date,
+            ])
+            .await?;
+    }
+
+    writer.finish().await
+}
+
+/// Removes the account's bucketed credits from the pending queue and returns
+/// them in posting order, ready to be linked into its chain.
+pub async fn take_pending_hot_entries(
+    tx: &Transaction<'_>,
+    account_id: Uuid,
+) -> Result<Vec<LedgerEntry>, Error> {
+    let rows = tx
+        .query(
+            &format!(
+                "WITH taken AS (
+                     DELETE FROM hot_account_pending_entries WHERE account_id = $1
+                     RETURNING entry_id, created_at
+                 )
+                 SELECT {} FROM ledger_entries
+                  WHERE account_id = $1
+                    AND (id, created_at) IN (SELECT entry_id, created_at FROM taken)
+                  ORDER BY created_at, id",
+                ENTRY_FIELDS
+            ),
+            &[&account_id],
+        )
+        .await?;
+
+    Ok(rows.iter().map(entry_from_row).collect())
+}
+
+pub async fn update_entry_links(tx: &Transaction<'_>, entry: &LedgerEntry) -> Result<(), Error> {
+    tx.execute(
+        "UPDATE ledger_entries SET account_seq = $3, prev_hash = $4, entry_hash = $5
+          WHERE id = $1 AND created_at = $2",
+        &[
+            &entry.id,
+            &entry.created_at,
+            &entry.account_seq,
+            &entry.prev_hash,
+            &entry.entry_hash,
+        ],
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Streams the entries selected by `account_filter` (an SQL condition on
+/// account_id) per account in chain order, skipping hot-account credits that
+/// are not folded into a balance yet.
+pub async fn stream_replay_entries(
+    tx: &Transaction<'_>,
+    account_filter: &str,
+) -> Result<impl Stream<Item = Result<LedgerEntry, Error>>, Error> {
+    let rows = tx
+        .query_raw(
+            &format!(
+                "SELECT {} FROM ledger_entries le
+                  WHERE {}
+                    AND NOT EXISTS (
+                        SELECT 1 FROM hot_account_pending_entries p WHERE p.entry_id = le.id
+                    )
+                  ORDER BY account_id, account_seq NULLS FIRST, created_at, id",
+                ENTRY_FIELDS, account_filter
+            ),
+            Vec::<String>::new(),
+        )
+        .await?;
+
+    Ok(rows.map_ok(|row| entry_from_row(&row)))
+}
+
+/// Which of the given transfers already have entries.
+pub async fn find_posted_transfer_ids(
+    tx: &Transaction<'_>,
+    transfer_ids: &[Uuid],
+) -> Result<Vec<Uuid>, Error> {
+    let rows = tx
+        .query(
+            "SELECT DISTINCT transfer_id FROM ledger_entries WHERE transfer_id = ANY($1)",
+            &[&transfer_ids],
+        )
+        .await?;
+
+    Ok(rows.iter().map(|row| row.get(0)).collect())
+}
+
+pub async fn find_blocked_ids(
+    tx: &Transaction<'_>,
+    account_ids: &[Uuid],
+) -> Result<Vec<Uuid>, Error> {
+    let rows = tx
+        .query(
+            "SELECT account_id FROM blocked_accounts WHERE account_id = ANY($1)",
+            &[&account_ids],
+        )
+        .await?;
+
+    Ok(rows.iter().map(|row| row.get(0)).collect())
+}
+
+pub async fn has_entries(tx: &Transaction<'_>, transfer_id: Uuid) -> Result<bool, Error> {
+    let row = tx
+        .query_one(
+            "SELECT EXISTS (SELECT 1 FROM ledger_entries WHERE transfer_id = $1)",
+            &[&transfer_id],
+        )
+        .await?;
+
+    Ok(row.get(0))
+}
+
+pub async fn find_by_transfer_id(transfer_id: Uuid) -> Result<Vec<LedgerEntry>, Error> {
+    let client = connect_to_db().await?;
+    let rows = client
+        .query(
+            &format!(
+                "SELECT {} FROM ledger_entries WHERE transfer_id = $1 ORDER BY created_at, id",
+                ENTRY_FIELDS
+            ),
+            &[&transfer_id],
+        )
+        .await?;
+
+    Ok(rows.iter().map(entry_from_row).collect())
+}
+
+/// Next page of an account's hash chain, in account_seq order.
+pub async fn find_chain_page(
+    client: &Client,
+    account_id: Uuid,
+    after_seq: i64,
+    limit: i64,
+) -> Result<Vec<LedgerEntry>, Error> {
+    let rows = client
+        .query(
+            &format!(
+                "SELECT {} FROM ledger_entries
+                  WHERE account_id = $1 AND account_seq > $2
+                  ORDER BY account_seq LIMIT $3",
+                ENTRY_FIELDS
+            ),
+            &[&account_id, &after_seq, &limit],
+        )
+        .await?;
+
+    Ok(rows.iter().map(entry_from_row).collect())
+}
+
+/// account_seq the account's chain starts after; 0 unless it has entries older than the chain.
+pub async fn find_chain_origin(client: &Client, account_id: Uuid) -> Result<i64, Error> {
+    let row = client
+        .query_opt(
+            "SELECT chain_origin_seq FROM account_balances WHERE account_id = $1",
+            &[&account_id],
+        )
+        .await?;
+
+    Ok(row.map(|row| row.get(0)).unwrap_or(0))
+}
+
+pub async fn find_by_transfer_ids(
+    client: &Client,
+    transfer_ids: &[Uuid],
+) -> Result<Vec<LedgerEntry>, Error> {
+    let rows = client
+        .query(
+            &format!(
+                "SELECT {} FROM ledger_entries WHERE transfer_id = ANY($1)
+                  ORDER BY transfer_id, created_at, id",
+                ENTRY_FIELDS
+            ),
+            &[&transfer_ids],
+        )
+        .await?;
+
+    Ok(rows.iter().map(entry_from_row).collect())
+}
@@ -0,0 +1,460 @@
+use crate::balance::{
+    balance::AccountBalance,
+    persistence::{find_balance, lock_balances},
+    service::apply_entries,
+};
+use crate::chart::persistence::{find_ids_by_codes, find_internal_ids};
+use crate::closing::service::ensure_day_open;
+use crate::configuration::db::connect_to_db;
+use crate::hold::hold::Hold;
+use crate::hot::{
+    hot::bucketed_accounts,
+    persistence::{credit_bucket, find_hot_buckets, insert_pending_entry, lock_bucket},
+    service::fold_locked,
+};
+use crate::ledger::{
+    batch::{plan_batch, BatchContext},
+    chain::{link_entries, ChainVerifier},
+    ledger::{
+        BatchPostingReport, BatchPostingResult, BatchPostingStatus, ChainVerification, EntryInput,
+        EntryType, LedgerEntry, PostingInput, PostingRequest,
+    },
+    persistence::{
+        copy_entries, find_blocked_ids, find_by_transfer_id, find_chain_origin, find_chain_page,
+        find_posted_transfer_ids, insert_entry,
+    },
+};
+use crate::partition::persistence::find_archived_tip;
+use crate::utils::business_day::business_date;
+use crate::utils::error::{parse_uuid, ErrorResponse, LedgerError};
+use chrono::{SubsecRound, Utc};
+use rust_decimal::Decimal;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use tokio_postgres::Transaction;
+use uuid::Uuid;
+
+pub fn validate_posting(input: &PostingInput) -> Result<(), LedgerError> {
+    if input.entries.len() < 2 {
+        return Err(LedgerError::Invalid(
+            "A posting needs at least one debit and one credit".to_string(),
+        ));
+    }
+
+    if input.entries.iter().any(|e| e.amount <= Decimal::ZERO) {
+        return Err(LedgerError::Invalid(
+            "Entry amounts must be positive".to_string(),
+        ));
+    }
+
+    if input.entries.iter().any(|e| e.amount.scale() > 4) {
+        return Err(LedgerError::Invalid(
+            "Entry amounts support at most 4 decimal places".to_string(),
+        ));
+    }
+
+    let total = |entry_type: EntryType| -> Decimal {
+        input
+            .entries
+            .iter()
+            .filter(|e| e.entry_type == entry_type)
+            .map(|e| e.amount)
+            .sum()
+    };
+
+    if total(EntryType::DEBIT) != total(EntryType::CREDIT) {
+        return Err(LedgerError::Invalid(
+            "Debits and credits must balance".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Rejects the posting if any account it takes money from would end up below
+/// its overdraft limit. The balances must be locked by the caller, which is
+/// what makes the check safe against concurrent postings.
+pub fn check_funds(
+    balances: &[AccountBalance],
+    entries: &[LedgerEntry],
+) -> Result<(), LedgerError> {
+    for balance in balances {
+        let net: Decimal = entries
+            .iter()
+            .filter(|e| e.account_id == balance.account_id)
+            .map(|e| e.entry_type.signed(e.amount))
+            .sum();
+
+        if net < Decimal::ZERO && !balance.can_spend(-net) {
+            return Err(LedgerError::InsufficientFunds(format!(
+                "Account {} has insufficient funds",
+                balance.account_id
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+/// Rejects the posting if it debits or credits an account that was blocked
+/// when its customer was deactivated.
+pub fn check_not_blocked(input: &PostingInput, blocked: &HashSet<Uuid>) -> Result<(), LedgerError> {
+    match input
+        .entries
+        .iter()
+        .find(|e| blocked.contains(&e.account_id))
+    {
+        Some(entry) => Err(LedgerError::AccountBlocked(format!(
+            "Account {} is blocked",
+            entry.account_id
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// Resolves internal account codes to their ids, then posts.
+pub async fn post_request(request: PostingRequest) -> Result<Vec<LedgerEntry>, LedgerError> {
+    let ids_by_code = resolve_codes(std::slice::from_ref(&request)).await?;
+    post(to_posting(request, &ids_by_code)?).await
+}
+
+async fn resolve_codes(requests: &[PostingRequest]) -> Result<HashMap<String, Uuid>, LedgerError> {
+    let codes: Vec<String> = requests
+        .iter()
+        .flat_map(|r| r.entries.iter())
+        .filter_map(|e| e.account_code.clone())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+
+    if codes.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    Ok(find_ids_by_codes(&codes).await?.into_iter().collect())
+}
+
+fn to_posting(
+    request: PostingRequest,
+    ids_by_code: &HashMap<String, Uuid>,
+) -> Result<PostingInput, LedgerError> {
+    let mut entries = Vec::with_capacity(request.entries.len());
+    for entry in request.entries {
+        let account_id = match (entry.account_id, entry.account_code) {
+            (Some(account_id), None) => account_id,
+            (None, Some(code)) => *ids_by_code.get(&code).ok_or_else(|| {
+                LedgerError::Invalid(format!("Unknown ledger account code: {}", code))
+            })?,
+            _ => {
+                return Err(LedgerError::Invalid(
+                    "Each entry needs exactly one of account_id or account_code".to_string(),
+                ))
+            }
+        };
+
+        entries.push(EntryInput {
+            account_id,
+            entry_type: entry.entry_type,
+            amount: entry.amount,
+        });
+    }
+
+    Ok(PostingInput {
+        transfer_id: request.transfer_id,
+        entries,
+    })
+}
+
+pub async fn post(input: PostingInput) -> Result<Vec<LedgerEntry>, LedgerError> {
+    validate_posting(&input)?;
+
+    let mut client = connect_to_db().await?;
+    let tx = client.transaction().await?;
+
+    let entries = post_entries(&tx, &input, None).await?;
+
+    tx.commit().await?;
+
+    Ok(entries)
+}
+
+/// Writes a validated posting inside the caller's transaction. When the posting
+/// captures a hold, the held amount is released in the same step.
+pub async fn post_entries(
+    tx: &Transaction<'_>,
+    input: &PostingInput,
+    captured_hold: Option<&Hold>,
+) -> Result<Vec<LedgerEntry>, LedgerError> {
+    let account_ids: Vec<Uuid> = input
+        .entries
+        .iter()
+        .map(|e| e.account_id)
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+
+    let blocked: HashSet<Uuid> = find_blocked_ids(tx, &account_ids)
+        .await?
+        .into_iter()
+        .collect();
+    check_not_blocked(input, &blocked)?;
+
+    // Hot accounts that this posting only credits go to one of their buckets
+    // and leave the balance row unlocked.
+    let hot = find_hot_buckets(tx, &account_ids).await?;
+    let bucketed: BTreeMap<Uuid, i32> = bucketed_accounts(input, &hot);
+    let locked_ids: Vec<Uuid> = account_ids
+        .iter()
+        .filter(|id| !bucketed.contains_key(id))
+        .copied()
+        .collect();
+
+    let mut balances = lock_balances(tx, &locked_ids).await?;
+    fold_locked(tx, &mut balances).await?;
+    for (account_id, bucket) in &bucketed {
+        lock_bucket(tx, *account_id, *bucket).await?;
+    }
+
+    if let Some(hold) = captured_hold {
+        if let Some(balance) = balances
+            .iter_mut()
+            .find(|b| b.account_id == hold.account_id)
+        {
+            balance.release(hold.amount);
+        }
+    }
+
+    // Taken after the locks so entry timestamps never go backwards for an account.
+    let posted_at = Utc::now().trunc_subsecs(6);
+    let business_date = business_date(posted_at);
+    ensure_day_open(tx, business_date).await?;
+
+    let mut entries: Vec<LedgerEntry> = input
+        .entries
+        .iter()
+        .map(|e| LedgerEntry {
+            id: Uuid::new_v4(),
+            transfer_id: input.transfer_id,
+            account_id: e.account_id,
+            entry_type: e.entry_type,
+            amount: e.amount,
+            created_at: posted_at,
+            business_date,
+            account_seq: None,
+            prev_hash: None,
+            entry_hash: None,
+        })
+        .collect();
+
+    // Internal accounts (settlement, fees...) are not limited by their balance.
+    let internal = find_internal_ids(tx, &account_ids).await?;
+    let customer_balances: Vec<AccountBalance> = balances
+        .iter()
+        .filter(|b| !internal.contains(&b.account_id))
+        .cloned()
+        .collect();
+
+    check_funds(&customer_balances, &entries)?;
+
+    let mut chained: Vec<LedgerEntry> = entries
+        .iter()
+        .filter(|e| !bucketed.contains_key(&e.account_id))
+        .cloned()
+        .collect();
+    link_entries(&mut chained, &balances);
+    for entry in entries.iter_mut() {
+        if let Some(linked) = chained.iter().find(|c| c.id == entry.id) {
+            *entry = linked.clone();
+        }
+    }
+
+    for entry in &entries {
+        insert_entry(tx, entry).await?;
+    }
+
+    apply_entries(tx, &mut balances, &chained).await?;
+
+    // Bucketed credits leave the balance row, its chain and its balance-changed
+    // event to the next fold (see HOT_FOLD_INTERVAL_SECS).
+    for (account_id, bucket) in &bucketed {
+        let credits: Vec<&LedgerEntry> = entries
+            .iter()
+            .filter(|e| e.account_id == *account_id)
+            .collect();
+        let amount: Decimal = credits.iter().map(|e| e.amount).sum();
+
+        credit_bucket(tx, *account_id, *bucket, amount, credits.len() as i64).await?;
+        for entry in credits {
+            insert_pending_entry(tx, entry.id, entry.account_id, entry.created_at).await?;
+        }
+    }
+
+    Ok(entries)
+}
+
+/// Most postings accepted by one `POST /postings/batch` call.
+pub const MAX_BATCH_POSTINGS: usize = 5000;
+
+/// Posts many transfers in one transaction. Every transfer is validated on its
+/// own, so a rejected one is reported without failing the rest, and all the
+/// accepted entries are written with a single COPY.
+pub async fn post_batch(requests: Vec<PostingRequest>) -> Result<BatchPostingReport, LedgerError> {
+    if requests.is_empty() || requests.len() > MAX_BATCH_POSTINGS {
+        return Err(LedgerError::Invalid(format!(
+            "A batch must have between 1 and {} postings",
+            MAX_BATCH_POSTINGS
+        )));
+    }
+
+    let ids_by_code = resolve_codes(&requests).await?;
+    let mut postings = Vec::with_capacity(requests.len());
+    // None where the request became a posting, so results keep the request order.
+    let mut unresolved = Vec::with_capacity(requests.len());
+    for request in requests {
+        let transfer_id = request.transfer_id;
+        match to_posting(request, &ids_by_code) {
+            Ok(posting) => {
+                postings.push(posting);
+                unresolved.push(None);
+            }
+            Err(e) => unresolved.push(Some(BatchPostingResult {
+                transfer_id,
+                status: BatchPostingStatus::REJECTED,
+                entries_count: 0,
+                error: Some(ErrorResponse::from(&e)),
+            })),
+        }
+    }
+
+    let mut client = connect_to_db().await?;
+    let tx = client.transaction().await?;
+
+    let (entries, planned) = post_batch_entries(&tx, &postings).await?;
+
+    tx.commit().await?;
+
+    let mut planned = planned.into_iter();
+    let results: Vec<BatchPostingResult> = unresolved
+        .into_iter()
+        .filter_map(|result| result.or_else(|| planned.next()))
+        .collect();
+    let posted = results
+        .iter()
+        .filter(|r| r.status == BatchPostingStatus::POSTED)
+        .count();
+
+    Ok(BatchPostingReport {
+        posted,
+        rejected: results.len() - posted,
+        entries_count: entries.len(),
+        results,
+    })
+}
+
+/// Batch counterpart of `post_entries`, inside the caller's transaction.
+pub async fn post_batch_entries(
+    tx: &Transaction<'_>,
+    postings: &[PostingInput],
+) -> Result<(Vec<LedgerEntry>, Vec<BatchPostingResult>), LedgerError> {
+    let account_ids: Vec<Uuid> = postings
+        .iter()
+        .flat_map(|p| p.entries.iter().map(|e| e.account_id))
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+    let transfer_ids: Vec<Uuid> = postings.iter().map(|p| p.transfer_id).collect();
+
+    let mut balances = lock_balances(tx, &account_ids).await?;
+    fold_locked(tx, &mut balances).await?;
+    let posted_at = Utc::now().trunc_subsecs(6);
+
+    ensure_day_open(tx, business_date(posted_at)).await?;
+
+    let internal_accounts: HashSet<Uuid> = find_internal_ids(tx, &account_ids)
+        .await?
+        .into_iter()
+        .collect();
+    let blocked_accounts: HashSet<Uuid> = find_blocked_ids(tx, &account_ids)
+        .await?
+        .into_iter()
+        .collect();
+    let posted_transfers: HashSet<Uuid> = find_posted_transfer_ids(tx, &transfer_ids)
+        .await?
+        .into_iter()
+        .collect();
+
+    let plan = plan_batch(
+        postings,
+        BatchContext {
+            balances: balances.iter().map(|b| (b.account_id, b.clone())).collect(),
+            internal_accounts: &internal_accounts,
+            blocked_accounts: &blocked_accounts,
+            posted_transfers: &posted_transfers,
+            posted_at,
+        },
+    );
+
+    if !plan.entries.is_empty() {
+        copy_entries(tx, &plan.entries).await?;
+        apply_entries(tx, &mut balances, &plan.entries).await?;
+    }
+
+    Ok((plan.entries, plan.results))
+}
+
+pub async fn find_by_transfer(input_uuid: &str) -> Result<Vec<LedgerEntry>, LedgerError> {
+    let transfer_id = parse_uuid(input_uuid)?;
+    let entries = find_by_transfer_id(transfer_id).await?;
+
+    if entries.is_empty() {
+        return Err(LedgerError::NotFound(format!(
+            "No entries for transfer {}",
+            transfer_id
+        )));
+    }
+
+    Ok(entries)
+}
+
+/// Entries read per query while walking a chain.
+pub const CHAIN_PAGE_SIZE: i64 = 5000;
+
+pub async fn verify_chain(input_uuid: &str) -> Result<ChainVerification, LedgerError> {
+    let account_id = parse_uuid(input_uuid)?;
+    let tip = find_balance(account_id)
+        .await?
+        .and_then(|b| b.last_entry_hash);
+
+    let client = connect_to_db().await?;
+    let mut verifier = match find_archived_tip(&client, account_id).await? {
+        Some((account_seq, entry_hash)) => ChainVerifier::resume(account_seq, entry_hash),
+        None => ChainVerifier::from_origin(find_chain_origin(&client, account_id).await?),
+    };
+    let mut first_broken_link = None;
+
+    'pages: loop {
+        let after_seq = verifier.last_seq();
+        let page = find_chain_page(&client, account_id, after_seq, CHAIN_PAGE_SIZE).await?;
+
+        for entry in &page {
+            if let Err(link) = verifier.check(entry) {
+                first_broken_link = Some(link);
+                break 'pages;
+            }
+        }
+
+        if (page.len() as i64) < CHAIN_PAGE_SIZE {
+            break;
+        }
+    }
+
+    if first_broken_link.is_none() {
+        first_broken_link = verifier.finish(tip.as_deref()).err();
+    }
+
+    Ok(ChainVerification {
+        account_id,
+        entries_checked: verifier.entries_checked,
+        valid: first_broken_link.is_none(),
+        first_broken_link,
+    })
+}
@@ -1,5 +1,16 @@
+pub mod balance;
+pub mod chart;
+pub mod closing;
 pub mod configuration;
+pub mod event;
+pub mod hold;
+pub mod hot;
+pub mod ledger;
+pub mod partition;
 pub mod product;
+pub mod rebuild;
+pub mod reconciliation;
+pub mod statement;
 pub mod tests;
 pub mod utils;
 
@@ -8,13 +19,39 @@ use configuration::db::connect_to_db;
 #[macro_use]
 extern crate rocket;
 
+use balance::controller::balance_routes;
+use chart::controller::chart_routes;
+use closing::controller::closing_routes;
 use configuration::migrations::{check_table_exists, create_migration_table, run_migrations};
+use hold::controller::hold_routes;
+use hot::controller::hot_routes;
+use ledger::controller::ledger_routes;
+use partition::controller::partition_routes;
+use partition::persistence::ensure_partitions;
+use partition::service::PARTITION_MONTHS_AHEAD;
 use product::controller::product_routes;
+use reconciliation::controller::reconciliation_routes;
+use rocket::fairing::AdHoc;
 use rocket::{get, routes, Build, Rocket};
+use statement::controller::statement_routes;
 use std::fs;
 use std::path::Path;
 
-#[launch]
+#[rocket::main]
+async fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    // `rebuild ...` runs the balance rebuild tool instead of the server.
+    if args.first().map(String::as_str) == Some("rebuild") {
+        std::process::exit(rebuild::cli::run(&args[1..]).await);
+    }
+
+    if let Err(e) = rocket().await.launch().await {
+        eprintln!("Server error: {}", e);
+        std::process::exit(1);
+    }
+}
+
 async fn rocket() -> Rocket<Build> {
     let client = expect_or_exit(connect_to_db().await, "Failed to connect to database");
 
@@ -26,8 +63,62 @@ async fn rocket() -> Rocket<Build> {
     }
 
     expect_or_exit(run_migrations(&client).await, "Error in migrations");
+    expect_or_exit(
+        ensure_partitions(&client, PARTITION_MONTHS_AHEAD).await,
+        "Failed to create ledger partitions",
+    );
 
-    rocket::build().mount("/", product_routes())
+    rocket::build()
+        .mount("/", product_routes())
+        .mount("/", ledger_routes())
+        .mount("/", balance_routes())
+        .mount("/", statement_routes())
+        .mount("/", hold_routes())
+        .mount("/", hot_routes())
+        .mount("/", reconciliation_routes())
+        .mount("/", chart_routes())
+        .mount("/", closing_routes())
+        .mount("/", partition_routes())
+        .attach(AdHoc::on_liftoff("Statement job", |_| {
+            Box::pin(async {
+                tokio::spawn(statement::service::run_scheduler());
+            })
+        }))
+        .attach(AdHoc::on_liftoff("Hold expiry job", |_| {
+            Box::pin(async {
+                tokio::spawn(hold::service::run_expiry_scheduler());
+            })
+        }))
+        .attach(AdHoc::on_liftoff("Hot account fold job", |_| {
+            Box::pin(async {
+                tokio::spawn(hot::service::run_fold_scheduler());
+            })
+        }))
+        .attach(AdHoc::on_liftoff("Transfer event consumer", |_| {
+            Box::pin(async {
+                tokio::spawn(event::consumer::run_consumer());
+            })
+        }))
+        .attach(AdHoc::on_liftoff("Balance event publisher", |_| {
+            Box::pin(async {
+                tokio::spawn(event::publisher::run_balance_publisher());
+            })
+        }))
+        .attach(AdHoc::on_liftoff("End-of-day close job", |_| {
+            Box::pin(async {
+                tokio::spawn(closing::service::run_scheduler());
+            })
+        }))
+        .attach(AdHoc::on_liftoff("Ledger partition job", |_| {
+            Box::pin(async {
+                tokio::spawn(partition::service::run_scheduler());
+            })
+        }))
+        .attach(AdHoc::on_liftoff("Reconciliation job", |_| {
+            Box::pin(async {
+                tokio::spawn(reconciliation::service::run_scheduler());
+            })
+        }))
 }
 
 fn expect_or_exit<T, E: std::fmt::Display>(result: Result<T, E>, msg: &str) -> T {
@@ -0,0 +1,18 @@
+use crate::partition::partition::{PartitionArchive, PartitionOverview};
+use crate::partition::service;
+use crate::utils::error::LedgerError;
+use rocket::{get, post, routes, serde::json::Json, Route};
+
+#[get("/ledger-partitions")]
+async fn list() -> Result<Json<PartitionOverview>, LedgerError> {
+    service::list_partitions().await.map(Json)
+}
+
+#[post("/ledger-partitions/<name>/archive")]
+async fn archive(name: &str) -> Result<Json<PartitionArchive>, LedgerError> {
+    service::archive(name).await.map(Json)
+}
+
+pub fn partition_routes() -> Vec<Route> {
+    routes![list, archive]
+}
@@ -0,0 +1,4 @@
+pub mod controller;
+pub mod partition;
+pub mod persistence;
+pub mod service;
@@ -0,0 +1,63 @@
+use chrono::{DateTime, Datelike, Months, NaiveDate, TimeZone, Utc};
+use serde::Serialize;
+
+pub const PARTITION_PREFIX: &str = "ledger_entries_p";
+
+/// A monthly partition still attached to ledger_entries.
+#[derive(Debug, Serialize)]
+pub struct LedgerPartition {
+    pub name: String,
+    pub range_start: DateTime<Utc>,
+    pub range_end: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PartitionArchive {
+    pub partition_name: String,
+    pub range_start: DateTime<Utc>,
+    pub range_end: DateTime<Utc>,
+    pub rows_count: i64,
+    pub file_path: String,
+    pub checksum_sha3: String,
+    pub archived_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PartitionOverview {
+    pub attached: Vec<LedgerPartition>,
+    pub archived: Vec<PartitionArchive>,
+}
+
+/// Name of the partition holding entries created in `month`, as created by the
+/// `create_ledger_entries_partition` SQL function.
+pub fn partition_name(month: NaiveDate) -> String {
+    format!(
+        "{}{:04}{:02}",
+        PARTITION_PREFIX,
+        month.year(),
+        month.month()
+    )
+}
+
+/// UTC month covered by a partition, `[start, end)`. None if the name is not one
+/// of ours, which also keeps it safe to interpolate into SQL.
+pub fn partition_range(name: &str) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+    let suffix = name.strip_prefix(PARTITION_PREFIX)?;
+    if suffix.len() != 6 || !suffix.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+
+    let year: i32 = suffix[..4].parse().ok()?;
+    let month: u32 = suffix[4..].parse().ok()?;
+    let first_day = NaiveDate::from_ymd_opt(year, month, 1)?;
+    let next_month = first_day.checked_add_months(Months::new(1))?;
+
+    Some((
+        Utc.from_utc_datetime(&first_day.and_hms_opt(0, 0, 0)?),
+        Utc.from_utc_datetime(&next_month.and_hms_opt(0, 0, 0)?),
+    ))
+}
+
+pub fn archive_file_name(partition_name: &str) -> String {
+    format!("{}.csv.gz", partition_name)
+}
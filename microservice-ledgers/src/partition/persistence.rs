@@ -0,0 +1,206 @@
+use crate::configuration::db::connect_to_db;
+use crate::partition::partition::{partition_name, PartitionArchive};
+use chrono::{DateTime, NaiveDate, Utc};
+use tokio_postgres::{Client, CopyOutStream, Error, Row, Transaction};
+use uuid::Uuid;
+
+const ARCHIVE_FIELDS: &str =
+    "partition_name, range_start, range_end, rows_count, file_path, checksum_sha3, archived_at";
+
+fn archive_from_row(row: &Row) -> PartitionArchive {
+    PartitionArchive {
+        partition_name: row.get(0),
+        range_start: row.get(1),
+        range_end: row.get(2),
+        rows_count: row.get(3),
+        file_path: row.get(4),
+        checksum_sha3: row.get::<_, String>(5).trim_end().to_string(),
+        archived_at: row.get(6),
+    }
+}
+
+pub async fn ensure_partitions(client: &Client, months_ahead: i32) -> Result<(), Error> {
+    client
+        .execute(
+            "SELECT ensure_ledger_entries_partitions($1)",
+            &[&months_ahead],
+        )
+        .await?;
+
+    Ok(())
+}
+
+/// Partitions currently attached to ledger_entries, oldest first.
+pub async fn find_attached_partitions(client: &Client) -> Result<Vec<String>, Error> {
+    let rows = client
+        .query(
+            "SELECT c.relname::text
+               FROM pg_inherits i
+               JOIN pg_class c ON c.oid = i.inhrelid
+              WHERE i.inhparent = 'ledger_entries'::regclass
+              ORDER BY c.relname",
+            &[],
+        )
+        .await?;
+
+    Ok(rows.iter().map(|row| row.get(0)).collect())
+}
+
+/// True if any entry in the partition belongs to a business day that is not closed.
+pub async fn has_unclosed_days(client: &Client, month: NaiveDate) -> Result<bool, Error> {
+    let row = client
+        .query_one(
+            &format!(
+                "SELECT EXISTS (
+                     SELECT 1 FROM {} le
+                       LEFT JOIN business_days d ON d.business_date = le.business_date
+                      WHERE d.status IS DISTINCT FROM 'CLOSED'
+                 )",
+                partition_name(month)
+            ),
+            &[],
+        )
+        .await?;
+
+    Ok(row.get(0))
+}
+
+pub async fn copy_out_partition(client: &Client, month: NaiveDate) -> Result<CopyOutStream, Error> {
+    client
+        .copy_out(&format!(
+            "COPY (SELECT id, transfer_id, account_id, entry_type, amount, created_at,
+                          account_seq, prev_hash, entry_hash, business_date
+                     FROM {} ORDER BY created_at, id)
+               TO STDOUT WITH (FORMAT csv, HEADER)",
+            partition_name(month)
+        ))
+        .await
+}
+
+pub async fn count_rows(tx: &Transaction<'_>, month: NaiveDate) -> Result<i64, Error> {
+    let row = tx
+        .query_one(
+            &format!("SELECT COUNT(*) FROM {}", partition_name(month)),
+            &[],
+        )
+        .await?;
+
+    Ok(row.get(0))
+}
+
+/// Writes, for every account with entries in the partition, a snapshot of its
+/// balance and chain position as of `cutoff`, the last instant the partition covers.
+/// Every entry up to the previous snapshot is either in this partition or was
+/// covered by the snapshots of partitions archived before it.
+pub async fn insert_archive_snapshots(
+    tx: &Transaction<'_>,
+    month: NaiveDate,
+    cutoff: DateTime<Utc>,
+) -> Result<u64, Error> {
+    tx.execute(
+        &format!(
+            "INSERT INTO balance_snapshots (account_id, balance, entries_count, taken_at,
+                                            account_seq, entry_hash)
+             SELECT a.account_id,
+                    COALESCE(s.balance, 0) + COALESCE(e.total, 0),
+                    COALESCE(s.entries_count, 0) + e.entries,
+                    $1,
+                    last.account_seq,
+                    last.entry_hash
+               FROM (SELECT DISTINCT account_id FROM {partition}) a
+               LEFT JOIN LATERAL (
+                    SELECT balance, entries_count, taken_at FROM balance_snapshots
+                     WHERE account_id = a.account_id AND taken_at <= $1
+                     ORDER BY taken_at DESC LIMIT 1
+               ) s ON TRUE
+               CROSS JOIN LATERAL (
+                    SELECT SUM(CASE WHEN entry_type = 'CREDIT' THEN amount ELSE -amount END) AS total,
+                           COUNT(*) AS entries
+                      FROM ledger_entries
+                     WHERE account_id = a.account_id
+                       AND created_at <= $1
+                       AND created_at > COALESCE(s.taken_at, '-infinity')
+               ) e
+               CROSS JOIN LATERAL (
+                    SELECT account_seq, entry_hash FROM {partition}
+                     WHERE account_id = a.account_id
+                     ORDER BY account_seq DESC NULLS LAST, created_at DESC LIMIT 1
+               ) last",
+            partition = partition_name(month)
+        ),
+        &[&cutoff],
+    )
+    .await
+}
+
+pub async fn detach_partition(tx: &Transaction<'_>, month: NaiveDate) -> Result<(), Error> {
+    tx.batch_execute(&format!(
+        "ALTER TABLE ledger_entries DETACH PARTITION {0}; DROP TABLE {0};",
+        partition_name(month)
+    ))
+    .await
+}
+
+pub async fn insert_archive(tx: &Transaction<'_>, archive: &PartitionArchive) -> Result<(), Error> {
+    tx.execute(
+        "INSERT INTO ledger_partition_archives (partition_name, range_start, range_end, rows_count,
+                                                file_path, checksum_sha3, archived_at)
+             VALUES ($1, $2, $3, $4, $5, $6, $7)",
+        &[
+            &archive.partition_name,
+            &archive.range_start,
+            &archive.range_end,
+            &archive.rows_count,
+            &archive.file_path,
+            &archive.checksum_sha3,
+            &archive.archived_at,
+        ],
+    )
+    .await?;
+
+    Ok(())
+}
+
+pub async fn find_archives() -> Result<Vec<PartitionArchive>, Error> {
+    let client = connect_to_db().await?;
+    let rows = client
+        .query(
+            &format!(
+                "SELECT {} FROM ledger_partition_archives ORDER BY range_start",
+                ARCHIVE_FIELDS
+            ),
+            &[],
+        )
+        .await?;
+
+    Ok(rows.iter().map(archive_from_row).collect())
+}
+
+/// End of the newest archived partition; entries before it are no longer in the database.
+pub async fn find_archived_until() -> Result<Option<DateTime<Utc>>, Error> {
+    let client = connect_to_db().await?;
+    let row = client
+        .query_one("SELECT MAX(range_end) FROM ledger_partition_archives", &[])
+        .await?;
+
+    Ok(row.get(0))
+}
+
+/// Chain position of the account at the last archived entry, if it had any.
+pub async fn find_archived_tip(
+    client: &Client,
+    account_id: Uuid,
+) -> Result<Option<(i64, String)>, Error> {
+    let row = client
+        .query_opt(
+            "SELECT account_seq, entry_hash FROM balance_snapshots
+              WHERE account_id = $1
+                AND account_seq IS NOT NULL AND entry_hash IS NOT NULL
+                AND taken_at < (SELECT MAX(range_end) FROM ledger_partition_archives)
+              ORDER BY taken_at DESC LIMIT 1",
+            &[&account_id],
+        )
+        .await?;
+
+    Ok(row.map(|row| (row.get(0), row.get(1))))
+}
@@ -0,0 +1,206 @@
+use crate::configuration::{archive::archive_dir, db::connect_to_db};
+use crate::partition::{
+    partition::{
+        archive_file_name, partition_range, LedgerPartition, PartitionArchive, PartitionOverview,
+    },
+    persistence::{
+        copy_out_partition, count_rows, detach_partition, ensure_partitions, find_archived_until,
+        find_archives, find_attached_partitions, has_unclosed_days, insert_archive,
+        insert_archive_snapshots,
+    },
+};
+use crate::utils::error::LedgerError;
+use crate::utils::sha3::sha3_256_of_file;
+use bytes::Bytes;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use flate2::{write::GzEncoder, Compression};
+use futures_util::{pin_mut, TryStreamExt};
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use tokio::sync::mpsc;
+use tokio_postgres::Client;
+
+/// Months of partitions kept ready ahead of the current one.
+pub const PARTITION_MONTHS_AHEAD: i32 = 3;
+
+/// COPY chunks buffered between the export stream and the file writer.
+pub const EXPORT_CHANNEL_CHUNKS: usize = 64;
+
+/// How often the maintenance job checks the future partitions.
+pub const PARTITION_JOB_INTERVAL_SECS: u64 = 6 * 60 * 60;
+
+pub async fn ensure_future_partitions() -> Result<(), LedgerError> {
+    let client = connect_to_db().await?;
+    ensure_partitions(&client, PARTITION_MONTHS_AHEAD).await?;
+    Ok(())
+}
+
+pub async fn run_scheduler() {
+    let mut interval =
+        tokio::time::interval(std::time::Duration::from_secs(PARTITION_JOB_INTERVAL_SECS));
+
+    loop {
+        interval.tick().await;
+
+        if let Err(e) = ensure_future_partitions().await {
+            eprintln!("Ledger partition maintenance failed: {}", e);
+        }
+    }
+}
+
+pub async fn list_partitions() -> Result<PartitionOverview, LedgerError> {
+    let client = connect_to_db().await?;
+
+    let attached = find_attached_partitions(&client)
+        .await?
+        .into_iter()
+        .filter_map(|name| {
+            partition_range(&name).map(|(range_start, range_end)| LedgerPartition {
+                name,
+                range_start,
+                range_end,
+            })
+        })
+        .collect();
+
+    Ok(PartitionOverview {
+        attached,
+        archived: find_archives().await?,
+    })
+}
+
+/// Exports a past partition to a gzipped CSV file, snapshots the balances of
+/// the accounts it touches and detaches it. Partitions go oldest first and only
+/// once every business day they hold is closed.
+pub async fn archive(name: &str) -> Result<PartitionArchive, LedgerError> {
+    let (range_start, range_end) = partition_range(name)
+        .ok_or_else(|| LedgerError::Invalid(format!("Invalid ledger partition: {}", name)))?;
+
+    let mut client = connect_to_db().await?;
+    let attached = find_attached_partitions(&client).await?;
+
+    if !attached.iter().any(|p| p == name) {
+        return Err(LedgerError::NotFound(format!(
+            "Ledger partition {} not found",
+            name
+        )));
+    }
+
+    if range_end > Utc::now() {
+        return Err(LedgerError::Invalid(format!(
+            "Ledger partition {} is still receiving entries",
+            name
+        )));
+    }
+
+    if let Some(oldest) = attached.iter().find(|p| partition_range(p).is_some()) {
+        if oldest != name {
+            return Err(LedgerError::Conflict(format!(
+                "Archive {} before {}",
+                oldest, name
+            )));
+        }
+    }
+
+    // Only names rebuilt from the month are ever interpolated into SQL.
+    let month = range_start.date_naive();
+
+    if has_unclosed_days(&client, month).await? {
+        return Err(LedgerError::Conflict(format!(
+            "Ledger partition {} has entries in business days that are not closed",
+            name
+        )));
+    }
+
+    let dir = archive_dir();
+    let file_path = Path::new(&dir)
+        .join(archive_file_name(name))
+        .to_string_lossy()
+        .to_string();
+
+    blocking(move || fs::create_dir_all(dir)).await?;
+    export(&client, month, &file_path).await?;
+    let checksum_path = file_path.clone();
+    let checksum_sha3 = blocking(move || sha3_256_of_file(&checksum_path)).await?;
+
+    let tx = client.transaction().await?;
+
+    let archive = PartitionArchive {
+        partition_name: name.to_string(),
+        range_start,
+        range_end,
+        rows_count: count_rows(&tx, month).await?,
+        file_path,
+        checksum_sha3,
+        archived_at: Utc::now(),
+    };
+
+    insert_archive_snapshots(&tx, month, range_end - Duration::microseconds(1)).await?;
+    detach_partition(&tx, month).await?;
+    insert_archive(&tx, &archive).await?;
+
+    tx.commit().await?;
+
+    Ok(archive)
+}
+
+/// Streams the partition out of Postgres while a blocking thread compresses
+/// it to disk, so file I/O never runs on the async runtime.
+async fn export(client: &Client, month: NaiveDate, file_path: &str) -> Result<(), LedgerError> {
+    let stream = copy_out_partition(client, month).await?;
+    pin_mut!(stream);
+
+    let (sender, mut receiver) = mpsc::channel::<Bytes>(EXPORT_CHANNEL_CHUNKS);
+    let file_path = file_path.to_string();
+    let writer = tokio::task::spawn_blocking(move || -> std::io::Result<()> {
+        let mut encoder = GzEncoder::new(
+            BufWriter::new(File::create(file_path)?),
+            Compression::default(),
+        );
+        while let Some(chunk) = receiver.blocking_recv() {
+            encoder.write_all(&chunk)?;
+        }
+        encoder.finish()?.flush()
+    });
+
+    while let Some(chunk) = stream.try_next().await? {
+        // The writer only hangs up after failing; its error is reported below.
+        if sender.send(chunk).await.is_err() {
+            break;
+        }
+    }
+    drop(sender);
+
+    writer
+        .await
+        .map_err(|e| LedgerError::Storage(e.to_string()))??;
+
+    Ok(())
+}
+
+/// Runs blocking file work off the async runtime.
+async fn blocking<T, F>(work: F) -> Result<T, LedgerError>
+where
+    F: FnOnce() -> std::io::Result<T> + Send + 'static,
+    T: Send + 'static,
+{
+    tokio::task::spawn_blocking(work)
+        .await
+        .map_err(|e| LedgerError::Storage(e.to_string()))?
+        .map_err(LedgerError::from)
+}
+
+/// Rejects reads that need entries older than the archived partitions.
+pub async fn ensure_not_archived(at: DateTime<Utc>) -> Result<(), LedgerError> {
+    if let Some(archived_until) = find_archived_until().await? {
+        if at < archived_until - Duration::microseconds(1) {
+            return Err(LedgerError::Invalid(format!(
+                "Ledger entries before {} are archived",
+                archived_until
+            )));
+        }
+    }
+
+    Ok(())
+}
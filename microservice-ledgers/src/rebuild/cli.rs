@@ -0,0 +1,81 @@
+use crate::rebuild::{rebuild::RebuildScope, service};
+use crate::utils::error::LedgerError;
+use serde::Serialize;
+
+pub const USAGE: &str = "\
+Usage:
+  rebuild <scope> [--swap]   replay ledger_entries into the shadow tables and print the diff
+  rebuild diff <run-id>      print the diff of an earlier run against the live tables
+  rebuild swap <run-id>      swap an earlier run in
+
+Scopes: all, account:<uuid>, shard:<index>/<count>";
+
+/// Entry point of `rust_simple_rest_api rebuild ...`. Returns the process exit code.
+pub async fn run(args: &[String]) -> i32 {
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+
+    let result = match args.as_slice() {
+        ["diff", run_id] => diff(run_id).await,
+        ["swap", run_id] => swap(run_id).await,
+        [scope] => rebuild(scope, false).await,
+        [scope, "--swap"] => rebuild(scope, true).await,
+        _ => {
+            eprintln!("{}", USAGE);
+            return 2;
+        }
+    };
+
+    match result {
+        Ok(()) => 0,
+        Err(e) => {
+            eprintln!("Rebuild failed: {}", e);
+            1
+        }
+    }
+}
+
+async fn rebuild(scope: &str, swap_in: bool) -> Result<(), LedgerError> {
+    let scope: RebuildScope = scope.parse().map_err(LedgerError::Invalid)?;
+
+    let run = service::rebuild(scope).await?;
+    eprintln!(
+        "Rebuild run {} replayed {} entries for {} accounts",
+        run.id, run.entries_replayed, run.accounts_count
+    );
+
+    let run_id = run.id.to_string();
+    diff(&run_id).await?;
+
+    if swap_in {
+        swap(&run_id).await?;
+    }
+
+    Ok(())
+}
+
+async fn diff(run_id: &str) -> Result<(), LedgerError> {
+    let diff = service::diff(run_id).await?;
+    eprintln!(
+        "{} balances differ; {} live snapshots, {} rebuilt",
+        diff.balances.len(),
+        diff.live_snapshots,
+        diff.rebuilt_snapshots
+    );
+    print_json(&diff)
+}
+
+async fn swap(run_id: &str) -> Result<(), LedgerError> {
+    let changed = service::swap(run_id).await?;
+    eprintln!(
+        "Swapped in rebuild run {}: {} balances changed",
+        run_id, changed
+    );
+    Ok(())
+}
+
+fn print_json<T: Serialize>(value: &T) -> Result<(), LedgerError> {
+    let json =
+        serde_json::to_string_pretty(value).map_err(|e| LedgerError::Storage(e.to_string()))?;
+    println!("{}", json);
+    Ok(())
+}
@@ -0,0 +1,4 @@
+pub mod cli;
+pub mod persistence;
+pub mod rebuild;
+pub mod service;
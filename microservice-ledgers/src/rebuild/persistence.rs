@@ -0,0 +1,360 @@
+use crate::rebuild::rebuild::{
+    BalanceDiff, RebuildRun, RebuildScope, RebuiltBalance, ReplayBaseline, RUN_BUILDING, RUN_BUILT,
+    RUN_SWAPPED,
+};
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+use tokio_postgres::{Client, Error, Row, Transaction};
+use uuid::Uuid;
+
+const RUN_FIELDS: &str = "id, scope, status, accounts_count, entries_replayed, created_at, \
+     built_at, swapped_at";
+
+fn run_from_row(row: &Row) -> RebuildRun {
+    RebuildRun {
+        id: row.get(0),
+        scope: row.get(1),
+        status: row.get(2),
+        accounts_count: row.get(3),
+        entries_replayed: row.get(4),
+        created_at: row.get(5),
+        built_at: row.get(6),
+        swapped_at: row.get(7),
+    }
+}
+
+pub async fn insert_run(client: &Client, id: Uuid, scope: RebuildScope) -> Result<(), Error> {
+    client
+        .execute(
+            "INSERT INTO rebuild_runs (id, scope, status) VALUES ($1, $2, $3)",
+            &[&id, &scope.to_string(), &RUN_BUILDING],
+        )
+        .await?;
+
+    Ok(())
+}
+
+pub async fn find_run(client: &Client, id: Uuid) -> Result<Option<RebuildRun>, Error> {
+    let row = client
+        .query_opt(
+            &format!("SELECT {} FROM rebuild_runs WHERE id = $1", RUN_FIELDS),
+            &[&id],
+        )
+        .await?;
+
+    Ok(row.as_ref().map(run_from_row))
+}
+
+pub async fn complete_build(
+    client: &Client,
+    id: Uuid,
+    accounts_count: i64,
+    entries_replayed: i64,
+) -> Result<(), Error> {
+    client
+        .execute(
+            "UPDATE rebuild_runs
+                SET status = $2, accounts_count = $3, entries_replayed = $4, built_at = NOW()
+              WHERE id = $1",
+            &[&id, &RUN_BUILT, &accounts_count, &entries_replayed],
+        )
+        .await?;
+
+    Ok(())
+}
+
+pub async fn mark_swapped(tx: &Transaction<'_>, id: Uuid) -> Result<(), Error> {
+    tx.execute(
+        "UPDATE rebuild_runs SET status = $2, swapped_at = NOW() WHERE id = $1",
+        &[&id, &RUN_SWAPPED],
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Archive snapshot of each account in scope: the balance its archived entries add up to.
+pub async fn find_baselines(
+    tx: &Transaction<'_>,
+    scope: RebuildScope,
+    archived_until: Option<DateTime<Utc>>,
+) -> Result<HashMap<Uuid, ReplayBaseline>, Error> {
+    let Some(archived_until) = archived_until else {
+        return Ok(HashMap::new());
+    };
+
+    let rows = tx
+        .query(
+            &format!(
+                "SELECT DISTINCT ON (account_id) account_id, balance, entries_count, entry_hash
+                   FROM balance_snapshots
+                  WHERE taken_at < $1 AND {}
+                  ORDER BY account_id, taken_at DESC",
+                scope.predicate("account_id")
+            ),
+            &[&archived_until],
+        )
+        .await?;
+
+    Ok(rows
+        .iter()
+        .map(|row| {
+            (
+                row.get(0),
+                ReplayBaseline {
+                    balance: row.get(1),
+                    entries_count: row.get(2),
+                    entry_hash: row.get(3),
+                },
+            )
+        })
+        .collect())
+}
+
+pub async fn find_scope_accounts(
+    tx: &Transaction<'_>,
+    scope: RebuildScope,
+) -> Result<Vec<Uuid>, Error> {
+    let rows = tx
+        .query(
+            &format!(
+                "SELECT account_id FROM account_balances WHERE {}",
+                scope.predicate("account_id")
+            ),
+            &[],
+        )
+        .await?;
+
+    Ok(rows.iter().map(|row| row.get(0)).collect())
+}
+
+/// Entries a replay of the scope goes through: everything still in
+/// ledger_entries except hot-account credits not folded into a balance yet.
+pub async fn count_scope_entries(tx: &Transaction<'_>, scope: RebuildScope) -> Result<i64, Error> {
+    let row = tx
+        .query_one(
+            &format!(
+                "SELECT COUNT(*) FROM ledger_entries le
+                  WHERE {}
+                    AND NOT EXISTS (
+                        SELECT 1 FROM hot_account_pending_entries p WHERE p.entry_id = le.id
+                    )",
+                scope.predicate("le.account_id")
+            ),
+            &[],
+        )
+        .await?;
+
+    Ok(row.get(0))
+}
+
+pub async fn insert_rebuilt(
+    client: &Client,
+    run_id: Uuid,
+    rebuilt: &[RebuiltBalance],
+) -> Result<(), Error> {
+    let account_ids: Vec<Uuid> = rebuilt.iter().map(|r| r.account_id).collect();
+    let balances: Vec<Decimal> = rebuilt.iter().map(|r| r.balance).collect();
+    let entries_counts: Vec<i64> = rebuilt.iter().map(|r| r.entries_count).collect();
+    let last_entry_ats: Vec<Option<DateTime<Utc>>> =
+        rebuilt.iter().map(|r| r.last_entry_at).collect();
+    let last_entry_hashes: Vec<Option<String>> =
+        rebuilt.iter().map(|r| r.last_entry_hash.clone()).collect();
+
+    client
+        .execute(
+            "INSERT INTO account_balances_rebuild (run_id, account_id, balance, entries_count,
+                                                   last_entry_at, last_entry_hash)
+             SELECT $1, * FROM UNNEST($2::uuid[], $3::numeric[], $4::bigint[],
+                                      $5::timestamptz[], $6::text[])",
+            &[
+                &run_id,
+                &account_ids,
+                &balances,
+                &entries_counts,
+                &last_entry_ats,
+                &last_entry_hashes,
+            ],
+        )
+        .await?;
+
+    let snapshots: Vec<(Uuid, &_)> = rebuilt
+        .iter()
+        .flat_map(|r| r.snapshots.iter().map(move |s| (r.account_id, s)))
+        .collect();
+    if snapshots.is_empty() {
+        return Ok(());
+    }
+
+    let account_ids: Vec<Uuid> = snapshots.iter().map(|(id, _)| *id).collect();
+    let balances: Vec<Decimal> = snapshots.iter().map(|(_, s)| s.balance).collect();
+    let entries_counts: Vec<i64> = snapshots.iter().map(|(_, s)| s.entries_count).collect();
+    let taken_ats: Vec<DateTime<Utc>> = snapshots.iter().map(|(_, s)| s.taken_at).collect();
+    let entry_hashes: Vec<Option<String>> = snapshots
+        .iter()
+        .map(|(_, s)| s.entry_hash.clone())
+        .collect();
+
+    client
+        .execute(
+            "INSERT INTO balance_snapshots_rebuild (run_id, account_id, balance, entries_count,
+                                                    taken_at, account_seq, entry_hash)
+             SELECT $1, s.account_id, s.balance, s.entries_count, s.taken_at, s.entries_count,
+                    s.entry_hash
+               FROM UNNEST($2::uuid[], $3::numeric[], $4::bigint[], $5::timestamptz[],
+                           $6::text[])
+                    AS s(account_id, balance, entries_count, taken_at, entry_hash)",
+            &[
+                &run_id,
+                &account_ids,
+                &balances,
+                &entries_counts,
+                &taken_ats,
+                &entry_hashes,
+            ],
+        )
+        .await?;
+
+    Ok(())
+}
+
+/// Accounts whose rebuilt balance, entry count or chain tip differs from the live row.
+pub async fn find_diffs(client: &Client, run_id: Uuid) -> Result<Vec<BalanceDiff>, Error> {
+    let rows = client
+        .query(
+            "SELECT r.account_id, b.balance, r.balance, b.entries_count, r.entries_count,
+                    b.last_entry_hash, r.last_entry_hash
+               FROM account_balances_rebuild r
+               LEFT JOIN account_balances b ON b.account_id = r.account_id
+              WHERE r.run_id = $1
+                AND (b.account_id IS NULL
+                     OR b.balance <> r.balance
+                     OR b.entries_count <> r.entries_count
+                     OR b.last_entry_hash IS DISTINCT FROM r.last_entry_hash)
+              ORDER BY r.account_id",
+            &[&run_id],
+        )
+        .await?;
+
+    Ok(rows
+        .iter()
+        .map(|row| BalanceDiff {
+            account_id: row.get(0),
+            live_balance: row.get(1),
+            rebuilt_balance: row.get(2),
+            live_entries_count: row.get(3),
+            rebuilt_entries_count: row.get(4),
+            live_last_entry_hash: row.get(5),
+            rebuilt_last_entry_hash: row.get(6),
+        })
+        .collect())
+}
+
+/// Live snapshots a swap would replace: all of the scope's except archive baselines.
+pub async fn count_live_snapshots(
+    client: &Client,
+    scope: RebuildScope,
+    archived_until: Option<DateTime<Utc>>,
+) -> Result<i64, Error> {
+    let row = client
+        .query_one(
+            &format!(
+                "SELECT COUNT(*) FROM balance_snapshots
+                  WHERE {} AND taken_at >= COALESCE($1::timestamptz, '-infinity')",
+                scope.predicate("account_id")
+            ),
+            &[&archived_until],
+        )
+        .await?;
+
+    Ok(row.get(0))
+}
+
+pub async fn count_rebuilt_snapshots(client: &Client, run_id: Uuid) -> Result<i64, Error> {
+    let row = client
+        .query_one(
+            "SELECT COUNT(*) FROM balance_snapshots_rebuild WHERE run_id = $1",
+            &[&run_id],
+        )
+        .await?;
+
+    Ok(row.get(0))
+}
+
+/// Creates missing balance rows and locks every row the run covers, in
+/// account_id order like postings do.
+pub async fn lock_rebuilt_accounts(tx: &Transaction<'_>, run_id: Uuid) -> Result<(), Error> {
+    tx.execute(
+        "INSERT INTO account_balances (account_id)
+             SELECT account_id FROM account_balances_rebuild WHERE run_id = $1
+         ON CONFLICT (account_id) DO NOTHING",
+        &[&run_id],
+    )
+    .await?;
+
+    tx.execute(
+        "SELECT 1 FROM account_balances
+          WHERE account_id IN (SELECT account_id FROM account_balances_rebuild WHERE run_id = $1)
+          ORDER BY account_id FOR UPDATE",
+        &[&run_id],
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Copies the rebuilt values over the live rows that differ, with a
+/// balance-changed event for each.
+pub async fn swap_balances(tx: &Transaction<'_>, run_id: Uuid) -> Result<u64, Error> {
+    tx.execute(
+        "WITH updated AS (
+             UPDATE account_balances b
+                SET balance = r.balance,
+                    entries_count = r.entries_count,
+                    last_entry_at = COALESCE(r.last_entry_at, b.last_entry_at),
+                    last_entry_hash = r.last_entry_hash,
+                    version = b.version + 1,
+                    updated_at = NOW()
+               FROM account_balances_rebuild r
+              WHERE r.run_id = $1
+                AND b.account_id = r.account_id
+                AND (b.balance <> r.balance
+                     OR b.entries_count <> r.entries_count
+                     OR b.last_entry_hash IS DISTINCT FROM r.last_entry_hash)
+          RETURNING b.account_id, b.balance, b.balance - b.held_amount, b.version
+         )
+         INSERT INTO balance_outbox (account_id, ledger_balance, available_balance, version)
+              SELECT * FROM updated",
+        &[&run_id],
+    )
+    .await
+}
+
+pub async fn swap_snapshots(
+    tx: &Transaction<'_>,
+    run_id: Uuid,
+    scope: RebuildScope,
+    archived_until: Option<DateTime<Utc>>,
+) -> Result<(), Error> {
+    tx.execute(
+        &format!(
+            "DELETE FROM balance_snapshots
+              WHERE {} AND taken_at >= COALESCE($1::timestamptz, '-infinity')",
+            scope.predicate("account_id")
+        ),
+        &[&archived_until],
+    )
+    .await?;
+
+    tx.execute(
+        "INSERT INTO balance_snapshots (account_id, balance, entries_count, taken_at,
+                                        account_seq, entry_hash)
+             SELECT account_id, balance, entries_count, taken_at, account_seq, entry_hash
+               FROM balance_snapshots_rebuild WHERE run_id = $1",
+        &[&run_id],
+    )
+    .await?;
+
+    Ok(())
+}
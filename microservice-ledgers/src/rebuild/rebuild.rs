@@ -0,0 +1,203 @@
+use crate::balance::service::crosses_snapshot_boundary;
+use crate::ledger::ledger::LedgerEntry;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::Serialize;
+use std::str::FromStr;
+use uuid::Uuid;
+
+pub const RUN_BUILDING: &str = "BUILDING";
+pub const RUN_BUILT: &str = "BUILT";
+pub const RUN_SWAPPED: &str = "SWAPPED";
+
+/// Which accounts a rebuild covers. Shards split accounts by a hash of their
+/// id, so several rebuilds can run side by side on disjoint sets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RebuildScope {
+    All,
+    Account(Uuid),
+    Shard { index: u32, count: u32 },
+}
+
+impl RebuildScope {
+    /// SQL condition selecting the scope's rows by their account id column.
+    pub fn predicate(&self, column: &str) -> String {
+        match self {
+            RebuildScope::All => "TRUE".to_string(),
+            RebuildScope::Account(account_id) => format!("{} = '{}'", column, account_id),
+            RebuildScope::Shard { index, count } => format!(
+                "mod(hashtext({}::text)::bigint + 2147483648, {}) = {}",
+                column, count, index
+            ),
+        }
+    }
+}
+
+impl std::fmt::Display for RebuildScope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RebuildScope::All => write!(f, "all"),
+            RebuildScope::Account(account_id) => write!(f, "account:{}", account_id),
+            RebuildScope::Shard { index, count } => write!(f, "shard:{}/{}", index, count),
+        }
+    }
+}
+
+impl FromStr for RebuildScope {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        if value == "all" {
+            return Ok(RebuildScope::All);
+        }
+
+        if let Some(id) = value.strip_prefix("account:") {
+            return Uuid::parse_str(id)
+                .map(RebuildScope::Account)
+                .map_err(|_| format!("Invalid account id: {}", id));
+        }
+
+        if let Some(shard) = value.strip_prefix("shard:") {
+            let parsed = shard
+                .split_once('/')
+                .and_then(|(index, count)| Some((index.parse().ok()?, count.parse().ok()?)));
+
+            return match parsed {
+                Some((index, count)) if count > 0 && index < count => {
+                    Ok(RebuildScope::Shard { index, count })
+                }
+                _ => Err(format!(
+                    "Invalid shard {}: expected <index>/<count> with index < count",
+                    shard
+                )),
+            };
+        }
+
+        Err(format!("Invalid rebuild scope: {}", value))
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct RebuildRun {
+    pub id: Uuid,
+    pub scope: String,
+    pub status: String,
+    pub accounts_count: i64,
+    pub entries_replayed: i64,
+    pub created_at: DateTime<Utc>,
+    pub built_at: Option<DateTime<Utc>>,
+    pub swapped_at: Option<DateTime<Utc>>,
+}
+
+/// Account state the replay starts from: the snapshot written when the
+/// account's older entries were archived.
+#[derive(Debug, Clone)]
+pub struct ReplayBaseline {
+    pub balance: Decimal,
+    pub entries_count: i64,
+    pub entry_hash: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RebuiltSnapshot {
+    pub balance: Decimal,
+    pub entries_count: i64,
+    pub taken_at: DateTime<Utc>,
+    pub entry_hash: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct RebuiltBalance {
+    pub account_id: Uuid,
+    pub balance: Decimal,
+    pub entries_count: i64,
+    pub last_entry_at: Option<DateTime<Utc>>,
+    pub last_entry_hash: Option<String>,
+    pub snapshots: Vec<RebuiltSnapshot>,
+}
+
+/// Replays one account's entries in chain order, the way postings applied
+/// them: entries sharing a timestamp come from one posting and are applied
+/// together, and a snapshot is taken whenever a posting crosses a boundary.
+pub struct AccountReplay {
+    rebuilt: RebuiltBalance,
+    posting_at: Option<DateTime<Utc>>,
+    count_before_posting: i64,
+}
+
+impl AccountReplay {
+    pub fn new(account_id: Uuid, baseline: Option<ReplayBaseline>) -> Self {
+        let baseline = baseline.unwrap_or(ReplayBaseline {
+            balance: Decimal::ZERO,
+            entries_count: 0,
+            entry_hash: None,
+        });
+
+        AccountReplay {
+            rebuilt: RebuiltBalance {
+                account_id,
+                balance: baseline.balance,
+                entries_count: baseline.entries_count,
+                last_entry_at: None,
+                last_entry_hash: baseline.entry_hash,
+                snapshots: Vec::new(),
+            },
+            posting_at: None,
+            count_before_posting: baseline.entries_count,
+        }
+    }
+
+    pub fn account_id(&self) -> Uuid {
+        self.rebuilt.account_id
+    }
+
+    pub fn push(&mut self, entry: &LedgerEntry) {
+        if self.posting_at != Some(entry.created_at) {
+            self.end_posting();
+            self.posting_at = Some(entry.created_at);
+        }
+
+        self.rebuilt.balance += entry.entry_type.signed(entry.amount);
+        self.rebuilt.entries_count += 1;
+        self.rebuilt.last_entry_at = Some(entry.created_at);
+        self.rebuilt.last_entry_hash = entry.entry_hash.clone();
+    }
+
+    pub fn finish(mut self) -> RebuiltBalance {
+        self.end_posting();
+        self.rebuilt
+    }
+
+    fn end_posting(&mut self) {
+        if crosses_snapshot_boundary(self.count_before_posting, self.rebuilt.entries_count) {
+            if let Some(taken_at) = self.rebuilt.last_entry_at {
+                self.rebuilt.snapshots.push(RebuiltSnapshot {
+                    balance: self.rebuilt.balance,
+                    entries_count: self.rebuilt.entries_count,
+                    taken_at,
+                    entry_hash: self.rebuilt.last_entry_hash.clone(),
+                });
+            }
+        }
+        self.count_before_posting = self.rebuilt.entries_count;
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct BalanceDiff {
+    pub account_id: Uuid,
+    pub live_balance: Option<Decimal>,
+    pub rebuilt_balance: Decimal,
+    pub live_entries_count: Option<i64>,
+    pub rebuilt_entries_count: i64,
+    pub live_last_entry_hash: Option<String>,
+    pub rebuilt_last_entry_hash: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RebuildDiff {
+    pub run: RebuildRun,
+    pub live_snapshots: i64,
+    pub rebuilt_snapshots: i64,
+    pub balances: Vec<BalanceDiff>,
+}
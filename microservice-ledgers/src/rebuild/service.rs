@@ -0,0 +1,170 @@
+use crate::configuration::db::connect_to_db;
+use crate::ledger::persistence::stream_replay_entries;
+use crate::partition::persistence::find_archived_until;
+use crate::rebuild::{
+    persistence::{
+        complete_build, count_live_snapshots, count_rebuilt_snapshots, count_scope_entries,
+        find_baselines, find_diffs, find_run, find_scope_accounts, insert_rebuilt, insert_run,
+        lock_rebuilt_accounts, mark_swapped, swap_balances, swap_snapshots,
+    },
+    rebuild::{AccountReplay, RebuildDiff, RebuildRun, RebuildScope, RebuiltBalance, RUN_BUILT},
+};
+use crate::utils::error::{parse_uuid, LedgerError};
+use futures_util::{pin_mut, TryStreamExt};
+use std::collections::BTreeSet;
+use tokio_postgres::{Client, IsolationLevel};
+use uuid::Uuid;
+
+/// Rebuilt accounts written to the shadow tables per statement.
+pub const REBUILD_WRITE_BATCH: usize = 1000;
+
+/// Replays the scope's entries into the shadow tables. The live tables are
+/// only read, from one consistent snapshot.
+pub async fn rebuild(scope: RebuildScope) -> Result<RebuildRun, LedgerError> {
+    let run_id = Uuid::new_v4();
+    let writer = connect_to_db().await?;
+    insert_run(&writer, run_id, scope).await?;
+
+    let archived_until = find_archived_until().await?;
+
+    let mut reader = connect_to_db().await?;
+    let tx = reader
+        .build_transaction()
+        .isolation_level(IsolationLevel::RepeatableRead)
+        .read_only(true)
+        .start()
+        .await?;
+
+    let mut baselines = find_baselines(&tx, scope, archived_until).await?;
+    let mut untouched: BTreeSet<Uuid> = find_scope_accounts(&tx, scope)
+        .await?
+        .into_iter()
+        .chain(baselines.keys().copied())
+        .collect();
+
+    let mut batch: Vec<RebuiltBalance> = Vec::with_capacity(REBUILD_WRITE_BATCH);
+    let mut accounts_count: i64 = 0;
+    let mut entries_replayed: i64 = 0;
+    let mut current: Option<AccountReplay> = None;
+
+    let entries = stream_replay_entries(&tx, &scope.predicate("account_id")).await?;
+    pin_mut!(entries);
+
+    while let Some(entry) = entries.try_next().await? {
+        if current.as_ref().map(|r| r.account_id()) != Some(entry.account_id) {
+            if let Some(done) = current.take() {
+                batch.push(done.finish());
+                accounts_count += 1;
+                flush(&writer, run_id, &mut batch, false).await?;
+            }
+
+            untouched.remove(&entry.account_id);
+            current = Some(AccountReplay::new(
+                entry.account_id,
+                baselines.remove(&entry.account_id),
+            ));
+        }
+
+        if let Some(replay) = current.as_mut() {
+            replay.push(&entry);
+        }
+        entries_replayed += 1;
+    }
+
+    if let Some(done) = current.take() {
+        batch.push(done.finish());
+        accounts_count += 1;
+    }
+
+    // Accounts without live entries: zero, or whatever their archived entries add up to.
+    for account_id in untouched {
+        batch.push(AccountReplay::new(account_id, baselines.remove(&account_id)).finish());
+        accounts_count += 1;
+        flush(&writer, run_id, &mut batch, false).await?;
+    }
+
+    flush(&writer, run_id, &mut batch, true).await?;
+    tx.commit().await?;
+
+    complete_build(&writer, run_id, accounts_count, entries_replayed).await?;
+
+    load_run(&writer, run_id).await
+}
+
+async fn flush(
+    client: &Client,
+    run_id: Uuid,
+    batch: &mut Vec<RebuiltBalance>,
+    force: bool,
+) -> Result<(), LedgerError> {
+    if batch.is_empty() || (!force && batch.len() < REBUILD_WRITE_BATCH) {
+        return Ok(());
+    }
+
+    insert_rebuilt(client, run_id, batch).await?;
+    batch.clear();
+
+    Ok(())
+}
+
+async fn load_run(client: &Client, run_id: Uuid) -> Result<RebuildRun, LedgerError> {
+    find_run(client, run_id)
+        .await?
+        .ok_or_else(|| LedgerError::NotFound(format!("Rebuild run {} not found", run_id)))
+}
+
+fn run_scope(run: &RebuildRun) -> Result<RebuildScope, LedgerError> {
+    run.scope.parse().map_err(LedgerError::Invalid)
+}
+
+pub async fn diff(input_uuid: &str) -> Result<RebuildDiff, LedgerError> {
+    let run_id = parse_uuid(input_uuid)?;
+    let client = connect_to_db().await?;
+    let run = load_run(&client, run_id).await?;
+    let scope = run_scope(&run)?;
+    let archived_until = find_archived_until().await?;
+
+    Ok(RebuildDiff {
+        live_snapshots: count_live_snapshots(&client, scope, archived_until).await?,
+        rebuilt_snapshots: count_rebuilt_snapshots(&client, run_id).await?,
+        balances: find_diffs(&client, run_id).await?,
+        run,
+    })
+}
+
+/// Replaces the live balances and snapshots of the run's scope with the
+/// rebuilt ones. Refused if entries were posted into the scope since the
+/// rebuild, as the shadow copy would be stale. Returns the balances changed.
+pub async fn swap(input_uuid: &str) -> Result<u64, LedgerError> {
+    let run_id = parse_uuid(input_uuid)?;
+    let mut client = connect_to_db().await?;
+    let run = load_run(&client, run_id).await?;
+    let scope = run_scope(&run)?;
+
+    if run.status != RUN_BUILT {
+        return Err(LedgerError::Conflict(format!(
+            "Rebuild run {} is {}, only {} runs can be swapped in",
+            run_id, run.status, RUN_BUILT
+        )));
+    }
+
+    let archived_until = find_archived_until().await?;
+    let tx = client.transaction().await?;
+
+    lock_rebuilt_accounts(&tx, run_id).await?;
+
+    if count_scope_entries(&tx, scope).await? != run.entries_replayed {
+        return Err(LedgerError::Conflict(format!(
+            "Entries were posted for {} since rebuild run {}; rebuild again",
+            scope, run_id
+        )));
+    }
+
+    let changed = swap_balances(&tx, run_id).await?;
+    swap_snapshots(&tx, run_id, scope, archived_until).await?;
+    mark_swapped(&tx, run_id).await?;
+
+    tx.commit().await?;
+
+    Ok(changed)
+}
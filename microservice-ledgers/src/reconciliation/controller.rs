@@ -0,0 +1,20 @@
+use crate::reconciliation::reconciliation::{ReconciliationReport, ReconciliationRunInput};
+use crate::reconciliation::service;
+use crate::utils::error::LedgerError;
+use rocket::{get, post, routes, serde::json::Json, Route};
+
+#[post("/reconciliation/runs", format = "json", data = "<input>")]
+async fn create_run(
+    input: Json<ReconciliationRunInput>,
+) -> Result<Json<ReconciliationReport>, LedgerError> {
+    service::reconcile(input.into_inner().date).await.map(Json)
+}
+
+#[get("/reconciliation/runs/<id>")]
+async fn find_run(id: &str) -> Result<Json<ReconciliationReport>, LedgerError> {
+    service::get_report(id).await.map(Json)
+}
+
+pub fn reconciliation_routes() -> Vec<Route> {
+    routes![create_run, find_run]
+}
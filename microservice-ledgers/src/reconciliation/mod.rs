@@ -0,0 +1,4 @@
+pub mod controller;
+pub mod persistence;
+pub mod reconciliation;
+pub mod service;
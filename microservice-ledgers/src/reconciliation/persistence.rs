@@ -0,0 +1,244 @@
+use crate::configuration::db::connect_to_db;
+use crate::reconciliation::reconciliation::{
+    Discrepancy, ReconciliationItem, ReconciliationRun, TransferRecord,
+};
+use chrono::{DateTime, NaiveDate, Utc};
+use tokio_postgres::{Client, Error, Row};
+use uuid::Uuid;
+
+const RUN_FIELDS: &str = "id, business_date, period_start, period_end, status, transfers_checked, \
+     transfers_matched, issues_found, error, started_at, finished_at";
+
+const ITEM_FIELDS: &str = "id, transfer_id, issue, side, account_id, expected_amount, \
+     actual_amount, details, created_at";
+
+fn run_from_row(row: &Row) -> ReconciliationRun {
+    ReconciliationRun {
+        id: row.get(0),
+        business_date: row.get(1),
+        period_start: row.get(2),
+        period_end: row.get(3),
+        status: row.get(4),
+        transfers_checked: row.get(5),
+        transfers_matched: row.get(6),
+        issues_found: row.get(7),
+        error: row.get(8),
+        started_at: row.get(9),
+        finished_at: row.get(10),
+    }
+}
+
+fn item_from_row(row: &Row) -> ReconciliationItem {
+    ReconciliationItem {
+        id: row.get(0),
+        transfer_id: row.get(1),
+        issue: row.get(2),
+        side: row.get(3),
+        account_id: row.get(4),
+        expected_amount: row.get(5),
+        actual_amount: row.get(6),
+        details: row.get(7),
+        created_at: row.get(8),
+    }
+}
+
+fn transfer_from_row(row: &Row) -> TransferRecord {
+    TransferRecord {
+        id: row.get(0),
+        debit_account_id: row.get(1),
+        credit_account_id: row.get(2),
+        amount: row.get(3),
+        status: row.get(4),
+    }
+}
+
+// ---- transfers shards ----
+
+/// Next page, in id order, of transfers posted or reversed inside the window on one shard.
+pub async fn find_posted_transfers(
+    shard: &Client,
+    period_start: DateTime<Utc>,
+    period_end: DateTime<Utc>,
+    cursor: Option<Uuid>,
+    limit: i64,
+) -> Result<Vec<TransferRecord>, Error> {
+    let rows = shard
+        .query(
+            "SELECT id, debit_account_id, credit_account_id, amount, status::text
+               FROM transfers
+              WHERE ((status = 'POSTED' AND posted_at >= $1 AND posted_at < $2)
+                  OR (status = 'REVERSED'
+                      AND ((posted_at >= $1 AND posted_at < $2)
+                        OR (reversed_at >= $1 AND reversed_at < $2))))
+                AND ($3::uuid IS NULL OR id > $3)
+              ORDER BY id LIMIT $4",
+            &[&period_start, &period_end, &cursor, &limit],
+        )
+        .await?;
+
+    Ok(rows.iter().map(transfer_from_row).collect())
+}
+
+pub async fn find_transfers_by_ids(
+    shard: &Client,
+    ids: &[Uuid],
+) -> Result<Vec<TransferRecord>, Error> {
+    let rows = shard
+        .query(
+            "SELECT id, debit_account_id, credit_account_id, amount, status::text
+               FROM transfers WHERE id = ANY($1)",
+            &[&ids],
+        )
+        .await?;
+
+    Ok(rows.iter().map(transfer_from_row).collect())
+}
+
+// ---- ledger ----
+
+/// Next page of distinct transfer ids that have ledger entries inside the window.
+pub async fn find_ledger_transfer_ids(
+    client: &Client,
+    period_start: DateTime<Utc>,
+    period_end: DateTime<Utc>,
+    cursor: Option<Uuid>,
+    limit: i64,
+) -> Result<Vec<Uuid>, Error> {
+    let rows = client
+        .query(
+            "SELECT DISTINCT transfer_id FROM ledger_entries
+              WHERE created_at >= $1 AND created_at < $2
+                AND ($3::uuid IS NULL OR transfer_id > $3)
+              ORDER BY transfer_id LIMIT $4",
+            &[&period_start, &period_end, &cursor, &limit],
+        )
+        .await?;
+
+    Ok(rows.iter().map(|row| row.get(0)).collect())
+}
+
+pub async fn insert_run(
+    client: &Client,
+    business_date: NaiveDate,
+    period_start: DateTime<Utc>,
+    period_end: DateTime<Utc>,
+) -> Result<Uuid, Error> {
+    let row = client
+        .query_one(
+            "INSERT INTO reconciliation_runs (business_date, period_start, period_end)
+                 VALUES ($1, $2, $3) RETURNING id",
+            &[&business_date, &period_start, &period_end],
+        )
+        .await?;
+
+    Ok(row.get(0))
+}
+
+pub async fn insert_items(
+    client: &Client,
+    run_id: Uuid,
+    discrepancies: &[Discrepancy],
+) -> Result<(), Error> {
+    for d in discrepancies {
+        client
+            .execute(
+                "INSERT INTO reconciliation_items (run_id, transfer_id, issue, side, account_id,
+                                                   expected_amount, actual_amount, details)
+                     VALUES ($1, $2, $3, $4, $5, $6, $7, $8)",
+                &[
+                    &run_id,
+                    &d.transfer_id,
+                    &d.issue,
+                    &d.side,
+                    &d.account_id,
+                    &d.expected_amount,
+                    &d.actual_amount,
+                    &d.details,
+                ],
+            )
+            .await?;
+    }
+
+    Ok(())
+}
+
+pub async fn advance_run(
+    client: &Client,
+    run_id: Uuid,
+    checked: i64,
+    matched: i64,
+    issues: i64,
+) -> Result<(), Error> {
+    client
+        .execute(
+            "UPDATE reconciliation_runs
+                SET transfers_checked = transfers_checked + $2,
+                    transfers_matched = transfers_matched + $3,
+                    issues_found = issues_found + $4
+              WHERE id = $1",
+            &[&run_id, &checked, &matched, &issues],
+        )
+        .await?;
+
+    Ok(())
+}
+
+pub async fn finish_run(
+    client: &Client,
+    run_id: Uuid,
+    status: &str,
+    error: Option<String>,
+) -> Result<(), Error> {
+    client
+        .execute(
+            "UPDATE reconciliation_runs SET status = $2, error = $3, finished_at = NOW()
+              WHERE id = $1",
+            &[&run_id, &status, &error],
+        )
+        .await?;
+
+    Ok(())
+}
+
+pub async fn find_run(id: Uuid) -> Result<Option<ReconciliationRun>, Error> {
+    let client = connect_to_db().await?;
+    let row = client
+        .query_opt(
+            &format!(
+                "SELECT {} FROM reconciliation_runs WHERE id = $1",
+                RUN_FIELDS
+            ),
+            &[&id],
+        )
+        .await?;
+
+    Ok(row.as_ref().map(run_from_row))
+}
+
+pub async fn has_completed_run(client: &Client, business_date: NaiveDate) -> Result<bool, Error> {
+    let row = client
+        .query_one(
+            "SELECT EXISTS (SELECT 1 FROM reconciliation_runs
+                             WHERE business_date = $1 AND status = 'COMPLETED')",
+            &[&business_date],
+        )
+        .await?;
+
+    Ok(row.get(0))
+}
+
+pub async fn find_items(run_id: Uuid) -> Result<Vec<ReconciliationItem>, Error> {
+    let client = connect_to_db().await?;
+    let rows = client
+        .query(
+            &format!(
+                "SELECT {} FROM reconciliation_items WHERE run_id = $1
+                  ORDER BY transfer_id, created_at",
+                ITEM_FIELDS
+            ),
+            &[&run_id],
+        )
+        .await?;
+
+    Ok(rows.iter().map(item_from_row).collect())
+}
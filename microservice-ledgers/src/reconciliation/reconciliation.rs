@@ -0,0 +1,131 @@
+use crate::ledger::ledger::EntryType;
+use bytes::BytesMut;
+use chrono::{DateTime, NaiveDate, Utc};
+use postgres_types::{to_sql_checked, FromSql, IsNull, ToSql, Type};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum IssueKind {
+    /// The transfer has no ledger entry for one of its sides.
+    MissingEntry,
+    /// One side was posted more than once.
+    DuplicateEntry,
+    /// One side was posted with a different amount.
+    AmountMismatch,
+    /// An entry of the transfer hits an account that is not one of its sides.
+    UnexpectedEntry,
+    /// Ledger entries point to a transfer that is unknown or was never posted.
+    MissingTransfer,
+}
+
+impl IssueKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IssueKind::MissingEntry => "MISSING_ENTRY",
+            IssueKind::DuplicateEntry => "DUPLICATE_ENTRY",
+            IssueKind::AmountMismatch => "AMOUNT_MISMATCH",
+            IssueKind::UnexpectedEntry => "UNEXPECTED_ENTRY",
+            IssueKind::MissingTransfer => "MISSING_TRANSFER",
+        }
+    }
+}
+
+// Stored as VARCHAR guarded by a CHECK constraint.
+impl ToSql for IssueKind {
+    fn to_sql(
+        &self,
+        ty: &Type,
+        out: &mut BytesMut,
+    ) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        self.as_str().to_sql(ty, out)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        <&str as ToSql>::accepts(ty)
+    }
+
+    to_sql_checked!();
+}
+
+impl<'a> FromSql<'a> for IssueKind {
+    fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        match <&str as FromSql>::from_sql(ty, raw)? {
+            "MISSING_ENTRY" => Ok(IssueKind::MissingEntry),
+            "DUPLICATE_ENTRY" => Ok(IssueKind::DuplicateEntry),
+            "AMOUNT_MISMATCH" => Ok(IssueKind::AmountMismatch),
+            "UNEXPECTED_ENTRY" => Ok(IssueKind::UnexpectedEntry),
+            "MISSING_TRANSFER" => Ok(IssueKind::MissingTransfer),
+            other => Err(format!("Unknown reconciliation issue: {}", other).into()),
+        }
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        <&str as FromSql>::accepts(ty)
+    }
+}
+
+/// The fields of a transfer the ledger side is reconciled against.
+#[derive(Debug, Clone)]
+pub struct TransferRecord {
+    pub id: Uuid,
+    pub debit_account_id: Uuid,
+    pub credit_account_id: Uuid,
+    pub amount: Decimal,
+    pub status: String,
+}
+
+/// A discrepancy found by the matcher, before it is written to the report.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Discrepancy {
+    pub transfer_id: Uuid,
+    pub issue: IssueKind,
+    pub side: Option<EntryType>,
+    pub account_id: Option<Uuid>,
+    pub expected_amount: Option<Decimal>,
+    pub actual_amount: Option<Decimal>,
+    pub details: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReconciliationItem {
+    pub id: Uuid,
+    pub transfer_id: Uuid,
+    pub issue: IssueKind,
+    pub side: Option<EntryType>,
+    pub account_id: Option<Uuid>,
+    pub expected_amount: Option<Decimal>,
+    pub actual_amount: Option<Decimal>,
+    pub details: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReconciliationRun {
+    pub id: Uuid,
+    pub business_date: NaiveDate,
+    pub period_start: DateTime<Utc>,
+    pub period_end: DateTime<Utc>,
+    pub status: String,
+    pub transfers_checked: i64,
+    pub transfers_matched: i64,
+    pub issues_found: i64,
+    pub error: Option<String>,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReconciliationReport {
+    #[serde(flatten)]
+    pub run: ReconciliationRun,
+    pub items: Vec<ReconciliationItem>,
+}
+
+#[derive(Deserialize)]
+pub struct ReconciliationRunInput {
+    pub date: NaiveDate,
+}
@@ -0,0 +1,309 @@
+use crate::configuration::db::connect_to_db;
+use crate::configuration::transfers_db::{connect_to_transfer_shard, TRANSFER_SHARD_PORTS};
+use crate::ledger::{
+    ledger::{EntryType, LedgerEntry},
+    persistence::find_by_transfer_ids,
+};
+use crate::reconciliation::{
+    persistence::{
+        advance_run, find_items, find_ledger_transfer_ids, find_posted_transfers, find_run,
+        find_transfers_by_ids, finish_run, has_completed_run, insert_items, insert_run,
+    },
+    reconciliation::{Discrepancy, IssueKind, ReconciliationReport, TransferRecord},
+};
+use crate::utils::business_day::{business_date, day_start};
+use crate::utils::error::{parse_uuid, LedgerError};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+use tokio_postgres::Client;
+use uuid::Uuid;
+
+pub const RUN_COMPLETED: &str = "COMPLETED";
+pub const RUN_FAILED: &str = "FAILED";
+
+/// Transfers matched per round trip to a shard and to the ledger.
+pub const RECONCILIATION_BATCH_SIZE: i64 = 1000;
+
+/// How often the scheduler checks whether yesterday has been reconciled.
+pub const RECONCILIATION_JOB_INTERVAL_SECS: u64 = 15 * 60;
+
+fn discrepancy(
+    transfer: &TransferRecord,
+    issue: IssueKind,
+    side: EntryType,
+    account_id: Uuid,
+    actual_amount: Option<Decimal>,
+    details: String,
+) -> Discrepancy {
+    Discrepancy {
+        transfer_id: transfer.id,
+        issue,
+        side: Some(side),
+        account_id: Some(account_id),
+        expected_amount: Some(transfer.amount),
+        actual_amount,
+        details,
+    }
+}
+
+/// Compares a posted transfer with the ledger entries carrying its id. A clean
+/// transfer has exactly one debit on the payer and one credit on the receiver,
+/// both for the transfer amount, and nothing else. A reversed transfer also
+/// needs the reversal: one debit on the receiver and one credit on the payer.
+pub fn match_transfer(transfer: &TransferRecord, entries: &[LedgerEntry]) -> Vec<Discrepancy> {
+    let mut sides = vec![
+        (EntryType::DEBIT, transfer.debit_account_id),
+        (EntryType::CREDIT, transfer.credit_account_id),
+    ];
+    if transfer.status == "REVERSED" {
+        sides.push((EntryType::DEBIT, transfer.credit_account_id));
+        sides.push((EntryType::CREDIT, transfer.debit_account_id));
+    }
+    let mut found = Vec::new();
+
+    for &(side, account_id) in &sides {
+        let posted: Vec<&LedgerEntry> = entries
+            .iter()
+            .filter(|e| e.entry_type == side && e.account_id == account_id)
+            .collect();
+        let total: Decimal = posted.iter().map(|e| e.amount).sum();
+
+        match posted.len() {
+            0 => found.push(discrepancy(
+                transfer,
+                IssueKind::MissingEntry,
+                side,
+                account_id,
+                None,
+                format!("No {} entry on account {}", side.as_str(), account_id),
+            )),
+            1 if total != transfer.amount => found.push(discrepancy(
+                transfer,
+                IssueKind::AmountMismatch,
+                side,
+                account_id,
+                Some(total),
+                format!(
+                    "{} entry of {} for a transfer of {}",
+                    side.as_str(),
+                    total,
+                    transfer.amount
+                ),
+            )),
+            1 => {}
+            n => found.push(discrepancy(
+                transfer,
+                IssueKind::DuplicateEntry,
+                side,
+                account_id,
+                Some(total),
+                format!("{} {} entries on account {}", n, side.as_str(), account_id),
+            )),
+        }
+    }
+
+    for entry in entries
+        .iter()
+        .filter(|e| !sides.contains(&(e.entry_type, e.account_id)))
+    {
+        found.push(Discrepancy {
+            transfer_id: transfer.id,
+            issue: IssueKind::UnexpectedEntry,
+            side: Some(entry.entry_type),
+            account_id: Some(entry.account_id),
+            expected_amount: None,
+            actual_amount: Some(entry.amount),
+            details: format!("Entry {} is not on either side of the transfer", entry.id),
+        });
+    }
+
+    found
+}
+
+/// Ledger entries are only legitimate for transfers that were posted (and
+/// possibly reversed later); anything else means the transfer side is missing.
+pub fn check_transfer_exists(
+    transfer_id: Uuid,
+    transfer: Option<&TransferRecord>,
+) -> Option<Discrepancy> {
+    let details = match transfer {
+        None => "Ledger entries reference a transfer that exists in no shard".to_string(),
+        Some(t) if t.status == "POSTED" || t.status == "REVERSED" => return None,
+        Some(t) => format!("Ledger entries reference a transfer in status {}", t.status),
+    };
+
+    Some(Discrepancy {
+        transfer_id,
+        issue: IssueKind::MissingTransfer,
+        side: None,
+        account_id: None,
+        expected_amount: None,
+        actual_amount: None,
+        details,
+    })
+}
+
+/// Reconciles one business day (Brasília time). Every call starts a new run,
+/// so a day can be checked again after the drift it reported was fixed.
+pub async fn reconcile(date: NaiveDate) -> Result<ReconciliationReport, LedgerError> {
+    let period_start = day_start(date);
+    let period_end = day_start(date + Duration::days(1));
+
+    if period_end > Utc::now() {
+        return Err(LedgerError::Invalid(format!(
+            "Business day {} has not ended yet",
+            date
+        )));
+    }
+
+    let client = connect_to_db().await?;
+    let run_id = insert_run(&client, date, period_start, period_end).await?;
+
+    match reconcile_window(&client, run_id, period_start, period_end).await {
+        Ok(()) => finish_run(&client, run_id, RUN_COMPLETED, None).await?,
+        Err(e) => {
+            finish_run(&client, run_id, RUN_FAILED, Some(e.to_string())).await?;
+            return Err(e);
+        }
+    }
+
+    get_report(&run_id.to_string()).await
+}
+
+async fn reconcile_window(
+    client: &Client,
+    run_id: Uuid,
+    period_start: DateTime<Utc>,
+    period_end: DateTime<Utc>,
+) -> Result<(), LedgerError> {
+    let mut shards = Vec::with_capacity(TRANSFER_SHARD_PORTS.len());
+    for port in TRANSFER_SHARD_PORTS {
+        shards.push(connect_to_transfer_shard(port).await?);
+    }
+
+    // Transfer side: every posted or reversed transfer must have its ledger entries.
+    for shard in &shards {
+        let mut cursor = None;
+        loop {
+            let transfers = find_posted_transfers(
+                shard,
+                period_start,
+                period_end,
+                cursor,
+                RECONCILIATION_BATCH_SIZE,
+            )
+            .await?;
+
+            let Some(last_id) = transfers.last().map(|t| t.id) else {
+                break;
+            };
+
+            let ids: Vec<Uuid> = transfers.iter().map(|t| t.id).collect();
+            let mut entries_by_transfer: HashMap<Uuid, Vec<LedgerEntry>> = HashMap::new();
+            for entry in find_by_transfer_ids(client, &ids).await? {
+                entries_by_transfer
+                    .entry(entry.transfer_id)
+                    .or_default()
+                    .push(entry);
+            }
+
+            let mut discrepancies = Vec::new();
+            let mut matched = 0;
+            for transfer in &transfers {
+                let entries = entries_by_transfer
+                    .get(&transfer.id)
+                    .map(Vec::as_slice)
+                    .unwrap_or_default();
+                let found = match_transfer(transfer, entries);
+                if found.is_empty() {
+                    matched += 1;
+                }
+                discrepancies.extend(found);
+            }
+
+            insert_items(client, run_id, &discrepancies).await?;
+            advance_run(
+                client,
+                run_id,
+                transfers.len() as i64,
+                matched,
+                discrepancies.len() as i64,
+            )
+            .await?;
+            cursor = Some(last_id);
+        }
+    }
+
+    // Ledger side: entries posted in the window must belong to a known transfer.
+    let mut cursor = None;
+    loop {
+        let ids = find_ledger_transfer_ids(
+            client,
+            period_start,
+            period_end,
+            cursor,
+            RECONCILIATION_BATCH_SIZE,
+        )
+        .await?;
+
+        let Some(&last_id) = ids.last() else {
+            break;
+        };
+
+        let mut known = HashMap::new();
+        for shard in &shards {
+            for transfer in find_transfers_by_ids(shard, &ids).await? {
+                known.insert(transfer.id, transfer);
+            }
+        }
+
+        let discrepancies: Vec<Discrepancy> = ids
+            .iter()
+            .filter_map(|id| check_transfer_exists(*id, known.get(id)))
+            .collect();
+
+        insert_items(client, run_id, &discrepancies).await?;
+        advance_run(client, run_id, 0, 0, discrepancies.len() as i64).await?;
+        cursor = Some(last_id);
+    }
+
+    Ok(())
+}
+
+/// Reconciles yesterday unless it already has a completed run.
+pub async fn reconcile_due() -> Result<(), LedgerError> {
+    let yesterday = business_date(Utc::now()) - Duration::days(1);
+
+    let client = connect_to_db().await?;
+    if has_completed_run(&client, yesterday).await? {
+        return Ok(());
+    }
+
+    reconcile(yesterday).await.map(|_| ())
+}
+
+pub async fn run_scheduler() {
+    let mut interval = tokio::time::interval(std::time::Duration::from_secs(
+        RECONCILIATION_JOB_INTERVAL_SECS,
+    ));
+
+    loop {
+        interval.tick().await;
+
+        if let Err(e) = reconcile_due().await {
+            eprintln!("Reconciliation job failed: {}", e);
+        }
+    }
+}
+
+pub async fn get_report(input_uuid: &str) -> Result<ReconciliationReport, LedgerError> {
+    let id = parse_uuid(input_uuid)?;
+
+    let run = find_run(id)
+        .await?
+        .ok_or_else(|| LedgerError::NotFound(format!("Reconciliation run {} not found", id)))?;
+    let items = find_items(id).await?;
+
+    Ok(ReconciliationReport { run, items })
+}
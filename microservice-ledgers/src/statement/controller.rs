@@ -0,0 +1,33 @@
+use crate::statement::service;
+use crate::statement::statement::{PeriodType, Statement, StatementRun, StatementRunInput};
+use crate::utils::error::LedgerError;
+use rocket::{get, post, routes, serde::json::Json, Route};
+
+#[post("/statements/runs", format = "json", data = "<input>")]
+async fn create_run(input: Json<StatementRunInput>) -> Result<Json<StatementRun>, LedgerError> {
+    let input = input.into_inner();
+    service::generate(input.period_type, input.date)
+        .await
+        .map(Json)
+}
+
+#[get("/statements/runs/<id>")]
+async fn find_run(id: &str) -> Result<Json<StatementRun>, LedgerError> {
+    service::get_run(id).await.map(Json)
+}
+
+#[get("/accounts/<id>/statements?<period_type>&<from>&<to>")]
+async fn list(
+    id: &str,
+    period_type: Option<PeriodType>,
+    from: &str,
+    to: &str,
+) -> Result<Json<Vec<Statement>>, LedgerError> {
+    service::list_statements(id, period_type, from, to)
+        .await
+        .map(Json)
+}
+
+pub fn statement_routes() -> Vec<Route> {
+    routes![create_run, find_run, list]
+}
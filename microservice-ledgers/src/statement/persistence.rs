@@ -0,0 +1,220 @@
+use crate::configuration::db::connect_to_db;
+use crate::statement::statement::{PeriodMovement, PeriodType, Statement, StatementRun};
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use tokio_postgres::{Client, Error, Row, Transaction};
+use uuid::Uuid;
+
+const STATEMENT_FIELDS: &str = "id, account_id, period_type, period_start, period_end, \
+     opening_balance, total_credits, total_debits, closing_balance, entries_count, generated_at";
+
+const RUN_FIELDS: &str = "id, period_type, period_start, period_end, status, last_account_id, \
+     accounts_processed, started_at, finished_at";
+
+fn statement_from_row(row: &Row) -> Statement {
+    Statement {
+        id: row.get(0),
+        account_id: row.get(1),
+        period_type: row.get(2),
+        period_start: row.get(3),
+        period_end: row.get(4),
+        opening_balance: row.get(5),
+        total_credits: row.get(6),
+        total_debits: row.get(7),
+        closing_balance: row.get(8),
+        entries_count: row.get(9),
+        generated_at: row.get(10),
+    }
+}
+
+fn run_from_row(row: &Row) -> StatementRun {
+    StatementRun {
+        id: row.get(0),
+        period_type: row.get(1),
+        period_start: row.get(2),
+        period_end: row.get(3),
+        status: row.get(4),
+        last_account_id: row.get(5),
+        accounts_processed: row.get(6),
+        started_at: row.get(7),
+        finished_at: row.get(8),
+    }
+}
+
+pub async fn find_or_create_run(
+    client: &Client,
+    period_type: PeriodType,
+    period_start: DateTime<Utc>,
+    period_end: DateTime<Utc>,
+) -> Result<StatementRun, Error> {
+    client
+        .execute(
+            "INSERT INTO statement_runs (period_type, period_start, period_end)
+                 VALUES ($1, $2, $3) ON CONFLICT (period_type, period_start) DO NOTHING",
+            &[&period_type, &period_start, &period_end],
+        )
+        .await?;
+
+    let row = client
+        .query_one(
+            &format!(
+                "SELECT {} FROM statement_runs WHERE period_type = $1 AND period_start = $2",
+                RUN_FIELDS
+            ),
+            &[&period_type, &period_start],
+        )
+        .await?;
+
+    Ok(run_from_row(&row))
+}
+
+pub async fn find_run(id: Uuid) -> Result<Option<StatementRun>, Error> {
+    let client = connect_to_db().await?;
+    let row = client
+        .query_opt(
+            &format!("SELECT {} FROM statement_runs WHERE id = $1", RUN_FIELDS),
+            &[&id],
+        )
+        .await?;
+
+    Ok(row.as_ref().map(run_from_row))
+}
+
+/// Next batch of accounts, in account_id order, that existed before the period ended.
+pub async fn find_accounts_after(
+    tx: &Transaction<'_>,
+    cursor: Option<Uuid>,
+    period_end: DateTime<Utc>,
+    limit: i64,
+) -> Result<Vec<Uuid>, Error> {
+    let rows = tx
+        .query(
+            "SELECT account_id FROM account_balances
+              WHERE ($1::uuid IS NULL OR account_id > $1) AND created_at < $2
+              ORDER BY account_id LIMIT $3",
+            &[&cursor, &period_end, &limit],
+        )
+        .await?;
+
+    Ok(rows.iter().map(|row| row.get(0)).collect())
+}
+
+/// Closing balance of the statement that ended exactly when this period starts.
+pub async fn find_previous_closing(
+    tx: &Transaction<'_>,
+    account_id: Uuid,
+    period_type: PeriodType,
+    period_start: DateTime<Utc>,
+) -> Result<Option<Decimal>, Error> {
+    let row = tx
+        .query_opt(
+            "SELECT closing_balance FROM statements
+              WHERE account_id = $1 AND period_type = $2 AND period_end = $3",
+            &[&account_id, &period_type, &period_start],
+        )
+        .await?;
+
+    Ok(row.map(|row| row.get(0)))
+}
+
+pub async fn find_period_movement(
+    tx: &Transaction<'_>,
+    account_id: Uuid,
+    period_start: DateTime<Utc>,
+    period_end: DateTime<Utc>,
+) -> Result<PeriodMovement, Error> {
+    let row = tx
+        .query_one(
+            "SELECT COALESCE(SUM(amount) FILTER (WHERE entry_type = 'CREDIT'), 0),
+                    COALESCE(SUM(amount) FILTER (WHERE entry_type = 'DEBIT'), 0),
+                    COUNT(*)
+               FROM ledger_entries
+              WHERE account_id = $1 AND created_at >= $2 AND created_at < $3",
+            &[&account_id, &period_start, &period_end],
+        )
+        .await?;
+
+    Ok(PeriodMovement {
+        total_credits: row.get(0),
+        total_debits: row.get(1),
+        entries_count: row.get(2),
+    })
+}
+
+/// Inserting twice for the same account and period is a no-op, which keeps reruns idempotent.
+pub async fn insert_statement(tx: &Transaction<'_>, statement: &Statement) -> Result<(), Error> {
+    tx.execute(
+        "INSERT INTO statements (id, account_id, period_type, period_start, period_end,
+                                 opening_balance, total_credits, total_debits, closing_balance,
+                                 entries_count, generated_at)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+         ON CONFLICT (account_id, period_type, period_start) DO NOTHING",
+        &[
+            &statement.id,
+            &statement.account_id,
+            &statement.period_type,
+            &statement.period_start,
+            &statement.period_end,
+            &statement.opening_balance,
+            &statement.total_credits,
+            &statement.total_debits,
+            &statement.closing_balance,
+            &statement.entries_count,
+            &statement.generated_at,
+        ],
+    )
+    .await?;
+
+    Ok(())
+}
+
+pub async fn advance_run(
+    tx: &Transaction<'_>,
+    run_id: Uuid,
+    last_account_id: Uuid,
+    processed: i64,
+) -> Result<(), Error> {
+    tx.execute(
+        "UPDATE statement_runs
+            SET last_account_id = $2, accounts_processed = accounts_processed + $3
+          WHERE id = $1",
+        &[&run_id, &last_account_id, &processed],
+    )
+    .await?;
+
+    Ok(())
+}
+
+pub async fn complete_run(client: &Client, run_id: Uuid) -> Result<(), Error> {
+    client
+        .execute(
+            "UPDATE statement_runs SET status = 'COMPLETED', finished_at = NOW() WHERE id = $1",
+            &[&run_id],
+        )
+        .await?;
+
+    Ok(())
+}
+
+pub async fn find_by_account(
+    account_id: Uuid,
+    period_type: Option<PeriodType>,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+) -> Result<Vec<Statement>, Error> {
+    let client = connect_to_db().await?;
+    let rows = client
+        .query(
+            &format!(
+                "SELECT {} FROM statements
+                  WHERE account_id = $1 AND ($2::varchar IS NULL OR period_type = $2)
+                    AND period_start >= $3 AND period_start < $4
+                  ORDER BY period_start, period_type",
+                STATEMENT_FIELDS
+            ),
+            &[&account_id, &period_type, &from, &to],
+        )
+        .await?;
+
+    Ok(rows.iter().map(statement_from_row).collect())
+}
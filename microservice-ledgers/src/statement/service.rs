@@ -0,0 +1,151 @@
+use crate::balance::persistence::find_balance_as_of;
+use crate::configuration::db::connect_to_db;
+use crate::partition::service::ensure_not_archived;
+use crate::statement::{
+    persistence::{
+        advance_run, complete_run, find_accounts_after, find_by_account, find_or_create_run,
+        find_period_movement, find_previous_closing, find_run, insert_statement,
+    },
+    statement::{PeriodType, Statement, StatementRun},
+};
+use crate::utils::business_day::{business_date, day_start, parse_date};
+use crate::utils::error::{parse_uuid, LedgerError};
+use chrono::{Datelike, Duration, NaiveDate, Utc};
+use uuid::Uuid;
+
+pub const RUN_COMPLETED: &str = "COMPLETED";
+
+/// Accounts closed per transaction; the run cursor advances once per batch.
+pub const STATEMENT_BATCH_SIZE: i64 = 500;
+
+/// How often the scheduler looks for periods that still need statements.
+pub const STATEMENT_JOB_INTERVAL_SECS: u64 = 15 * 60;
+
+/// Closes `period_type` for the period containing `date`. Safe to call again
+/// for the same period: a completed run is returned as is and an interrupted
+/// one resumes after the last account it committed.
+pub async fn generate(period_type: PeriodType, date: NaiveDate) -> Result<StatementRun, LedgerError> {
+    let (first_day, next_period_day) = period_type.bounds(date);
+    let period_start = day_start(first_day);
+    let period_end = day_start(next_period_day);
+
+    if period_end > Utc::now() {
+        return Err(LedgerError::Invalid(format!(
+            "The {} period starting {} has not ended yet",
+            period_type.as_str(),
+            first_day
+        )));
+    }
+
+    ensure_not_archived(period_start).await?;
+
+    let mut client = connect_to_db().await?;
+    let run = find_or_create_run(&client, period_type, period_start, period_end).await?;
+
+    if run.status == RUN_COMPLETED {
+        return Ok(run);
+    }
+
+    let mut cursor = run.last_account_id;
+    loop {
+        let tx = client.transaction().await?;
+        let accounts = find_accounts_after(&tx, cursor, period_end, STATEMENT_BATCH_SIZE).await?;
+
+        let Some(&last_account_id) = accounts.last() else {
+            break;
+        };
+
+        for account_id in &accounts {
+            let opening_balance =
+                match find_previous_closing(&tx, *account_id, period_type, period_start).await? {
+                    Some(closing) => closing,
+                    None => {
+                        find_balance_as_of(*account_id, period_start - Duration::microseconds(1))
+                            .await?
+                    }
+                };
+            let movement = find_period_movement(&tx, *account_id, period_start, period_end).await?;
+
+            insert_statement(
+                &tx,
+                &Statement {
+                    id: Uuid::new_v4(),
+                    account_id: *account_id,
+                    period_type,
+                    period_start,
+                    period_end,
+                    opening_balance,
+                    total_credits: movement.total_credits,
+                    total_debits: movement.total_debits,
+                    closing_balance: opening_balance + movement.total_credits
+                        - movement.total_debits,
+                    entries_count: movement.entries_count,
+                    generated_at: Utc::now(),
+                },
+            )
+            .await?;
+        }
+
+        advance_run(&tx, run.id, last_account_id, accounts.len() as i64).await?;
+        tx.commit().await?;
+        cursor = Some(last_account_id);
+    }
+
+    complete_run(&client, run.id).await?;
+
+    find_run(run.id)
+        .await?
+        .ok_or_else(|| LedgerError::NotFound(format!("Statement run {} not found", run.id)))
+}
+
+/// Closes yesterday and the previous month, in Brasília time.
+pub async fn generate_due() -> Result<(), LedgerError> {
+    let today = business_date(Utc::now());
+    let yesterday = today - Duration::days(1);
+    let last_month = today.with_day(1).unwrap_or(today) - Duration::days(1);
+
+    generate(PeriodType::DAILY, yesterday).await?;
+    generate(PeriodType::MONTHLY, last_month).await?;
+
+    Ok(())
+}
+
+pub async fn run_scheduler() {
+    let mut interval =
+        tokio::time::interval(std::time::Duration::from_secs(STATEMENT_JOB_INTERVAL_SECS));
+
+    loop {
+        interval.tick().await;
+
+        if let Err(e) = generate_due().await {
+            eprintln!("Statement job failed: {}", e);
+        }
+    }
+}
+
+pub async fn get_run(input_uuid: &str) -> Result<StatementRun, LedgerError> {
+    let id = parse_uuid(input_uuid)?;
+
+    find_run(id)
+        .await?
+        .ok_or_else(|| LedgerError::NotFound(format!("Statement run {} not found", id)))
+}
+
+pub async fn list_statements(
+    input_uuid: &str,
+    period_type: Option<PeriodType>,
+    from: &str,
+    to: &str,
+) -> Result<Vec<Statement>, LedgerError> {
+    let account_id = parse_uuid(input_uuid)?;
+    let from = parse_date(from)?;
+    let to = parse_date(to)?;
+
+    Ok(find_by_account(
+        account_id,
+        period_type,
+        day_start(from),
+        day_start(to + Duration::days(1)),
+    )
+    .await?)
+}
@@ -0,0 +1,108 @@
+use bytes::BytesMut;
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc};
+use postgres_types::{to_sql_checked, FromSql, IsNull, ToSql, Type};
+use rocket::FromFormField;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, FromFormField)]
+pub enum PeriodType {
+    DAILY,
+    MONTHLY,
+}
+
+impl PeriodType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PeriodType::DAILY => "DAILY",
+            PeriodType::MONTHLY => "MONTHLY",
+        }
+    }
+
+    /// First day of the period containing `date` and first day of the next one.
+    pub fn bounds(&self, date: NaiveDate) -> (NaiveDate, NaiveDate) {
+        match self {
+            PeriodType::DAILY => (date, date + Duration::days(1)),
+            PeriodType::MONTHLY => {
+                let start = date.with_day(1).unwrap_or(date);
+                let end = if start.month() == 12 {
+                    NaiveDate::from_ymd_opt(start.year() + 1, 1, 1)
+                } else {
+                    NaiveDate::from_ymd_opt(start.year(), start.month() + 1, 1)
+                };
+                (start, end.unwrap_or(start))
+            }
+        }
+    }
+}
+
+// Stored as VARCHAR guarded by a CHECK constraint.
+impl ToSql for PeriodType {
+    fn to_sql(&self, ty: &Type, out: &mut BytesMut) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        self.as_str().to_sql(ty, out)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        <&str as ToSql>::accepts(ty)
+    }
+
+    to_sql_checked!();
+}
+
+impl<'a> FromSql<'a> for PeriodType {
+    fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        match <&str as FromSql>::from_sql(ty, raw)? {
+            "DAILY" => Ok(PeriodType::DAILY),
+            "MONTHLY" => Ok(PeriodType::MONTHLY),
+            other => Err(format!("Unknown period_type: {}", other).into()),
+        }
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        <&str as FromSql>::accepts(ty)
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct Statement {
+    pub id: Uuid,
+    pub account_id: Uuid,
+    pub period_type: PeriodType,
+    pub period_start: DateTime<Utc>,
+    pub period_end: DateTime<Utc>,
+    pub opening_balance: Decimal,
+    pub total_credits: Decimal,
+    pub total_debits: Decimal,
+    pub closing_balance: Decimal,
+    pub entries_count: i64,
+    pub generated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct StatementRun {
+    pub id: Uuid,
+    pub period_type: PeriodType,
+    pub period_start: DateTime<Utc>,
+    pub period_end: DateTime<Utc>,
+    pub status: String,
+    pub last_account_id: Option<Uuid>,
+    pub accounts_processed: i64,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Deserialize)]
+pub struct StatementRunInput {
+    pub period_type: PeriodType,
+    pub date: NaiveDate,
+}
+
+/// Movements of one account inside a statement period.
+#[derive(Debug)]
+pub struct PeriodMovement {
+    pub total_credits: Decimal,
+    pub total_debits: Decimal,
+    pub entries_count: i64,
+}
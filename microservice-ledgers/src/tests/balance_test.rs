@@ -0,0 +1,72 @@
+#[cfg(test)]
+mod balance_test {
+    use crate::balance::balance::DailyMovement;
+    use crate::balance::service::build_daily_series;
+    use crate::utils::business_day::{business_date, day_end, day_start, parse_as_of};
+    use chrono::{NaiveDate, TimeZone, Utc};
+    use rust_decimal::Decimal;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_business_day_bounds_use_brasilia_time() {
+        let day = date(2025, 8, 14);
+        assert_eq!(day_start(day), Utc.with_ymd_and_hms(2025, 8, 14, 3, 0, 0).unwrap());
+        assert_eq!(
+            day_end(day),
+            Utc.with_ymd_and_hms(2025, 8, 15, 2, 59, 59).unwrap()
+                + chrono::Duration::microseconds(999_999)
+        );
+    }
+
+    #[test]
+    fn test_late_evening_belongs_to_the_brasilia_day() {
+        let at = Utc.with_ymd_and_hms(2025, 8, 15, 1, 30, 0).unwrap();
+        assert_eq!(business_date(at), date(2025, 8, 14));
+    }
+
+    #[test]
+    fn test_parse_as_of_formats() {
+        assert_eq!(
+            parse_as_of("2025-08-14T23:59:00-03:00").unwrap(),
+            Utc.with_ymd_and_hms(2025, 8, 15, 2, 59, 0).unwrap()
+        );
+        assert_eq!(
+            parse_as_of("2025-08-14T23:59:00").unwrap(),
+            Utc.with_ymd_and_hms(2025, 8, 15, 2, 59, 0).unwrap()
+        );
+        assert_eq!(parse_as_of("2025-08-14").unwrap(), day_end(date(2025, 8, 14)));
+        assert!(parse_as_of("yesterday").is_err());
+    }
+
+    #[test]
+    fn test_daily_series_carries_closing_balance_forward() {
+        let movements = vec![
+            DailyMovement {
+                date: date(2025, 8, 1),
+                credits: Decimal::new(10000, 2),
+                debits: Decimal::new(2500, 2),
+            },
+            DailyMovement {
+                date: date(2025, 8, 3),
+                credits: Decimal::ZERO,
+                debits: Decimal::new(5000, 2),
+            },
+        ];
+
+        let series = build_daily_series(
+            Decimal::new(1000, 2),
+            date(2025, 8, 1),
+            date(2025, 8, 3),
+            &movements,
+        );
+
+        assert_eq!(series.len(), 3);
+        assert_eq!(series[0].closing_balance, Decimal::new(8500, 2));
+        assert_eq!(series[1].opening_balance, Decimal::new(8500, 2));
+        assert_eq!(series[1].closing_balance, Decimal::new(8500, 2));
+        assert_eq!(series[2].closing_balance, Decimal::new(3500, 2));
+    }
+}
@@ -0,0 +1,63 @@
+#[cfg(test)]
+mod chart_test {
+    use crate::chart::chart::{AccountClass, TrialBalanceLine};
+    use crate::chart::service::{build_trial_balance, customer_line, validate_code};
+    use crate::ledger::ledger::EntryType;
+    use chrono::Utc;
+    use rust_decimal::Decimal;
+
+    fn line(code: &str, account_class: AccountClass, debit: i64, credit: i64) -> TrialBalanceLine {
+        TrialBalanceLine {
+            code: code.to_string(),
+            name: code.to_string(),
+            account_class,
+            normal_balance: account_class.normal_balance(),
+            accounts: 1,
+            debit: Decimal::new(debit, 0),
+            credit: Decimal::new(credit, 0),
+        }
+    }
+
+    #[test]
+    fn test_normal_balance_sides() {
+        assert_eq!(AccountClass::ASSET.normal_balance(), EntryType::DEBIT);
+        assert_eq!(AccountClass::EXPENSE.normal_balance(), EntryType::DEBIT);
+        assert_eq!(AccountClass::LIABILITY.normal_balance(), EntryType::CREDIT);
+        assert_eq!(AccountClass::EQUITY.normal_balance(), EntryType::CREDIT);
+        assert_eq!(AccountClass::REVENUE.normal_balance(), EntryType::CREDIT);
+    }
+
+    #[test]
+    fn test_account_codes() {
+        assert!(validate_code("FEE_REVENUE").is_ok());
+        assert!(validate_code("SETTLEMENT_2").is_ok());
+        assert!(validate_code("").is_err());
+        assert!(validate_code("fee revenue").is_err());
+        assert!(validate_code("CUSTOMER_ACCOUNTS").is_err());
+        assert!(validate_code(&"A".repeat(65)).is_err());
+    }
+
+    #[test]
+    fn test_trial_balance_totals() {
+        // 1000 received from another participant, 2 of it charged as a fee.
+        let lines = vec![
+            line("SETTLEMENT", AccountClass::ASSET, 1000, 0),
+            line("FEE_REVENUE", AccountClass::REVENUE, 0, 2),
+            customer_line(3, Decimal::ZERO, Decimal::new(998, 0)),
+        ];
+
+        let trial_balance = build_trial_balance(Utc::now(), lines);
+        assert_eq!(trial_balance.total_debits, Decimal::new(1000, 0));
+        assert_eq!(trial_balance.total_credits, Decimal::new(1000, 0));
+        assert!(trial_balance.balanced);
+    }
+
+    #[test]
+    fn test_drifted_trial_balance_is_flagged() {
+        let lines = vec![
+            line("SETTLEMENT", AccountClass::ASSET, 1000, 0),
+            customer_line(1, Decimal::ZERO, Decimal::new(999, 0)),
+        ];
+        assert!(!build_trial_balance(Utc::now(), lines).balanced);
+    }
+}
@@ -0,0 +1,57 @@
+#[cfg(test)]
+mod closing_test {
+    use crate::chart::chart::AccountClass;
+    use crate::closing::closing::ClassTotals;
+    use crate::closing::service::build_closing;
+    use chrono::{NaiveDate, Utc};
+    use rust_decimal::Decimal;
+
+    fn totals(account_class: AccountClass, debits: i64, credits: i64) -> ClassTotals {
+        ClassTotals {
+            account_class,
+            debits: Decimal::new(debits, 0),
+            credits: Decimal::new(credits, 0),
+            entries_count: 2,
+        }
+    }
+
+    fn day() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 10, 16).unwrap()
+    }
+
+    #[test]
+    fn test_balanced_day_closing() {
+        let closing = build_closing(
+            day(),
+            vec![
+                totals(AccountClass::ASSET, 300, 100),
+                totals(AccountClass::LIABILITY, 50, 250),
+            ],
+            "ops",
+            Utc::now(),
+        );
+
+        assert!(closing.balanced);
+        assert_eq!(closing.total_debits, Decimal::new(350, 0));
+        assert_eq!(closing.total_credits, Decimal::new(350, 0));
+        assert_eq!(closing.entries_count, 4);
+    }
+
+    #[test]
+    fn test_unbalanced_day_closing_is_flagged() {
+        let closing = build_closing(
+            day(),
+            vec![totals(AccountClass::LIABILITY, 100, 90)],
+            "ops",
+            Utc::now(),
+        );
+        assert!(!closing.balanced);
+    }
+
+    #[test]
+    fn test_empty_day_closes_balanced() {
+        let closing = build_closing(day(), vec![], "system", Utc::now());
+        assert!(closing.balanced);
+        assert_eq!(closing.entries_count, 0);
+    }
+}
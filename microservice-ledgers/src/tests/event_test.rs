@@ -0,0 +1,64 @@
+#[cfg(test)]
+mod event_test {
+    use crate::event::event::{TransferEvent, TransferEventType};
+    use crate::event::service::is_transient;
+    use crate::ledger::ledger::EntryType;
+    use crate::ledger::service::validate_posting;
+    use crate::utils::error::LedgerError;
+    use rust_decimal::Decimal;
+
+    const PAYLOAD: &str = r#"{
+        "event_type": "POSTED",
+        "transfer_id": "6d1f7f6e-5f0e-4a1c-9a55-0a8f1b2c3d4e",
+        "debit_account_id": "11111111-1111-4111-8111-111111111111",
+        "credit_account_id": "22222222-2222-4222-8222-222222222222",
+        "amount": "150.25"
+    }"#;
+
+    fn event() -> TransferEvent {
+        serde_json::from_str(PAYLOAD).unwrap()
+    }
+
+    #[test]
+    fn test_event_payload_is_parsed() {
+        let event = event();
+        assert_eq!(event.event_type, TransferEventType::POSTED);
+        assert_eq!(event.amount, Decimal::new(15025, 2));
+    }
+
+    #[test]
+    fn test_unknown_event_type_is_rejected() {
+        let payload = PAYLOAD.replace("POSTED", "SETTLED");
+        assert!(serde_json::from_str::<TransferEvent>(&payload).is_err());
+    }
+
+    #[test]
+    fn test_transfer_posting_moves_money_to_receiver() {
+        let event = event();
+        let posting = event.transfer_posting();
+        assert!(validate_posting(&posting).is_ok());
+        assert_eq!(posting.entries[0].account_id, event.debit_account_id);
+        assert_eq!(posting.entries[0].entry_type, EntryType::DEBIT);
+        assert_eq!(posting.entries[1].account_id, event.credit_account_id);
+        assert_eq!(posting.entries[1].entry_type, EntryType::CREDIT);
+    }
+
+    #[test]
+    fn test_reversal_posting_gives_money_back() {
+        let event = event();
+        let posting = event.reversal_posting();
+        assert!(validate_posting(&posting).is_ok());
+        assert_eq!(posting.transfer_id, event.transfer_id);
+        assert_eq!(posting.entries[0].account_id, event.credit_account_id);
+        assert_eq!(posting.entries[0].entry_type, EntryType::DEBIT);
+        assert_eq!(posting.entries[1].account_id, event.debit_account_id);
+    }
+
+    #[test]
+    fn test_business_errors_are_not_retried() {
+        assert!(!is_transient(&LedgerError::InsufficientFunds(
+            "no".to_string()
+        )));
+        assert!(!is_transient(&LedgerError::Conflict("no".to_string())));
+    }
+}
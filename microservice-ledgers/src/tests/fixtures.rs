@@ -0,0 +1,37 @@
+use crate::balance::balance::AccountBalance;
+use crate::ledger::ledger::{EntryType, LedgerEntry};
+use chrono::Utc;
+use rust_decimal::Decimal;
+use uuid::Uuid;
+
+/// A fresh balance row with nothing held, no overdraft and no chain yet.
+pub fn account_balance(account_id: Uuid, balance: Decimal) -> AccountBalance {
+    AccountBalance {
+        account_id,
+        balance,
+        held_amount: Decimal::ZERO,
+        available_balance: balance,
+        overdraft_limit: Decimal::ZERO,
+        entries_count: 0,
+        version: 0,
+        last_entry_at: None,
+        last_entry_hash: None,
+        updated_at: Utc::now(),
+    }
+}
+
+/// An entry of its own transfer, posted now and not linked into a chain yet.
+pub fn ledger_entry(account_id: Uuid, entry_type: EntryType, amount: i64) -> LedgerEntry {
+    LedgerEntry {
+        id: Uuid::new_v4(),
+        transfer_id: Uuid::new_v4(),
+        account_id,
+        entry_type,
+        amount: Decimal::new(amount, 0),
+        created_at: Utc::now(),
+        business_date: Utc::now().date_naive(),
+        account_seq: None,
+        prev_hash: None,
+        entry_hash: None,
+    }
+}
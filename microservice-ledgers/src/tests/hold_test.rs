@@ -0,0 +1,47 @@
+#[cfg(test)]
+mod hold_test {
+    use crate::hold::hold::HoldInput;
+    use crate::hold::service::{validate_hold, HOLD_DEFAULT_TTL_SECS, HOLD_MAX_TTL_SECS};
+    use crate::tests::fixtures::account_balance;
+    use rust_decimal::Decimal;
+    use uuid::Uuid;
+
+    fn hold_input(amount: Decimal, expires_in_seconds: Option<i64>) -> HoldInput {
+        HoldInput {
+            account_id: Uuid::new_v4(),
+            transfer_id: Uuid::new_v4(),
+            amount,
+            expires_in_seconds,
+        }
+    }
+
+    #[test]
+    fn test_default_ttl() {
+        let ttl = validate_hold(&hold_input(Decimal::ONE, None)).unwrap();
+        assert_eq!(ttl.num_seconds(), HOLD_DEFAULT_TTL_SECS);
+    }
+
+    #[test]
+    fn test_invalid_holds_are_rejected() {
+        assert!(validate_hold(&hold_input(Decimal::ZERO, None)).is_err());
+        assert!(validate_hold(&hold_input(Decimal::new(1, 5), None)).is_err());
+        assert!(validate_hold(&hold_input(Decimal::ONE, Some(0))).is_err());
+        assert!(validate_hold(&hold_input(Decimal::ONE, Some(HOLD_MAX_TTL_SECS + 1))).is_err());
+    }
+
+    #[test]
+    fn test_holds_only_move_available_balance() {
+        let mut balance = account_balance(Uuid::new_v4(), Decimal::new(10000, 2));
+
+        balance.hold(Decimal::new(3000, 2));
+        assert_eq!(balance.balance, Decimal::new(10000, 2));
+        assert_eq!(balance.available_balance, Decimal::new(7000, 2));
+
+        // Capture: the hold is released and the debit hits the ledger balance.
+        balance.release(Decimal::new(3000, 2));
+        balance.apply(Decimal::new(-3000, 2));
+        assert_eq!(balance.balance, Decimal::new(7000, 2));
+        assert_eq!(balance.available_balance, Decimal::new(7000, 2));
+        assert_eq!(balance.held_amount, Decimal::ZERO);
+    }
+}
@@ -0,0 +1,122 @@
+#[cfg(test)]
+mod hot_test {
+    use crate::hot::hot::{bucket_for, bucketed_accounts, MAX_BUCKETS};
+    use crate::hot::service::fold_pending;
+    use crate::ledger::chain::ChainVerifier;
+    use crate::ledger::ledger::{EntryInput, EntryType, PostingInput};
+    use crate::tests::fixtures::{account_balance, ledger_entry};
+    use rust_decimal::Decimal;
+    use std::collections::{HashMap, HashSet};
+    use uuid::Uuid;
+
+    #[test]
+    fn test_bucket_is_within_range() {
+        for _ in 0..1000 {
+            let bucket = bucket_for(Uuid::new_v4(), 8);
+            assert!((0..8).contains(&bucket));
+        }
+    }
+
+    #[test]
+    fn test_same_transfer_uses_same_bucket() {
+        let transfer_id = Uuid::new_v4();
+        assert_eq!(bucket_for(transfer_id, 16), bucket_for(transfer_id, 16));
+    }
+
+    #[test]
+    fn test_transfers_spread_across_buckets() {
+        let used: HashSet<i32> = (0..2000)
+            .map(|_| bucket_for(Uuid::new_v4(), MAX_BUCKETS))
+            .collect();
+        assert_eq!(used.len(), MAX_BUCKETS as usize);
+    }
+
+    #[test]
+    fn test_unfolded_credits_count_towards_available_balance() {
+        let mut balance = account_balance(Uuid::new_v4(), Decimal::new(100, 0));
+        balance.hold(Decimal::new(30, 0));
+        balance.entries_count = 4;
+
+        balance.include_unfolded(Decimal::new(25, 0), 3);
+
+        assert_eq!(balance.balance, Decimal::new(125, 0));
+        assert_eq!(balance.available_balance, Decimal::new(95, 0));
+        assert_eq!(balance.entries_count, 7);
+    }
+
+    #[test]
+    fn test_only_credited_hot_accounts_are_bucketed() {
+        let (payer, merchant, settlement) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let entry = |account_id, entry_type| EntryInput {
+            account_id,
+            entry_type,
+            amount: Decimal::new(10, 0),
+        };
+        let input = PostingInput {
+            transfer_id: Uuid::new_v4(),
+            entries: vec![
+                entry(payer, EntryType::DEBIT),
+                entry(merchant, EntryType::CREDIT),
+                entry(settlement, EntryType::DEBIT),
+                entry(settlement, EntryType::CREDIT),
+            ],
+        };
+        let hot: HashMap<Uuid, i32> = [(merchant, 8), (settlement, 4)].into_iter().collect();
+
+        let bucketed = bucketed_accounts(&input, &hot);
+
+        assert_eq!(bucketed.len(), 1);
+        assert_eq!(bucketed[&merchant], bucket_for(input.transfer_id, 8));
+    }
+
+    #[test]
+    fn test_fold_moves_pending_credits_into_the_balance() {
+        let account_id = Uuid::new_v4();
+        let mut balance = account_balance(account_id, Decimal::new(100, 0));
+        balance.entries_count = 4;
+        balance.version = 2;
+        balance.last_entry_hash = Some("a".repeat(64));
+        let mut pending: Vec<_> = [10, 20, 30]
+            .into_iter()
+            .map(|amount| ledger_entry(account_id, EntryType::CREDIT, amount))
+            .collect();
+
+        let previous_count = fold_pending(&mut balance, &mut pending);
+
+        assert_eq!(previous_count, 4);
+        assert_eq!(balance.balance, Decimal::new(160, 0));
+        assert_eq!(balance.available_balance, Decimal::new(160, 0));
+        assert_eq!(balance.entries_count, 7);
+        assert_eq!(balance.version, 3);
+        assert_eq!(balance.last_entry_hash, pending[2].entry_hash);
+    }
+
+    #[test]
+    fn test_fold_keeps_posting_order_in_the_chain() {
+        let account_id = Uuid::new_v4();
+        let mut balance = account_balance(account_id, Decimal::ZERO);
+        balance.entries_count = 4;
+        balance.last_entry_hash = Some("a".repeat(64));
+        let mut pending: Vec<_> = [10, 20, 30]
+            .into_iter()
+            .map(|amount| ledger_entry(account_id, EntryType::CREDIT, amount))
+            .collect();
+        let posting_order: Vec<Uuid> = pending.iter().map(|e| e.id).collect();
+
+        fold_pending(&mut balance, &mut pending);
+
+        assert_eq!(
+            pending.iter().map(|e| e.id).collect::<Vec<_>>(),
+            posting_order
+        );
+        assert_eq!(
+            pending.iter().map(|e| e.account_seq).collect::<Vec<_>>(),
+            vec![Some(5), Some(6), Some(7)]
+        );
+        let mut verifier = ChainVerifier::resume(4, "a".repeat(64));
+        for entry in &pending {
+            verifier.check(entry).unwrap();
+        }
+        assert!(verifier.finish(balance.last_entry_hash.as_deref()).is_ok());
+    }
+}
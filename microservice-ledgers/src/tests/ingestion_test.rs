@@ -0,0 +1,249 @@
+#[cfg(test)]
+mod ingestion_test {
+    use crate::balance::balance::AccountBalance;
+    use crate::chart::persistence::find_ids_by_codes;
+    use crate::ledger::batch::{plan_batch, BatchContext, BatchPlan};
+    use crate::ledger::ledger::{
+        BatchPostingStatus, EntryInput, EntryRequest, EntryType, PostingInput, PostingRequest,
+    };
+    use crate::ledger::service::{post_batch, post_request};
+    use crate::tests::fixtures::account_balance;
+    use chrono::Utc;
+    use rust_decimal::Decimal;
+    use std::collections::{HashMap, HashSet};
+    use std::time::Instant;
+    use uuid::Uuid;
+
+    fn balance(account_id: Uuid, available: i64) -> AccountBalance {
+        account_balance(account_id, Decimal::new(available, 0))
+    }
+
+    fn transfer(from: Uuid, to: Uuid, amount: i64) -> PostingInput {
+        PostingInput {
+            transfer_id: Uuid::new_v4(),
+            entries: vec![
+                EntryInput {
+                    account_id: from,
+                    entry_type: EntryType::DEBIT,
+                    amount: Decimal::new(amount, 0),
+                },
+                EntryInput {
+                    account_id: to,
+                    entry_type: EntryType::CREDIT,
+                    amount: Decimal::new(amount, 0),
+                },
+            ],
+        }
+    }
+
+    fn plan(
+        postings: &[PostingInput],
+        balances: Vec<AccountBalance>,
+        posted_transfers: &HashSet<Uuid>,
+    ) -> BatchPlan {
+        plan_with_blocked(postings, balances, posted_transfers, &HashSet::new())
+    }
+
+    fn plan_with_blocked(
+        postings: &[PostingInput],
+        balances: Vec<AccountBalance>,
+        posted_transfers: &HashSet<Uuid>,
+        blocked_accounts: &HashSet<Uuid>,
+    ) -> BatchPlan {
+        plan_batch(
+            postings,
+            BatchContext {
+                balances: balances.into_iter().map(|b| (b.account_id, b)).collect(),
+                internal_accounts: &HashSet::new(),
+                blocked_accounts,
+                posted_transfers,
+                posted_at: Utc::now(),
+            },
+        )
+    }
+
+    fn statuses(plan: &BatchPlan) -> Vec<BatchPostingStatus> {
+        plan.results.iter().map(|r| r.status).collect()
+    }
+
+    #[test]
+    fn test_funds_are_checked_against_earlier_postings_of_the_batch() {
+        let (payer, payee) = (Uuid::new_v4(), Uuid::new_v4());
+        let postings = vec![
+            transfer(payer, payee, 60),
+            transfer(payer, payee, 60),
+            transfer(payer, payee, 40),
+        ];
+
+        let plan = plan(
+            &postings,
+            vec![balance(payer, 100), balance(payee, 0)],
+            &HashSet::new(),
+        );
+
+        use BatchPostingStatus::{POSTED, REJECTED};
+        assert_eq!(statuses(&plan), vec![POSTED, REJECTED, POSTED]);
+        assert_eq!(plan.entries.len(), 4);
+        assert_eq!(
+            plan.results[1].error.as_ref().unwrap().error,
+            "insufficient_funds"
+        );
+    }
+
+    #[test]
+    fn test_chain_continues_across_postings_of_the_batch() {
+        let (payer, payee) = (Uuid::new_v4(), Uuid::new_v4());
+        let postings = vec![transfer(payer, payee, 10), transfer(payer, payee, 10)];
+
+        let plan = plan(
+            &postings,
+            vec![balance(payer, 100), balance(payee, 0)],
+            &HashSet::new(),
+        );
+
+        let payee_entries: Vec<_> = plan
+            .entries
+            .iter()
+            .filter(|e| e.account_id == payee)
+            .collect();
+        assert_eq!(payee_entries[0].account_seq, Some(1));
+        assert_eq!(payee_entries[1].account_seq, Some(2));
+        assert_eq!(payee_entries[1].prev_hash, payee_entries[0].entry_hash);
+    }
+
+    #[test]
+    fn test_duplicate_and_already_posted_transfers_are_rejected() {
+        let (payer, payee) = (Uuid::new_v4(), Uuid::new_v4());
+        let first = transfer(payer, payee, 10);
+        let already_posted = transfer(payer, payee, 10);
+        let duplicate = PostingInput {
+            transfer_id: first.transfer_id,
+            ..transfer(payer, payee, 10)
+        };
+        let posted: HashSet<Uuid> = [already_posted.transfer_id].into_iter().collect();
+
+        let plan = plan(
+            &[first, duplicate, already_posted],
+            vec![balance(payer, 100), balance(payee, 0)],
+            &posted,
+        );
+
+        use BatchPostingStatus::{POSTED, REJECTED};
+        assert_eq!(statuses(&plan), vec![POSTED, REJECTED, REJECTED]);
+        assert_eq!(plan.entries.len(), 2);
+    }
+
+    #[test]
+    fn test_invalid_postings_do_not_stop_the_batch() {
+        let (payer, payee) = (Uuid::new_v4(), Uuid::new_v4());
+        let mut unbalanced = transfer(payer, payee, 10);
+        unbalanced.entries[1].amount = Decimal::new(9, 0);
+        let mut zero = transfer(payer, payee, 10);
+        zero.entries[0].amount = Decimal::ZERO;
+        zero.entries[1].amount = Decimal::ZERO;
+
+        let plan = plan(
+            &[unbalanced, zero, transfer(payer, payee, 10)],
+            vec![balance(payer, 100), balance(payee, 0)],
+            &HashSet::new(),
+        );
+
+        use BatchPostingStatus::{POSTED, REJECTED};
+        assert_eq!(statuses(&plan), vec![REJECTED, REJECTED, POSTED]);
+    }
+
+    #[test]
+    fn test_postings_touching_a_blocked_account_are_rejected() {
+        let (payer, payee, blocked) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let blocked_accounts: HashSet<Uuid> = [blocked].into_iter().collect();
+
+        let plan = plan_with_blocked(
+            &[
+                transfer(blocked, payee, 10),
+                transfer(payer, blocked, 10),
+                transfer(payer, payee, 10),
+            ],
+            vec![
+                balance(payer, 100),
+                balance(payee, 0),
+                balance(blocked, 100),
+            ],
+            &HashSet::new(),
+            &blocked_accounts,
+        );
+
+        use BatchPostingStatus::{POSTED, REJECTED};
+        assert_eq!(statuses(&plan), vec![REJECTED, REJECTED, POSTED]);
+        assert_eq!(plan.entries.len(), 2);
+        assert_eq!(
+            plan.results[0].error.as_ref().unwrap().error,
+            "account_blocked"
+        );
+    }
+
+    fn settlement_payouts(settlement: Uuid, count: usize) -> Vec<PostingRequest> {
+        (0..count)
+            .map(|_| {
+                let entry = |account_id, entry_type| EntryRequest {
+                    account_id: Some(account_id),
+                    account_code: None,
+                    entry_type,
+                    amount: Decimal::new(1050, 2),
+                };
+                PostingRequest {
+                    transfer_id: Uuid::new_v4(),
+                    entries: vec![
+                        entry(settlement, EntryType::DEBIT),
+                        entry(Uuid::new_v4(), EntryType::CREDIT),
+                    ],
+                }
+            })
+            .collect()
+    }
+
+    /// Payouts from the settlement account to fresh accounts, posted one
+    /// transaction per transfer and then in COPY batches.
+    #[tokio::test]
+    #[ignore = "needs the ledgers database: cargo test bench_ -- --ignored --nocapture"]
+    async fn bench_batch_copy_vs_row_by_row() {
+        const POSTINGS: usize = 5000;
+        const BATCH_SIZE: usize = 1000;
+
+        let codes: HashMap<String, Uuid> = find_ids_by_codes(&["SETTLEMENT".to_string()])
+            .await
+            .unwrap()
+            .into_iter()
+            .collect();
+        let settlement = codes["SETTLEMENT"];
+
+        let started = Instant::now();
+        for request in settlement_payouts(settlement, POSTINGS) {
+            post_request(request).await.unwrap();
+        }
+        let row_by_row = started.elapsed();
+
+        let started = Instant::now();
+        for _ in 0..POSTINGS / BATCH_SIZE {
+            let report = post_batch(settlement_payouts(settlement, BATCH_SIZE))
+                .await
+                .unwrap();
+            assert_eq!(report.posted, BATCH_SIZE);
+        }
+        let batched = started.elapsed();
+
+        let rate = |elapsed: std::time::Duration| POSTINGS as f64 / elapsed.as_secs_f64();
+        println!(
+            "row-by-row: {} postings in {:?} ({:.0}/s)",
+            POSTINGS,
+            row_by_row,
+            rate(row_by_row)
+        );
+        println!(
+            "batch COPY: {} postings in {:?} ({:.0}/s, {} per batch)",
+            POSTINGS,
+            batched,
+            rate(batched),
+            BATCH_SIZE
+        );
+    }
+}
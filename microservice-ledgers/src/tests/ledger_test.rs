@@ -0,0 +1,294 @@
+#[cfg(test)]
+mod ledger_test {
+    use crate::balance::balance::AccountBalance;
+    use crate::balance::service::{crosses_snapshot_boundary, SNAPSHOT_EVERY_ENTRIES};
+    use crate::ledger::chain::{link_entries, ChainVerifier};
+    use crate::ledger::ledger::{EntryInput, EntryType, LedgerEntry, PostingInput};
+    use crate::ledger::service::{check_funds, check_not_blocked, validate_posting};
+    use crate::tests::fixtures::{account_balance, ledger_entry};
+    use crate::utils::error::LedgerError;
+    use rust_decimal::Decimal;
+    use std::collections::HashSet;
+    use uuid::Uuid;
+
+    fn entry(entry_type: EntryType, amount: &str) -> EntryInput {
+        EntryInput {
+            account_id: Uuid::new_v4(),
+            entry_type,
+            amount: amount.parse().unwrap(),
+        }
+    }
+
+    fn posting(entries: Vec<EntryInput>) -> PostingInput {
+        PostingInput {
+            transfer_id: Uuid::new_v4(),
+            entries,
+        }
+    }
+
+    #[test]
+    fn test_balanced_posting_is_valid() {
+        let input = posting(vec![
+            entry(EntryType::DEBIT, "100.50"),
+            entry(EntryType::CREDIT, "100.00"),
+            entry(EntryType::CREDIT, "0.50"),
+        ]);
+        assert!(validate_posting(&input).is_ok());
+    }
+
+    #[test]
+    fn test_unbalanced_posting_is_rejected() {
+        let input = posting(vec![
+            entry(EntryType::DEBIT, "100.00"),
+            entry(EntryType::CREDIT, "99.99"),
+        ]);
+        assert!(validate_posting(&input).is_err());
+    }
+
+    #[test]
+    fn test_single_entry_posting_is_rejected() {
+        let input = posting(vec![entry(EntryType::CREDIT, "10.00")]);
+        assert!(validate_posting(&input).is_err());
+    }
+
+    #[test]
+    fn test_non_positive_amounts_are_rejected() {
+        let input = posting(vec![
+            entry(EntryType::DEBIT, "0"),
+            entry(EntryType::CREDIT, "0"),
+        ]);
+        assert!(validate_posting(&input).is_err());
+    }
+
+    fn balance(available: i64, overdraft_limit: i64) -> AccountBalance {
+        let mut balance = account_balance(Uuid::new_v4(), Decimal::new(available, 0));
+        balance.overdraft_limit = Decimal::new(overdraft_limit, 0);
+        balance
+    }
+
+    #[test]
+    fn test_debit_within_balance_is_accepted() {
+        let payer = balance(100, 0);
+        let entries = vec![ledger_entry(payer.account_id, EntryType::DEBIT, 100)];
+        assert!(check_funds(&[payer], &entries).is_ok());
+    }
+
+    #[test]
+    fn test_debit_beyond_balance_is_insufficient_funds() {
+        let payer = balance(100, 0);
+        let entries = vec![ledger_entry(payer.account_id, EntryType::DEBIT, 101)];
+        assert!(matches!(
+            check_funds(&[payer], &entries),
+            Err(LedgerError::InsufficientFunds(_))
+        ));
+    }
+
+    #[test]
+    fn test_overdraft_limit_extends_spendable_amount() {
+        let payer = balance(100, 50);
+        let within = vec![ledger_entry(payer.account_id, EntryType::DEBIT, 150)];
+        let beyond = vec![ledger_entry(payer.account_id, EntryType::DEBIT, 151)];
+        assert!(check_funds(std::slice::from_ref(&payer), &within).is_ok());
+        assert!(check_funds(&[payer], &beyond).is_err());
+    }
+
+    #[test]
+    fn test_receiving_account_is_not_checked() {
+        let receiver = balance(-10, 0);
+        let entries = vec![ledger_entry(receiver.account_id, EntryType::CREDIT, 5)];
+        assert!(check_funds(&[receiver], &entries).is_ok());
+    }
+
+    fn blocked(account_id: Uuid) -> HashSet<Uuid> {
+        [account_id].into_iter().collect()
+    }
+
+    #[test]
+    fn test_transfer_from_blocked_account_is_refused() {
+        let input = posting(vec![
+            entry(EntryType::DEBIT, "10.00"),
+            entry(EntryType::CREDIT, "10.00"),
+        ]);
+        assert!(matches!(
+            check_not_blocked(&input, &blocked(input.entries[0].account_id)),
+            Err(LedgerError::AccountBlocked(_))
+        ));
+    }
+
+    #[test]
+    fn test_transfer_to_blocked_account_is_refused() {
+        let input = posting(vec![
+            entry(EntryType::DEBIT, "10.00"),
+            entry(EntryType::CREDIT, "10.00"),
+        ]);
+        assert!(matches!(
+            check_not_blocked(&input, &blocked(input.entries[1].account_id)),
+            Err(LedgerError::AccountBlocked(_))
+        ));
+    }
+
+    #[test]
+    fn test_transfer_between_unblocked_accounts_is_accepted() {
+        let input = posting(vec![
+            entry(EntryType::DEBIT, "10.00"),
+            entry(EntryType::CREDIT, "10.00"),
+        ]);
+        assert!(check_not_blocked(&input, &blocked(Uuid::new_v4())).is_ok());
+    }
+
+    #[test]
+    fn test_signed_amounts() {
+        let amount = Decimal::new(1050, 2);
+        assert_eq!(EntryType::CREDIT.signed(amount), amount);
+        assert_eq!(EntryType::DEBIT.signed(amount), -amount);
+    }
+
+    #[test]
+    fn test_snapshot_boundary() {
+        assert!(!crosses_snapshot_boundary(0, 1));
+        assert!(crosses_snapshot_boundary(
+            SNAPSHOT_EVERY_ENTRIES - 1,
+            SNAPSHOT_EVERY_ENTRIES
+        ));
+        assert!(crosses_snapshot_boundary(
+            SNAPSHOT_EVERY_ENTRIES - 1,
+            SNAPSHOT_EVERY_ENTRIES + 2
+        ));
+        assert!(!crosses_snapshot_boundary(
+            SNAPSHOT_EVERY_ENTRIES,
+            SNAPSHOT_EVERY_ENTRIES + 1
+        ));
+    }
+
+    fn chained(account_id: Uuid, count: usize) -> Vec<LedgerEntry> {
+        let mut entries: Vec<LedgerEntry> = (0..count)
+            .map(|i| ledger_entry(account_id, EntryType::CREDIT, i as i64 + 1))
+            .collect();
+        link_entries(&mut entries, &[]);
+        entries
+    }
+
+    fn verify(entries: &[LedgerEntry]) -> Result<(), Option<i64>> {
+        let mut verifier = ChainVerifier::default();
+        for entry in entries {
+            verifier.check(entry).map_err(|link| link.account_seq)?;
+        }
+        let tip = entries.last().and_then(|e| e.entry_hash.clone());
+        verifier
+            .finish(tip.as_deref())
+            .map_err(|link| link.account_seq)
+    }
+
+    #[test]
+    fn test_link_entries_continues_from_balance_tip() {
+        let account_id = Uuid::new_v4();
+        let mut tip = balance(0, 0);
+        tip.account_id = account_id;
+        tip.entries_count = 7;
+        tip.last_entry_hash = Some("a".repeat(64));
+
+        let mut entries = vec![
+            ledger_entry(account_id, EntryType::CREDIT, 10),
+            ledger_entry(account_id, EntryType::DEBIT, 4),
+        ];
+        link_entries(&mut entries, std::slice::from_ref(&tip));
+
+        assert_eq!(entries[0].account_seq, Some(8));
+        assert_eq!(entries[0].prev_hash, tip.last_entry_hash);
+        assert_eq!(entries[1].account_seq, Some(9));
+        assert_eq!(entries[1].prev_hash, entries[0].entry_hash);
+    }
+
+    #[test]
+    fn test_intact_chain_verifies() {
+        assert_eq!(verify(&chained(Uuid::new_v4(), 5)), Ok(()));
+    }
+
+    #[test]
+    fn test_tampered_amount_breaks_chain() {
+        let mut entries = chained(Uuid::new_v4(), 5);
+        entries[2].amount = Decimal::new(1_000, 0);
+        assert_eq!(verify(&entries), Err(Some(3)));
+    }
+
+    #[test]
+    fn test_moved_business_date_breaks_chain() {
+        let mut entries = chained(Uuid::new_v4(), 3);
+        entries[1].business_date = entries[1].business_date.pred_opt().unwrap();
+        assert_eq!(verify(&entries), Err(Some(2)));
+    }
+
+    #[test]
+    fn test_removed_entry_breaks_chain() {
+        let mut entries = chained(Uuid::new_v4(), 5);
+        entries.remove(1);
+        assert_eq!(verify(&entries), Err(Some(3)));
+    }
+
+    #[test]
+    fn test_removed_first_entry_breaks_chain() {
+        let mut entries = chained(Uuid::new_v4(), 5);
+        entries.remove(0);
+        assert_eq!(verify(&entries), Err(Some(2)));
+    }
+
+    #[test]
+    fn test_chain_starts_after_its_origin() {
+        let account_id = Uuid::new_v4();
+        let mut legacy = balance(0, 0);
+        legacy.account_id = account_id;
+        legacy.entries_count = 3;
+
+        let mut entries: Vec<LedgerEntry> = (0..3)
+            .map(|i| ledger_entry(account_id, EntryType::CREDIT, i + 1))
+            .collect();
+        link_entries(&mut entries, std::slice::from_ref(&legacy));
+
+        let mut verifier = ChainVerifier::from_origin(3);
+        for entry in &entries {
+            verifier.check(entry).unwrap();
+        }
+        assert!(verifier.finish(entries[2].entry_hash.as_deref()).is_ok());
+
+        let mut verifier = ChainVerifier::from_origin(3);
+        let link = verifier.check(&entries[1]).unwrap_err();
+        assert_eq!(link.account_seq, Some(5));
+    }
+
+    #[test]
+    fn test_gap_after_archived_tip_breaks_chain() {
+        let entries = chained(Uuid::new_v4(), 5);
+        let archived_tip = &entries[1];
+        let mut verifier = ChainVerifier::resume(
+            archived_tip.account_seq.unwrap(),
+            archived_tip.entry_hash.clone().unwrap(),
+        );
+        let link = verifier.check(&entries[3]).unwrap_err();
+        assert_eq!(link.account_seq, Some(4));
+    }
+
+    #[test]
+    fn test_chain_resumes_after_archived_entries() {
+        let entries = chained(Uuid::new_v4(), 5);
+        let archived_tip = &entries[2];
+        let mut verifier = ChainVerifier::resume(
+            archived_tip.account_seq.unwrap(),
+            archived_tip.entry_hash.clone().unwrap(),
+        );
+        for entry in &entries[3..] {
+            verifier.check(entry).unwrap();
+        }
+        assert!(verifier.finish(entries[4].entry_hash.as_deref()).is_ok());
+        assert_eq!(verifier.entries_checked, 2);
+    }
+
+    #[test]
+    fn test_removed_tail_is_detected() {
+        let entries = chained(Uuid::new_v4(), 5);
+        let mut verifier = ChainVerifier::default();
+        for entry in &entries[..3] {
+            verifier.check(entry).unwrap();
+        }
+        assert!(verifier.finish(entries[4].entry_hash.as_deref()).is_err());
+    }
+}
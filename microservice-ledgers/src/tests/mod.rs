@@ -1,2 +1,28 @@
 #[cfg(test)]
+mod balance_test;
+#[cfg(test)]
+mod chart_test;
+#[cfg(test)]
+mod closing_test;
+#[cfg(test)]
+mod event_test;
+#[cfg(test)]
+mod fixtures;
+#[cfg(test)]
+mod hold_test;
+#[cfg(test)]
+mod hot_test;
+#[cfg(test)]
+mod ingestion_test;
+#[cfg(test)]
+mod ledger_test;
+#[cfg(test)]
+mod partition_test;
+#[cfg(test)]
 mod products_test;
+#[cfg(test)]
+mod rebuild_test;
+#[cfg(test)]
+mod reconciliation_test;
+#[cfg(test)]
+mod statement_test;
@@ -0,0 +1,47 @@
+#[cfg(test)]
+mod partition_test {
+    use crate::partition::partition::{archive_file_name, partition_name, partition_range};
+    use chrono::{NaiveDate, TimeZone, Utc};
+
+    #[test]
+    fn test_partition_name_is_year_and_month() {
+        let day = NaiveDate::from_ymd_opt(2026, 3, 17).unwrap();
+        assert_eq!(partition_name(day), "ledger_entries_p202603");
+    }
+
+    #[test]
+    fn test_partition_name_round_trips_through_its_range() {
+        let (start, _) = partition_range("ledger_entries_p202511").unwrap();
+        assert_eq!(partition_name(start.date_naive()), "ledger_entries_p202511");
+    }
+
+    #[test]
+    fn test_partition_range_covers_utc_month() {
+        let (start, end) = partition_range("ledger_entries_p202602").unwrap();
+        assert_eq!(start, Utc.with_ymd_and_hms(2026, 2, 1, 0, 0, 0).unwrap());
+        assert_eq!(end, Utc.with_ymd_and_hms(2026, 3, 1, 0, 0, 0).unwrap());
+    }
+
+    #[test]
+    fn test_december_partition_ends_next_year() {
+        let (_, end) = partition_range("ledger_entries_p202612").unwrap();
+        assert_eq!(end, Utc.with_ymd_and_hms(2027, 1, 1, 0, 0, 0).unwrap());
+    }
+
+    #[test]
+    fn test_foreign_names_are_rejected() {
+        assert!(partition_range("ledger_entries").is_none());
+        assert!(partition_range("ledger_entries_p202613").is_none());
+        assert!(partition_range("ledger_entries_p2026011").is_none());
+        assert!(partition_range("ledger_entries_p2026; DROP TABLE x").is_none());
+        assert!(partition_range("balance_snapshots_p202601").is_none());
+    }
+
+    #[test]
+    fn test_archive_file_name() {
+        assert_eq!(
+            archive_file_name("ledger_entries_p202601"),
+            "ledger_entries_p202601.csv.gz"
+        );
+    }
+}
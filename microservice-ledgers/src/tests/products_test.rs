@@ -6,6 +6,7 @@ mod products_test {
     async fn test_find_all_returns_products() {
         let result = persistence::find_all().await;
         assert!(result.is_ok());
+        let products = result.unwrap();
         assert!(products.len() >= 0);
     }
 
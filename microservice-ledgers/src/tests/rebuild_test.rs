@@ -0,0 +1,132 @@
+#[cfg(test)]
+mod rebuild_test {
+    use crate::balance::service::SNAPSHOT_EVERY_ENTRIES;
+    use crate::ledger::ledger::{EntryType, LedgerEntry};
+    use crate::rebuild::rebuild::{AccountReplay, RebuildScope, ReplayBaseline};
+    use chrono::{DateTime, Duration, Utc};
+    use rust_decimal::Decimal;
+    use uuid::Uuid;
+
+    fn entry(
+        account_id: Uuid,
+        entry_type: EntryType,
+        amount: i64,
+        at: DateTime<Utc>,
+    ) -> LedgerEntry {
+        LedgerEntry {
+            id: Uuid::new_v4(),
+            transfer_id: Uuid::new_v4(),
+            account_id,
+            entry_type,
+            amount: Decimal::new(amount, 0),
+            created_at: at,
+            business_date: at.date_naive(),
+            account_seq: None,
+            prev_hash: None,
+            entry_hash: Some(format!("{:064}", amount)),
+        }
+    }
+
+    #[test]
+    fn test_scope_round_trip() {
+        let account_id = Uuid::new_v4();
+        for scope in [
+            RebuildScope::All,
+            RebuildScope::Account(account_id),
+            RebuildScope::Shard { index: 3, count: 8 },
+        ] {
+            assert_eq!(scope.to_string().parse::<RebuildScope>(), Ok(scope));
+        }
+    }
+
+    #[test]
+    fn test_invalid_scopes_are_rejected() {
+        for scope in [
+            "",
+            "everything",
+            "account:42",
+            "shard:8/8",
+            "shard:1",
+            "shard:0/0",
+        ] {
+            assert!(scope.parse::<RebuildScope>().is_err(), "{}", scope);
+        }
+    }
+
+    #[test]
+    fn test_shard_predicate() {
+        assert_eq!(
+            RebuildScope::Shard { index: 1, count: 4 }.predicate("account_id"),
+            "mod(hashtext(account_id::text)::bigint + 2147483648, 4) = 1"
+        );
+        assert_eq!(RebuildScope::All.predicate("account_id"), "TRUE");
+    }
+
+    #[test]
+    fn test_replay_sums_entries_from_baseline() {
+        let account_id = Uuid::new_v4();
+        let now = Utc::now();
+        let mut replay = AccountReplay::new(
+            account_id,
+            Some(ReplayBaseline {
+                balance: Decimal::new(500, 0),
+                entries_count: 40,
+                entry_hash: Some("a".repeat(64)),
+            }),
+        );
+
+        replay.push(&entry(account_id, EntryType::CREDIT, 100, now));
+        replay.push(&entry(
+            account_id,
+            EntryType::DEBIT,
+            30,
+            now + Duration::seconds(1),
+        ));
+        let rebuilt = replay.finish();
+
+        assert_eq!(rebuilt.balance, Decimal::new(570, 0));
+        assert_eq!(rebuilt.entries_count, 42);
+        assert_eq!(rebuilt.last_entry_hash, Some(format!("{:064}", 30)));
+        assert!(rebuilt.snapshots.is_empty());
+    }
+
+    #[test]
+    fn test_account_without_entries_keeps_baseline() {
+        let rebuilt = AccountReplay::new(Uuid::new_v4(), None).finish();
+        assert_eq!(rebuilt.balance, Decimal::ZERO);
+        assert_eq!(rebuilt.entries_count, 0);
+        assert_eq!(rebuilt.last_entry_hash, None);
+    }
+
+    #[test]
+    fn test_snapshot_is_taken_after_the_posting_that_crosses_the_boundary() {
+        let account_id = Uuid::new_v4();
+        let start = Utc::now();
+        let mut replay = AccountReplay::new(
+            account_id,
+            Some(ReplayBaseline {
+                balance: Decimal::ZERO,
+                entries_count: SNAPSHOT_EVERY_ENTRIES - 1,
+                entry_hash: None,
+            }),
+        );
+
+        // One posting with two entries on the account crosses the boundary.
+        let posted_at = start + Duration::seconds(1);
+        replay.push(&entry(account_id, EntryType::CREDIT, 10, posted_at));
+        replay.push(&entry(account_id, EntryType::CREDIT, 5, posted_at));
+        replay.push(&entry(
+            account_id,
+            EntryType::DEBIT,
+            1,
+            start + Duration::seconds(2),
+        ));
+        let rebuilt = replay.finish();
+
+        assert_eq!(rebuilt.snapshots.len(), 1);
+        let snapshot = &rebuilt.snapshots[0];
+        assert_eq!(snapshot.taken_at, posted_at);
+        assert_eq!(snapshot.balance, Decimal::new(15, 0));
+        assert_eq!(snapshot.entries_count, SNAPSHOT_EVERY_ENTRIES + 1);
+    }
+}
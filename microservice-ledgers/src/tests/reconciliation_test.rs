@@ -0,0 +1,155 @@
+#[cfg(test)]
+mod reconciliation_test {
+    use crate::ledger::ledger::{EntryType, LedgerEntry};
+    use crate::reconciliation::reconciliation::{IssueKind, TransferRecord};
+    use crate::reconciliation::service::{check_transfer_exists, match_transfer};
+    use chrono::Utc;
+    use rust_decimal::Decimal;
+    use uuid::Uuid;
+
+    fn transfer(amount: i64) -> TransferRecord {
+        TransferRecord {
+            id: Uuid::new_v4(),
+            debit_account_id: Uuid::new_v4(),
+            credit_account_id: Uuid::new_v4(),
+            amount: Decimal::new(amount, 0),
+            status: "POSTED".to_string(),
+        }
+    }
+
+    fn entry(
+        transfer: &TransferRecord,
+        account_id: Uuid,
+        entry_type: EntryType,
+        amount: i64,
+    ) -> LedgerEntry {
+        LedgerEntry {
+            id: Uuid::new_v4(),
+            transfer_id: transfer.id,
+            account_id,
+            entry_type,
+            amount: Decimal::new(amount, 0),
+            created_at: Utc::now(),
+            business_date: Utc::now().date_naive(),
+            account_seq: None,
+            prev_hash: None,
+            entry_hash: None,
+        }
+    }
+
+    fn issues(transfer: &TransferRecord, entries: &[LedgerEntry]) -> Vec<IssueKind> {
+        match_transfer(transfer, entries)
+            .into_iter()
+            .map(|d| d.issue)
+            .collect()
+    }
+
+    #[test]
+    fn test_balanced_transfer_matches() {
+        let t = transfer(100);
+        let entries = vec![
+            entry(&t, t.debit_account_id, EntryType::DEBIT, 100),
+            entry(&t, t.credit_account_id, EntryType::CREDIT, 100),
+        ];
+        assert!(match_transfer(&t, &entries).is_empty());
+    }
+
+    #[test]
+    fn test_missing_sides_are_flagged() {
+        let t = transfer(100);
+        assert_eq!(
+            issues(&t, &[]),
+            vec![IssueKind::MissingEntry, IssueKind::MissingEntry]
+        );
+
+        let debit_only = vec![entry(&t, t.debit_account_id, EntryType::DEBIT, 100)];
+        let found = match_transfer(&t, &debit_only);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].side, Some(EntryType::CREDIT));
+    }
+
+    #[test]
+    fn test_duplicate_side_is_flagged() {
+        let t = transfer(100);
+        let entries = vec![
+            entry(&t, t.debit_account_id, EntryType::DEBIT, 100),
+            entry(&t, t.debit_account_id, EntryType::DEBIT, 100),
+            entry(&t, t.credit_account_id, EntryType::CREDIT, 100),
+        ];
+        let found = match_transfer(&t, &entries);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].issue, IssueKind::DuplicateEntry);
+        assert_eq!(found[0].actual_amount, Some(Decimal::new(200, 0)));
+    }
+
+    #[test]
+    fn test_amount_mismatch_is_flagged() {
+        let t = transfer(100);
+        let entries = vec![
+            entry(&t, t.debit_account_id, EntryType::DEBIT, 100),
+            entry(&t, t.credit_account_id, EntryType::CREDIT, 90),
+        ];
+        assert_eq!(issues(&t, &entries), vec![IssueKind::AmountMismatch]);
+    }
+
+    #[test]
+    fn test_entry_outside_transfer_sides_is_flagged() {
+        let t = transfer(100);
+        let entries = vec![
+            entry(&t, t.debit_account_id, EntryType::DEBIT, 100),
+            entry(&t, t.credit_account_id, EntryType::CREDIT, 100),
+            entry(&t, t.debit_account_id, EntryType::CREDIT, 5),
+        ];
+        assert_eq!(issues(&t, &entries), vec![IssueKind::UnexpectedEntry]);
+    }
+
+    #[test]
+    fn test_reversed_transfer_needs_posting_and_reversal() {
+        let mut t = transfer(100);
+        t.status = "REVERSED".to_string();
+        let mut entries = vec![
+            entry(&t, t.debit_account_id, EntryType::DEBIT, 100),
+            entry(&t, t.credit_account_id, EntryType::CREDIT, 100),
+            entry(&t, t.credit_account_id, EntryType::DEBIT, 100),
+            entry(&t, t.debit_account_id, EntryType::CREDIT, 100),
+        ];
+        assert!(match_transfer(&t, &entries).is_empty());
+
+        entries.truncate(2);
+        let missing = match_transfer(&t, &entries);
+        assert_eq!(missing.len(), 2);
+        assert!(missing.iter().all(|d| d.issue == IssueKind::MissingEntry));
+        assert_eq!(missing[0].account_id, Some(t.credit_account_id));
+        assert_eq!(missing[1].account_id, Some(t.debit_account_id));
+    }
+
+    #[test]
+    fn test_reversal_entries_on_posted_transfer_are_flagged() {
+        let t = transfer(100);
+        let entries = vec![
+            entry(&t, t.debit_account_id, EntryType::DEBIT, 100),
+            entry(&t, t.credit_account_id, EntryType::CREDIT, 100),
+            entry(&t, t.credit_account_id, EntryType::DEBIT, 100),
+            entry(&t, t.debit_account_id, EntryType::CREDIT, 100),
+        ];
+        assert_eq!(
+            issues(&t, &entries),
+            vec![IssueKind::UnexpectedEntry, IssueKind::UnexpectedEntry]
+        );
+    }
+
+    #[test]
+    fn test_ledger_entries_need_a_posted_transfer() {
+        let mut t = transfer(100);
+        assert!(check_transfer_exists(t.id, Some(&t)).is_none());
+
+        t.status = "REVERSED".to_string();
+        assert!(check_transfer_exists(t.id, Some(&t)).is_none());
+
+        t.status = "FAILED".to_string();
+        assert!(check_transfer_exists(t.id, Some(&t)).is_some());
+
+        let orphan = check_transfer_exists(Uuid::new_v4(), None).unwrap();
+        assert_eq!(orphan.issue, IssueKind::MissingTransfer);
+    }
+}
@@ -0,0 +1,29 @@
+#[cfg(test)]
+mod statement_test {
+    use crate::statement::statement::PeriodType;
+    use chrono::NaiveDate;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_daily_bounds() {
+        assert_eq!(
+            PeriodType::DAILY.bounds(date(2025, 8, 31)),
+            (date(2025, 8, 31), date(2025, 9, 1))
+        );
+    }
+
+    #[test]
+    fn test_monthly_bounds() {
+        assert_eq!(
+            PeriodType::MONTHLY.bounds(date(2025, 8, 14)),
+            (date(2025, 8, 1), date(2025, 9, 1))
+        );
+        assert_eq!(
+            PeriodType::MONTHLY.bounds(date(2025, 12, 31)),
+            (date(2025, 12, 1), date(2026, 1, 1))
+        );
+    }
+}
@@ -0,0 +1,51 @@
+use crate::utils::error::LedgerError;
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
+use chrono_tz::{America::Sao_Paulo, Tz};
+
+/// Business days follow Brasília local time, whatever the server time zone is.
+pub const BUSINESS_TZ: Tz = Sao_Paulo;
+pub const BUSINESS_TZ_NAME: &str = "America/Sao_Paulo";
+
+pub fn business_date(at: DateTime<Utc>) -> NaiveDate {
+    at.with_timezone(&BUSINESS_TZ).date_naive()
+}
+
+/// First instant of a business day, in UTC.
+pub fn day_start(date: NaiveDate) -> DateTime<Utc> {
+    local_to_utc(date.and_time(NaiveTime::MIN))
+}
+
+/// Last representable instant (microsecond precision) of a business day, in UTC.
+pub fn day_end(date: NaiveDate) -> DateTime<Utc> {
+    day_start(date + Duration::days(1)) - Duration::microseconds(1)
+}
+
+/// Accepts an RFC 3339 timestamp, a local Brasília date-time
+/// (`2025-08-14T23:59:59`) or a bare date, which means the end of that day.
+pub fn parse_as_of(value: &str) -> Result<DateTime<Utc>, LedgerError> {
+    if let Ok(at) = DateTime::parse_from_rfc3339(value) {
+        return Ok(at.with_timezone(&Utc));
+    }
+
+    for format in ["%Y-%m-%dT%H:%M:%S%.f", "%Y-%m-%d %H:%M:%S%.f"] {
+        if let Ok(local) = NaiveDateTime::parse_from_str(value, format) {
+            return Ok(local_to_utc(local));
+        }
+    }
+
+    parse_date(value).map(day_end)
+}
+
+pub fn parse_date(value: &str) -> Result<NaiveDate, LedgerError> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map_err(|_| LedgerError::Invalid(format!("Invalid date: {}", value)))
+}
+
+fn local_to_utc(local: NaiveDateTime) -> DateTime<Utc> {
+    match BUSINESS_TZ.from_local_datetime(&local).earliest() {
+        Some(at) => at.with_timezone(&Utc),
+        // Local times skipped by the old daylight saving rules (clocks jumped
+        // at midnight) are read with the standard -03:00 offset.
+        None => Utc.from_utc_datetime(&(local + Duration::hours(3))),
+    }
+}
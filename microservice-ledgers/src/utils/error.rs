@@ -0,0 +1,100 @@
+use rocket::{
+    http::Status,
+    response::{self, Responder},
+    serde::json::Json,
+    Request, Response,
+};
+use serde::Serialize;
+use uuid::Uuid;
+
+#[derive(Debug)]
+pub enum LedgerError {
+    NotFound(String),
+    Invalid(String),
+    Conflict(String),
+    InsufficientFunds(String),
+    AccountBlocked(String),
+    Messaging(String),
+    Storage(String),
+    Database(tokio_postgres::Error),
+}
+
+#[derive(Debug, Serialize)]
+pub struct ErrorResponse {
+    pub error: String,
+    pub message: String,
+}
+
+impl std::fmt::Display for LedgerError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LedgerError::NotFound(msg) => write!(f, "{}", msg),
+            LedgerError::Invalid(msg) => write!(f, "{}", msg),
+            LedgerError::Conflict(msg) => write!(f, "{}", msg),
+            LedgerError::InsufficientFunds(msg) => write!(f, "{}", msg),
+            LedgerError::AccountBlocked(msg) => write!(f, "{}", msg),
+            LedgerError::Messaging(msg) => write!(f, "Messaging error: {}", msg),
+            LedgerError::Storage(msg) => write!(f, "Storage error: {}", msg),
+            LedgerError::Database(e) => write!(f, "Database error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for LedgerError {}
+
+impl From<tokio_postgres::Error> for LedgerError {
+    fn from(e: tokio_postgres::Error) -> Self {
+        LedgerError::Database(e)
+    }
+}
+
+impl From<std::io::Error> for LedgerError {
+    fn from(e: std::io::Error) -> Self {
+        LedgerError::Storage(e.to_string())
+    }
+}
+
+impl LedgerError {
+    fn status_and_code(&self) -> (Status, &'static str) {
+        match self {
+            LedgerError::NotFound(_) => (Status::NotFound, "not_found"),
+            LedgerError::Invalid(_) => (Status::BadRequest, "api_error"),
+            LedgerError::Conflict(_) => (Status::Conflict, "conflict"),
+            LedgerError::InsufficientFunds(_) => {
+                (Status::UnprocessableEntity, "insufficient_funds")
+            }
+            LedgerError::AccountBlocked(_) => (Status::UnprocessableEntity, "account_blocked"),
+            LedgerError::Messaging(_) => (Status::ServiceUnavailable, "messaging_error"),
+            LedgerError::Storage(_) => (Status::InternalServerError, "storage_error"),
+            LedgerError::Database(_) => (Status::InternalServerError, "database_error"),
+        }
+    }
+}
+
+impl From<&LedgerError> for ErrorResponse {
+    fn from(e: &LedgerError) -> Self {
+        ErrorResponse {
+            error: e.status_and_code().1.to_string(),
+            message: e.to_string(),
+        }
+    }
+}
+
+impl<'r> Responder<'r, 'static> for LedgerError {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'static> {
+        if let LedgerError::Database(ref e) = self {
+            eprintln!("Database error: {}", e);
+        }
+
+        let status = self.status_and_code().0;
+        let body = Json(ErrorResponse::from(&self));
+
+        Response::build_from(body.respond_to(req)?)
+            .status(status)
+            .ok()
+    }
+}
+
+pub fn parse_uuid(value: &str) -> Result<Uuid, LedgerError> {
+    Uuid::parse_str(value).map_err(|_| LedgerError::Invalid(format!("Invalid UUID: {}", value)))
+}
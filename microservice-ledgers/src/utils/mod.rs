@@ -1 +1,3 @@
+pub mod business_day;
+pub mod error;
 pub mod sha3;
\ No newline at end of file
@@ -17,4 +17,10 @@ pub fn sha3_256_of_file(path: &str) -> Result<String, std::io::Error> {
 
     let result = hasher.finalize();
     Ok(format!("{:x}", result))
-}
\ No newline at end of file
+}
+
+pub fn sha3_256_hex(data: &[u8]) -> String {
+    let mut hasher = Sha3_256::new();
+    hasher.update(data);
+    format!("{:x}", hasher.finalize())
+}